@@ -1,5 +1,5 @@
 use pinocchio::program_error::ProgramError;
-use crate::api::types::{Discriminator, AccountValidation};
+use crate::types::{Discriminator, AccountValidation};
 
 #[macro_export]
 macro_rules! impl_to_bytes {
@@ -17,13 +17,13 @@ macro_rules! account {
     ($discriminator_name:ident, $struct_name:ident) => {
         $crate::impl_to_bytes!($struct_name);
 
-        impl $crate::api::types::Discriminator for $struct_name {
+        impl $crate::types::Discriminator for $struct_name {
             fn discriminator() -> u8 {
                 $discriminator_name::$struct_name.into()
             }
         }
 
-        impl $crate::api::types::AccountValidation for $struct_name {
+        impl $crate::types::AccountValidation for $struct_name {
             #[track_caller]
             fn assert<F>(
                 &self,
@@ -116,3 +116,41 @@ macro_rules! account {
         }
     };
 }
+
+#[macro_export]
+macro_rules! event {
+    ($discriminator_name:ident, $struct_name:ident) => {
+        $crate::impl_to_bytes!($struct_name);
+
+        const _: () = assert!(
+            core::mem::size_of::<$struct_name>() <= $crate::consts::MAX_EVENT_SIZE,
+            "event does not fit in an EmitEvent instruction"
+        );
+
+        impl $crate::types::Discriminator for $struct_name {
+            fn discriminator() -> u8 {
+                $discriminator_name::$struct_name.into()
+            }
+        }
+
+        impl $struct_name {
+            /// Emit this event as `[discriminator, bytes]` through `sol_log_data`.
+            pub fn log(&self) {
+                let discriminator =
+                    [<Self as $crate::types::Discriminator>::discriminator()];
+                pinocchio::log::sol_log_data(&[&discriminator, self.to_bytes()]);
+            }
+
+            /// Emit this event through whichever channel the instruction selected.
+            pub fn emit(
+                &self,
+                channel: &$crate::event::EventChannel,
+            ) -> pinocchio::ProgramResult {
+                channel.emit(
+                    <Self as $crate::types::Discriminator>::discriminator(),
+                    self.to_bytes(),
+                )
+            }
+        }
+    };
+}
@@ -17,6 +17,7 @@ pub const TAPE:     &[u8] = b"tape";
 pub const TREASURY: &[u8] = b"treasury";
 pub const MINT:     &[u8] = b"mint";
 pub const METADATA: &[u8] = b"metadata";
+pub const EVENT_AUTHORITY: &[u8] = b"__event_authority";
 
 /// Mint PDA seed (raw bytes)
 pub const MINT_SEED: &[u8] = &[152, 68, 212, 200, 25, 113, 221, 71];
@@ -42,6 +43,13 @@ pub const TAPE_TREE_HEIGHT: usize = 10;
 /// Number of hashes in a Merkle proof for the tape tree
 pub const TAPE_PROOF_LEN: usize = TAPE_TREE_HEIGHT;
 
+/// Deepest level of the segment tree a tape account may cache after its
+/// fixed fields (levels 1..=depth, 2^(depth+1) - 2 nodes). Bounded by the
+/// 10 KiB an account created through CPI may hold.
+pub const MAX_SUBTREE_DEPTH: u64 = 7;
+/// Largest number of cached subtree nodes a tape account can hold
+pub const MAX_SUBTREE_NODES: usize = (1 << (MAX_SUBTREE_DEPTH + 1)) - 2;
+
 // ====================================================================
 // Sizing
 // ====================================================================
@@ -54,6 +62,13 @@ pub const PACKED_SEGMENT_SIZE: usize = 152; // packx::SOLUTION_SIZE
 pub const MAX_SEGMENTS_PER_TAPE: usize = 1 << SEGMENT_TREE_HEIGHT - 1;
 /// Maximum number of tapes in a spool
 pub const MAX_TAPES_PER_SPOOL: usize = 1 << TAPE_TREE_HEIGHT - 1;
+/// Maximum number of segments in one batched tape update
+pub const MAX_BATCH_UPDATES: usize = 16;
+/// Number of segments past the end of a tape that can be written out of
+/// order (one pending leaf slot each in the writer account)
+pub const WRITE_WINDOW: usize = 256;
+/// Maximum number of pending segments moved into the tree per instruction
+pub const MAX_DRAIN_PER_IX: usize = 64;
 
 // ====================================================================
 // Token Economics
@@ -106,6 +121,8 @@ pub const EMPTY_PROOF: [[u8; 32]; SEGMENT_PROOF_LEN] = [[0; 32]; SEGMENT_PROOF_L
 pub const NAME_LEN:   usize = 32;
 /// Header size in bytes
 pub const HEADER_SIZE: usize = 64;
+/// Maximum event payload in bytes (excluding discriminators)
+pub const MAX_EVENT_SIZE: usize = 256;
 
 // ====================================================================
 // Const Addresses
@@ -145,6 +162,12 @@ pub const TREASURY_ADDRESS: Pubkey =
 pub const TREASURY_BUMP: u8 = 
     ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).1;
 
+pub const EVENT_AUTHORITY_ADDRESS: Pubkey =
+    ed25519::derive_program_address(&[EVENT_AUTHORITY], &PROGRAM_ID).0;
+
+pub const EVENT_AUTHORITY_BUMP: u8 =
+    ed25519::derive_program_address(&[EVENT_AUTHORITY], &PROGRAM_ID).1;
+
 pub const TREASURY_ATA: Pubkey = 
     ed25519::derive_program_address(
         &[
@@ -166,3 +189,13 @@ pub const TREASURY_ATA_BUMP: u8 =
         unsafe { &*(&pinocchio_associated_token_account::id() as *const Pubkey as *const [u8; 32]) },
     )
     .1;
+
+/// mpl_token_metadata (metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s)
+pub const MPL_TOKEN_METADATA_ID: Pubkey = [
+    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108, 115,
+    26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
+];
+
+/// SlotHashes sysvar (SysvarS1otHashes111111111111111111111111111)
+pub const SLOT_HASHES_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarS1otHashes111111111111111111111111111");
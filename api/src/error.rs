@@ -0,0 +1,223 @@
+//! Program error codes.
+//!
+//! Every error the program returns through `ProgramError::Custom` comes from
+//! `TapeError`. Codes are grouped by subsystem and are stable: never renumber
+//! a variant, only append within its range.
+//!
+//! | Range         | Subsystem                                   |
+//! | ------------- | ------------------------------------------- |
+//! | `0x00..=0x0F` | General (instruction data, accounts, PDAs)  |
+//! | `0x10..=0x1F` | Tapes (create, write, update, finalize)     |
+//! | `0x20..=0x2F` | Mining (mine, claim, register)              |
+//! | `0x30..=0x3F` | Spools (pack, unpack, commit)               |
+
+use pinocchio::program_error::ProgramError;
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, shank::ShankType)]
+pub enum TapeError {
+    // The instruction data has the wrong length or layout
+    InvalidInstructionData  = 0x01,
+    // The provided account does not match the expected PDA
+    PdaMismatch             = 0x02,
+    // The provided account is not owned by the expected program
+    InvalidOwner            = 0x03,
+
+    // The provided tape is in an unexpected state
+    UnexpectedState         = 0x10,
+    // The tape write failed
+    WriteFailed             = 0x11,
+    // The tape is too long
+    TapeTooLong             = 0x12,
+    // The tape does not have enough rent
+    InsufficientRent        = 0x13,
+    // The requested subtree depth is larger than the tape can cache
+    InvalidSubtreeDepth     = 0x14,
+    // A shortened proof needs a subtree node the tape has not cached
+    SubtreeNotCached        = 0x15,
+    // The proof is longer than the tree or shorter than the cache allows
+    InvalidProofLength      = 0x16,
+    // The tape still has pending segments after a gap
+    TapeHasHoles            = 0x17,
+    // The segment was already written
+    SegmentAlreadyWritten   = 0x18,
+    // The segment is outside the writer's out-of-order window
+    SegmentOutOfWindow      = 0x19,
+
+    // The provided hash is invalid
+    SolutionInvalid         = 0x20,
+    // The provided tape doesn't match the expected tape
+    UnexpectedTape          = 0x21,
+    // The provided hash did not satisfy the minimum required difficulty
+    SolutionTooEasy         = 0x22,
+    // The provided solution is too early
+    SolutionTooEarly        = 0x23,
+    // The provided claim is too large
+    ClaimTooLarge           = 0x24,
+
+    // Faild to pack the tape into the spool
+    SpoolPackFailed         = 0x30,
+    // Failed to unpack the tape from the spool
+    SpoolUnpackFailed       = 0x31,
+    // Too many tapes in the spool
+    SpoolTooManyTapes       = 0x32,
+    // Spool commit failed
+    SpoolCommitFailed       = 0x33,
+}
+
+impl TapeError {
+    /// Every variant, in code order.
+    pub const ALL: [TapeError; 22] = [
+        TapeError::InvalidInstructionData,
+        TapeError::PdaMismatch,
+        TapeError::InvalidOwner,
+        TapeError::UnexpectedState,
+        TapeError::WriteFailed,
+        TapeError::TapeTooLong,
+        TapeError::InsufficientRent,
+        TapeError::InvalidSubtreeDepth,
+        TapeError::SubtreeNotCached,
+        TapeError::InvalidProofLength,
+        TapeError::TapeHasHoles,
+        TapeError::SegmentAlreadyWritten,
+        TapeError::SegmentOutOfWindow,
+        TapeError::SolutionInvalid,
+        TapeError::UnexpectedTape,
+        TapeError::SolutionTooEasy,
+        TapeError::SolutionTooEarly,
+        TapeError::ClaimTooLarge,
+        TapeError::SpoolPackFailed,
+        TapeError::SpoolUnpackFailed,
+        TapeError::SpoolTooManyTapes,
+        TapeError::SpoolCommitFailed,
+    ];
+
+    /// Decode a `ProgramError::Custom` code. Returns `None` for codes the
+    /// program never emits.
+    pub const fn from_code(code: u32) -> Option<TapeError> {
+        Some(match code {
+            0x01 => TapeError::InvalidInstructionData,
+            0x02 => TapeError::PdaMismatch,
+            0x03 => TapeError::InvalidOwner,
+
+            0x10 => TapeError::UnexpectedState,
+            0x11 => TapeError::WriteFailed,
+            0x12 => TapeError::TapeTooLong,
+            0x13 => TapeError::InsufficientRent,
+            0x14 => TapeError::InvalidSubtreeDepth,
+            0x15 => TapeError::SubtreeNotCached,
+            0x16 => TapeError::InvalidProofLength,
+            0x17 => TapeError::TapeHasHoles,
+            0x18 => TapeError::SegmentAlreadyWritten,
+            0x19 => TapeError::SegmentOutOfWindow,
+
+            0x20 => TapeError::SolutionInvalid,
+            0x21 => TapeError::UnexpectedTape,
+            0x22 => TapeError::SolutionTooEasy,
+            0x23 => TapeError::SolutionTooEarly,
+            0x24 => TapeError::ClaimTooLarge,
+
+            0x30 => TapeError::SpoolPackFailed,
+            0x31 => TapeError::SpoolUnpackFailed,
+            0x32 => TapeError::SpoolTooManyTapes,
+            0x33 => TapeError::SpoolCommitFailed,
+
+            _ => return None,
+        })
+    }
+
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            TapeError::InvalidInstructionData => "InvalidInstructionData",
+            TapeError::PdaMismatch => "PdaMismatch",
+            TapeError::InvalidOwner => "InvalidOwner",
+
+            TapeError::UnexpectedState => "UnexpectedState",
+            TapeError::WriteFailed => "WriteFailed",
+            TapeError::TapeTooLong => "TapeTooLong",
+            TapeError::InsufficientRent => "InsufficientRent",
+            TapeError::InvalidSubtreeDepth => "InvalidSubtreeDepth",
+            TapeError::SubtreeNotCached => "SubtreeNotCached",
+            TapeError::InvalidProofLength => "InvalidProofLength",
+            TapeError::TapeHasHoles => "TapeHasHoles",
+            TapeError::SegmentAlreadyWritten => "SegmentAlreadyWritten",
+            TapeError::SegmentOutOfWindow => "SegmentOutOfWindow",
+
+            TapeError::SolutionInvalid => "SolutionInvalid",
+            TapeError::UnexpectedTape => "UnexpectedTape",
+            TapeError::SolutionTooEasy => "SolutionTooEasy",
+            TapeError::SolutionTooEarly => "SolutionTooEarly",
+            TapeError::ClaimTooLarge => "ClaimTooLarge",
+
+            TapeError::SpoolPackFailed => "SpoolPackFailed",
+            TapeError::SpoolUnpackFailed => "SpoolUnpackFailed",
+            TapeError::SpoolTooManyTapes => "SpoolTooManyTapes",
+            TapeError::SpoolCommitFailed => "SpoolCommitFailed",
+        }
+    }
+}
+
+/// Name of a custom error code, for logging. Unknown codes map to
+/// `"UnknownError"`.
+pub const fn error_name(code: u32) -> &'static str {
+    match TapeError::from_code(code) {
+        Some(e) => e.name(),
+        None => "UnknownError",
+    }
+}
+
+impl From<TapeError> for ProgramError {
+    fn from(e: TapeError) -> Self {
+        Self::Custom(e as u32)
+    }
+}
+
+impl TryFrom<u32> for TapeError {
+    type Error = u32;
+
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        TapeError::from_code(code).ok_or(code)
+    }
+}
+
+impl core::fmt::Display for TapeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} (0x{:x})", self.name(), self.code())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip() {
+        for e in TapeError::ALL {
+            assert_eq!(TapeError::from_code(e.code()), Some(e));
+            assert_eq!(error_name(e.code()), e.name());
+        }
+    }
+
+    #[test]
+    fn codes_stay_in_their_range() {
+        assert!(TapeError::InvalidOwner.code() <= 0x0F);
+        assert!((0x10..=0x1F).contains(&TapeError::InsufficientRent.code()));
+        assert!((0x20..=0x2F).contains(&TapeError::ClaimTooLarge.code()));
+        assert!((0x30..=0x3F).contains(&TapeError::SpoolCommitFailed.code()));
+    }
+
+    #[test]
+    fn unknown_codes_are_rejected() {
+        assert_eq!(TapeError::from_code(0x00), None);
+        assert_eq!(TapeError::from_code(0x04), None);
+        assert_eq!(TapeError::try_from(0xFFFF), Err(0xFFFF));
+        assert_eq!(error_name(0xFFFF), "UnknownError");
+    }
+}
@@ -0,0 +1,185 @@
+use bytemuck::{Pod, Zeroable};
+use num_enum::TryFromPrimitive;
+use crate::consts::*;
+use crate::instruction::TapeInstruction;
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
+pub enum EventType {
+    Unknown = 0,
+
+    WriteEvent,
+    UpdateEvent,
+    FinalizeEvent,
+
+    MineEvent,
+    ClaimEvent,
+
+    PackEvent,
+    UnpackEvent,
+    CommitEvent,
+}
+
+impl Into<u8> for EventType {
+    fn into(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Delivery channel for program events.
+///
+/// Processors build one from their trailing accounts and call `emit` on each
+/// event; whether the event ends up in the logs or in an inner instruction is
+/// decided by the accounts the client passed in.
+pub enum EventChannel<'a> {
+    /// Events are written with `sol_log_data`. Cheap, but the runtime may
+    /// truncate logs under load.
+    Log,
+    /// Events are written as the data of a self-CPI (`EmitEvent`) signed by
+    /// the event authority PDA, so they survive log truncation and cannot be
+    /// forged by other programs.
+    Cpi {
+        event_authority: &'a AccountInfo,
+        program: &'a AccountInfo,
+    },
+}
+
+impl<'a> EventChannel<'a> {
+    /// Pick the channel from the trailing `[event_authority, program]`
+    /// accounts. Falls back to `Log` when they are not present.
+    pub fn from_accounts(remaining: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        match remaining {
+            [event_authority, program, ..] if event_authority.key() == &EVENT_AUTHORITY_ADDRESS => {
+                if program.key() != &crate::ID {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+                Ok(Self::Cpi {
+                    event_authority,
+                    program,
+                })
+            }
+            _ => Ok(Self::Log),
+        }
+    }
+
+    pub fn emit(&self, discriminator: u8, payload: &[u8]) -> ProgramResult {
+        match self {
+            Self::Log => {
+                pinocchio::log::sol_log_data(&[&[discriminator], payload]);
+                Ok(())
+            }
+            Self::Cpi {
+                event_authority,
+                program,
+            } => {
+                // (`event!` keeps every event under the limit at compile time)
+                if payload.len() > MAX_EVENT_SIZE {
+                    return Err(ProgramError::InvalidArgument);
+                }
+
+                let mut data = [0u8; MAX_EVENT_SIZE + 2];
+                data[0] = TapeInstruction::EmitEvent as u8;
+                data[1] = discriminator;
+                data[2..payload.len() + 2].copy_from_slice(payload);
+
+                let instruction = Instruction {
+                    program_id: program.key(),
+                    data: &data[..payload.len() + 2],
+                    accounts: &[AccountMeta::readonly_signer(event_authority.key())],
+                };
+
+                let bump_binding = [EVENT_AUTHORITY_BUMP];
+                let signer_seeds = [Seed::from(EVENT_AUTHORITY), Seed::from(&bump_binding)];
+
+                invoke_signed(
+                    &instruction,
+                    &[*event_authority],
+                    &[Signer::from(&signer_seeds[..])],
+                )
+            }
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct WriteEvent {
+    pub num_added: u64,
+    pub num_total: u64,
+    pub prev_slot: u64,
+    pub address: [u8; 32],
+    /// Index of the first segment written
+    pub segment_number: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UpdateEvent {
+    pub segment_number: u64,
+    pub prev_slot: u64,
+    pub address: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct FinalizeEvent {
+    pub tape: u64,
+    pub address: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MineEvent {
+    pub tape: u64,
+    pub segment: u64,
+    pub reward: u64,
+    pub miner: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ClaimEvent {
+    pub amount: u64,
+    pub miner: [u8; 32],
+    pub beneficiary: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct PackEvent {
+    pub tape: u64,
+    pub total_tapes: u64,
+    pub spool: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct UnpackEvent {
+    pub tape: u64,
+    pub total_tapes: u64,
+    pub spool: [u8; 32],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CommitEvent {
+    pub tape: u64,
+    pub miner: [u8; 32],
+    pub spool: [u8; 32],
+}
+
+event!(EventType, WriteEvent);
+event!(EventType, UpdateEvent);
+event!(EventType, FinalizeEvent);
+event!(EventType, MineEvent);
+event!(EventType, ClaimEvent);
+event!(EventType, PackEvent);
+event!(EventType, UnpackEvent);
+event!(EventType, CommitEvent);
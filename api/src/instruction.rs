@@ -0,0 +1,478 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+
+use crate::consts::*;
+use crate::state::utils::DataLen;
+use crate::types::{PoA, PoW};
+
+/// Instruction discriminators, the first byte of every instruction.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TapeInstruction {
+    // ProgramInstruction variants
+    Unknown = 0,
+    Initialize = 1, // ProgramInstruction::Initialize
+    Airdrop = 2,    // ProgramInstruction::Airdrop
+
+    // TapeInstruction variants
+    TapeCreate = 0x10,    // TapeInstruction::Create = 0x10
+    TapeWrite = 0x11,     // TapeInstruction::Write
+    TapeUpdate = 0x12,    // TapeInstruction::Update
+    TapeFinalize = 0x13,  // TapeInstruction::Finalize
+    TapeSetHeader = 0x14, // TapeInstruction::SetHeader
+    TapeSubsidize = 0x15, // TapeInstruction::Subsidize
+    TapeUpdateBatch = 0x16, // TapeInstruction::UpdateBatch
+    TapeWriteAt = 0x17,   // TapeInstruction::WriteAt
+
+    // MinerInstruction variants
+    MinerRegister = 0x20,   // MinerInstruction::Register = 0x20
+    MinerUnregister = 0x21, // MinerInstruction::Unregister
+    MinerMine = 0x22,       // MinerInstruction::Mine
+    MinerClaim = 0x23,      // MinerInstruction::Claim
+
+    // SpoolInstruction variants
+    SpoolCreate = 0x40,  // SpoolInstruction::Create = 0x40
+    SpoolDestroy = 0x41, // SpoolInstruction::Destroy
+    SpoolPack = 0x42,    // SpoolInstruction::Pack
+    SpoolUnpack = 0x43,  // SpoolInstruction::Unpack
+    SpoolCommit = 0x44,  // SpoolInstruction::Commit
+
+    // Reserved for the self-CPI event channel
+    EmitEvent = 0xE0,
+}
+
+impl TryFrom<&u8> for TapeInstruction {
+    type Error = ProgramError;
+
+    fn try_from(value: &u8) -> Result<Self, Self::Error> {
+        match *value {
+            // ProgramInstruction variants
+            0 => Ok(TapeInstruction::Unknown),
+            1 => Ok(TapeInstruction::Initialize),
+            2 => Ok(TapeInstruction::Airdrop),
+
+            // TapeInstruction variants
+            0x10 => Ok(TapeInstruction::TapeCreate),
+            0x11 => Ok(TapeInstruction::TapeWrite),
+            0x12 => Ok(TapeInstruction::TapeUpdate),
+            0x13 => Ok(TapeInstruction::TapeFinalize),
+            0x14 => Ok(TapeInstruction::TapeSetHeader),
+            0x15 => Ok(TapeInstruction::TapeSubsidize),
+            0x16 => Ok(TapeInstruction::TapeUpdateBatch),
+            0x17 => Ok(TapeInstruction::TapeWriteAt),
+
+            // MinerInstruction variants
+            0x20 => Ok(TapeInstruction::MinerRegister),
+            0x21 => Ok(TapeInstruction::MinerUnregister),
+            0x22 => Ok(TapeInstruction::MinerMine),
+            0x23 => Ok(TapeInstruction::MinerClaim),
+
+            // SpoolInstruction variants
+            0x40 => Ok(TapeInstruction::SpoolCreate),
+            0x41 => Ok(TapeInstruction::SpoolDestroy),
+            0x42 => Ok(TapeInstruction::SpoolPack),
+            0x43 => Ok(TapeInstruction::SpoolUnpack),
+            0x44 => Ok(TapeInstruction::SpoolCommit),
+
+            // Reserved
+            0xE0 => Ok(TapeInstruction::EmitEvent),
+
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct InitializeIxData {}
+
+impl DataLen for InitializeIxData {
+    const LEN: usize = core::mem::size_of::<InitializeIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct AirdropIxData {
+    pub amount: u64,
+}
+
+impl DataLen for AirdropIxData {
+    const LEN: usize = core::mem::size_of::<AirdropIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct CreateTapeIxData {
+    #[idl_type("[u8; 32]")]
+    pub name: [u8; NAME_LEN],
+    /// Levels of the segment tree cached in the tape account (0 disables
+    /// the cache, at most `MAX_SUBTREE_DEPTH`).
+    pub subtree_depth: u64,
+}
+
+impl DataLen for CreateTapeIxData {
+    const LEN: usize = core::mem::size_of::<CreateTapeIxData>();
+}
+
+/// Header of a `TapeWrite` instruction. The segment bytes follow it and are
+/// split into `SEGMENT_SIZE` chunks, the last one zero-padded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct WriteTapeIxData {}
+
+impl DataLen for WriteTapeIxData {
+    const LEN: usize = core::mem::size_of::<WriteTapeIxData>();
+}
+
+/// Header of a `TapeUpdate` instruction. The Merkle proof follows it, leaf
+/// side first: `SEGMENT_PROOF_LEN` hashes, or as few as
+/// `SEGMENT_PROOF_LEN - subtree_depth` when the tape caches the rest.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct UpdateTapeIxData {
+    pub segment_number: u64,
+    #[idl_type("[u8; 128]")]
+    pub old_data: [u8; SEGMENT_SIZE],
+    #[idl_type("[u8; 128]")]
+    pub new_data: [u8; SEGMENT_SIZE],
+}
+
+impl DataLen for UpdateTapeIxData {
+    const LEN: usize = core::mem::size_of::<UpdateTapeIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct FinalizeTapeIxData {}
+
+impl DataLen for FinalizeTapeIxData {
+    const LEN: usize = core::mem::size_of::<FinalizeTapeIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct SetTapeHeaderIxData {
+    #[idl_type("[u8; 64]")]
+    pub header: [u8; HEADER_SIZE],
+}
+
+impl DataLen for SetTapeHeaderIxData {
+    const LEN: usize = core::mem::size_of::<SetTapeHeaderIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct SubsidizeTapeIxData {
+    pub amount: u64,
+}
+
+impl DataLen for SubsidizeTapeIxData {
+    const LEN: usize = core::mem::size_of::<SubsidizeTapeIxData>();
+}
+
+/// Header of a `TapeUpdateBatch` instruction. It is followed by `count`
+/// `SegmentUpdate`s, sorted by segment number, and then by the multiproof
+/// for those segments (see `build_multiproof`).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct UpdateTapeBatchIxData {
+    pub count: u64,
+}
+
+impl DataLen for UpdateTapeBatchIxData {
+    const LEN: usize = core::mem::size_of::<UpdateTapeBatchIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct SegmentUpdate {
+    pub segment_number: u64,
+    #[idl_type("[u8; 128]")]
+    pub old_data: [u8; SEGMENT_SIZE],
+    #[idl_type("[u8; 128]")]
+    pub new_data: [u8; SEGMENT_SIZE],
+}
+
+impl DataLen for SegmentUpdate {
+    const LEN: usize = core::mem::size_of::<SegmentUpdate>();
+}
+
+/// Header of a `TapeWriteAt` instruction. The segment bytes follow it, as in
+/// `TapeWrite`, and land at `segment_number` onwards. Segments may arrive in
+/// any order as long as they fall within `WRITE_WINDOW` of the end of the
+/// tape; they join the Merkle tree once every segment before them has.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct WriteTapeAtIxData {
+    pub segment_number: u64,
+}
+
+impl DataLen for WriteTapeAtIxData {
+    const LEN: usize = core::mem::size_of::<WriteTapeAtIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct RegisterMinerIxData {
+    pub name: [u8; 32],
+}
+
+impl DataLen for RegisterMinerIxData {
+    const LEN: usize = core::mem::size_of::<RegisterMinerIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct UnregisterMinerIxData {}
+
+impl DataLen for UnregisterMinerIxData {
+    const LEN: usize = core::mem::size_of::<UnregisterMinerIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, shank::ShankType)]
+pub struct MineIxData {
+    pub pow: PoW,
+    pub poa: PoA,
+}
+
+impl DataLen for MineIxData {
+    const LEN: usize = core::mem::size_of::<MineIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct ClaimIxData {
+    pub amount: u64,
+}
+
+impl DataLen for ClaimIxData {
+    const LEN: usize = core::mem::size_of::<ClaimIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct CreateSpoolIxData {
+    pub number: u64,
+}
+
+impl DataLen for CreateSpoolIxData {
+    const LEN: usize = core::mem::size_of::<CreateSpoolIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct DestroySpoolIxData {}
+
+impl DataLen for DestroySpoolIxData {
+    const LEN: usize = core::mem::size_of::<DestroySpoolIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct PackSpoolIxData {
+    pub value: [u8; 32],
+}
+
+impl DataLen for PackSpoolIxData {
+    const LEN: usize = core::mem::size_of::<PackSpoolIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct UnpackSpoolIxData {
+    pub index: u64,
+    #[idl_type("[[u8; 32]; 10]")]
+    pub proof: [[u8; 32]; TAPE_PROOF_LEN],
+    pub value: [u8; 32],
+}
+
+impl DataLen for UnpackSpoolIxData {
+    const LEN: usize = core::mem::size_of::<UnpackSpoolIxData>();
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable, shank::ShankType)]
+pub struct CommitSpoolIxData {
+    pub index: u64,
+    #[idl_type("[[u8; 32]; 10]")]
+    pub proof: [[u8; 32]; TAPE_PROOF_LEN],
+    pub value: [u8; 32],
+}
+
+impl DataLen for CommitSpoolIxData {
+    const LEN: usize = core::mem::size_of::<CommitSpoolIxData>();
+}
+
+/// Account metadata for the IDL, read by `shank idl` (see `gen-client.sh`).
+/// The discriminants must match `TapeInstruction`.
+///
+/// Instructions that read past their fixed header (`load_ix_data_with_tail`)
+/// list the trailing data as `Vec` fields. The client generator reads the
+/// last `Vec` to the end of the data and counts any earlier one with a
+/// leading u64 (see `client/generate-client.ts`).
+#[cfg(feature = "std")]
+mod idl_gen {
+    use super::*;
+    use std::vec::Vec;
+
+    #[repr(u8)]
+    #[allow(dead_code)]
+    #[derive(shank::ShankInstruction)]
+    enum _TapeInstruction {
+        #[account(0, writable, signer, name = "signer", desc = "Payer and initializer")]
+        #[account(1, writable, name = "archive", desc = "Archive PDA")]
+        #[account(2, writable, name = "epoch", desc = "Epoch PDA")]
+        #[account(3, writable, name = "block", desc = "Block PDA")]
+        #[account(4, writable, name = "metadata", desc = "TAPE mint metadata PDA")]
+        #[account(5, writable, name = "mint", desc = "TAPE mint PDA")]
+        #[account(6, writable, name = "treasury", desc = "Treasury PDA")]
+        #[account(7, writable, name = "treasury_ata", desc = "Treasury token account")]
+        #[account(8, writable, name = "tape", desc = "Genesis tape PDA")]
+        #[account(9, writable, name = "writer", desc = "Genesis writer PDA")]
+        #[account(10, name = "tape_program", desc = "Tape program")]
+        #[account(11, name = "system_program", desc = "System program")]
+        #[account(12, name = "token_program", desc = "Token program")]
+        #[account(13, name = "associated_token_program", desc = "Associated token program")]
+        #[account(14, name = "metadata_program", desc = "Token metadata program")]
+        #[account(15, name = "rent_sysvar", desc = "Rent sysvar")]
+        #[account(16, name = "slot_hashes", desc = "SlotHashes sysvar")]
+        Initialize(InitializeIxData) = 1,
+
+        #[account(0, writable, signer, name = "signer", desc = "Airdrop authority")]
+        #[account(1, writable, name = "beneficiary", desc = "Recipient token account")]
+        #[account(2, writable, name = "mint", desc = "TAPE mint PDA")]
+        #[account(3, writable, name = "treasury", desc = "Treasury PDA")]
+        #[account(4, name = "token_program", desc = "Token program")]
+        Airdrop(AirdropIxData) = 2,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority and payer")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA")]
+        #[account(3, name = "system_program", desc = "System program")]
+        #[account(4, name = "rent_sysvar", desc = "Rent sysvar")]
+        TapeCreate(CreateTapeIxData) = 0x10,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        TapeWrite { segments: Vec<u8> } = 0x11,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        TapeUpdate { update_tape_ix_data: UpdateTapeIxData, proof: Vec<[u8; 32]> } = 0x12,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority, receives the writer rent")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA, closed")]
+        #[account(3, writable, name = "archive", desc = "Archive PDA")]
+        #[account(4, name = "system_program", desc = "System program")]
+        #[account(5, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(6, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        TapeFinalize(FinalizeTapeIxData) = 0x13,
+
+        #[account(0, signer, name = "signer", desc = "Tape authority")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        TapeSetHeader(SetTapeHeaderIxData) = 0x14,
+
+        #[account(0, signer, name = "signer", desc = "Token owner")]
+        #[account(1, writable, name = "ata", desc = "Source TAPE token account")]
+        #[account(2, writable, name = "tape", desc = "Tape PDA")]
+        #[account(3, writable, name = "treasury_ata", desc = "Treasury token account")]
+        #[account(4, name = "token_program", desc = "Token program")]
+        TapeSubsidize(SubsidizeTapeIxData) = 0x15,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        TapeUpdateBatch { updates: Vec<SegmentUpdate>, proof: Vec<[u8; 32]> } = 0x16,
+
+        #[account(0, writable, signer, name = "signer", desc = "Tape authority")]
+        #[account(1, writable, name = "tape", desc = "Tape PDA")]
+        #[account(2, writable, name = "writer", desc = "Writer PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        TapeWriteAt { write_tape_at_ix_data: WriteTapeAtIxData, segments: Vec<u8> } = 0x17,
+
+        #[account(0, writable, signer, name = "signer", desc = "Miner authority and payer")]
+        #[account(1, writable, name = "miner", desc = "Miner PDA")]
+        #[account(2, name = "rent_sysvar", desc = "Rent sysvar")]
+        #[account(3, name = "slot_hashes", desc = "SlotHashes sysvar")]
+        #[account(4, name = "system_program", desc = "System program")]
+        MinerRegister(RegisterMinerIxData) = 0x20,
+
+        #[account(0, writable, signer, name = "signer", desc = "Miner authority, receives the miner rent")]
+        #[account(1, writable, name = "miner", desc = "Miner PDA, closed")]
+        #[account(2, name = "system_program", desc = "System program")]
+        MinerUnregister(UnregisterMinerIxData) = 0x21,
+
+        #[account(0, signer, name = "signer", desc = "Miner authority")]
+        #[account(1, writable, name = "epoch", desc = "Epoch PDA")]
+        #[account(2, writable, name = "block", desc = "Block PDA")]
+        #[account(3, writable, name = "miner", desc = "Miner PDA")]
+        #[account(4, writable, name = "tape", desc = "Recalled tape PDA")]
+        #[account(5, name = "archive", desc = "Archive PDA")]
+        #[account(6, name = "slot_hashes", desc = "SlotHashes sysvar")]
+        #[account(7, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(8, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        MinerMine {
+            pow: PoW,
+            bump: [u8; 8],
+            seed: [u8; 16],
+            nonce: [u8; 128],
+            path: Vec<[u8; 32]>,
+        } = 0x22,
+
+        #[account(0, signer, name = "signer", desc = "Miner authority")]
+        #[account(1, writable, name = "beneficiary", desc = "Recipient TAPE token account")]
+        #[account(2, writable, name = "miner", desc = "Miner PDA")]
+        #[account(3, name = "treasury", desc = "Treasury PDA")]
+        #[account(4, writable, name = "treasury_ata", desc = "Treasury token account")]
+        #[account(5, name = "token_program", desc = "Token program")]
+        #[account(6, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(7, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        MinerClaim(ClaimIxData) = 0x23,
+
+        #[account(0, writable, signer, name = "signer", desc = "Miner authority and payer")]
+        #[account(1, name = "miner", desc = "Miner PDA")]
+        #[account(2, writable, name = "spool", desc = "Spool PDA")]
+        #[account(3, name = "system_program", desc = "System program")]
+        #[account(4, name = "rent_sysvar", desc = "Rent sysvar")]
+        SpoolCreate(CreateSpoolIxData) = 0x40,
+
+        #[account(0, writable, signer, name = "signer", desc = "Spool authority, receives the spool rent")]
+        #[account(1, writable, name = "spool", desc = "Spool PDA, closed")]
+        #[account(2, name = "system_program", desc = "System program")]
+        SpoolDestroy(DestroySpoolIxData) = 0x41,
+
+        #[account(0, signer, name = "signer", desc = "Spool authority")]
+        #[account(1, writable, name = "spool", desc = "Spool PDA")]
+        #[account(2, name = "tape", desc = "Finalized tape PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        SpoolPack(PackSpoolIxData) = 0x42,
+
+        #[account(0, signer, name = "signer", desc = "Spool authority")]
+        #[account(1, writable, name = "spool", desc = "Spool PDA")]
+        #[account(2, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(3, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        SpoolUnpack(UnpackSpoolIxData) = 0x43,
+
+        #[account(0, signer, name = "signer", desc = "Miner and spool authority")]
+        #[account(1, writable, name = "miner", desc = "Miner PDA")]
+        #[account(2, writable, name = "spool", desc = "Spool PDA")]
+        #[account(3, optional, name = "event_authority", desc = "Event authority PDA (self-CPI events)")]
+        #[account(4, optional, name = "program", desc = "Tape program (self-CPI events)")]
+        SpoolCommit(CommitSpoolIxData) = 0x44,
+
+        #[account(0, signer, name = "event_authority", desc = "Event authority PDA")]
+        EmitEvent = 0xE0,
+    }
+}
@@ -0,0 +1,42 @@
+//! Account layouts, constants, PDAs, events and errors of the tape program.
+//!
+//! `no_std` so the program can build on it; enable `std` for host tools
+//! (miners, indexers, clients) that want the `std`-only helpers.
+
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[macro_use]
+pub mod account;
+pub mod consts;
+pub mod error;
+pub mod event;
+pub mod instruction;
+pub mod loaders;
+pub mod multiproof;
+pub mod pda;
+pub mod rent;
+pub mod state;
+pub mod subtree;
+pub mod types;
+pub mod utils;
+
+pub mod prelude {
+    pub use crate::account::*;
+    pub use crate::consts::*;
+    pub use crate::error::*;
+    pub use crate::event::*;
+    pub use crate::instruction::*;
+    pub use crate::loaders::*;
+    pub use crate::multiproof::*;
+    pub use crate::pda::*;
+    pub use crate::rent::*;
+    pub use crate::state::*;
+    pub use crate::subtree::*;
+    pub use crate::types::*;
+    pub use crate::utils::*;
+}
+
+pinocchio_pubkey::declare_id!("ENrRns55VechXJiq4bMbdx7idzQh7tvaEJoYeWxRNe7Y");
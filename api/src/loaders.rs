@@ -1,4 +1,5 @@
-use crate::api::consts::*;
+use crate::consts::*;
+use crate::error::TapeError;
 use pinocchio::{account_info::AccountInfo, pubkey::Pubkey, program_error::ProgramError};
 
 pub trait AccountInfoLoader {
@@ -7,52 +8,60 @@ pub trait AccountInfoLoader {
     fn is_block(&self) -> Result<&Self, ProgramError>;
     fn is_treasury(&self) -> Result<&Self, ProgramError>;
     fn is_treasury_ata(&self) -> Result<&Self, ProgramError>;
+    fn is_event_authority(&self) -> Result<&Self, ProgramError>;
 }
 
 impl AccountInfoLoader for AccountInfo {
     fn is_archive(&self) -> Result<&Self, ProgramError> {
         if self.key() != &ARCHIVE_ADDRESS {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(TapeError::PdaMismatch.into());
         }
         if self.owner() != &crate::ID {
-            return Err(ProgramError::IllegalOwner);
+            return Err(TapeError::InvalidOwner.into());
         }
         Ok(self)
     }
 
     fn is_epoch(&self) -> Result<&Self, ProgramError> {
         if self.key() != &EPOCH_ADDRESS {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(TapeError::PdaMismatch.into());
         }
         if self.owner() != &crate::ID {
-            return Err(ProgramError::IllegalOwner);
+            return Err(TapeError::InvalidOwner.into());
         }
         Ok(self)
     }
 
     fn is_block(&self) -> Result<&Self, ProgramError> {
         if self.key() != &BLOCK_ADDRESS {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(TapeError::PdaMismatch.into());
         }
         if self.owner() != &crate::ID {
-            return Err(ProgramError::IllegalOwner);
+            return Err(TapeError::InvalidOwner.into());
         }
         Ok(self)
     }
 
     fn is_treasury(&self) -> Result<&Self, ProgramError> {
         if self.key() != &TREASURY_ADDRESS {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(TapeError::PdaMismatch.into());
         }
         if self.owner() != &crate::ID {
-            return Err(ProgramError::IllegalOwner);
+            return Err(TapeError::InvalidOwner.into());
         }
         Ok(self)
     }
 
     fn is_treasury_ata(&self) -> Result<&Self, ProgramError> {
         if self.key() != &TREASURY_ATA {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(TapeError::PdaMismatch.into());
+        }
+        Ok(self)
+    }
+
+    fn is_event_authority(&self) -> Result<&Self, ProgramError> {
+        if self.key() != &EVENT_AUTHORITY_ADDRESS {
+            return Err(TapeError::PdaMismatch.into());
         }
         Ok(self)
     }
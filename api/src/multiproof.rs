@@ -0,0 +1,218 @@
+//! Merkle multiproofs for updating many segments at once.
+//!
+//! A multiproof covers a sorted set of leaves. The verifier walks the tree
+//! one level at a time: two touched nodes that are siblings hash together,
+//! any other touched node takes its sibling from the proof. Hashes shared by
+//! several leaves are therefore sent once, and a run of adjacent segments
+//! needs little more than one proof's worth of hashes.
+//!
+//! Proof order is the order the verifier consumes them: level by level from
+//! the leaves up, left to right within a level.
+
+use crate::consts::*;
+use crate::error::*;
+use crate::subtree::hash_pair;
+use crate::utils::check_condition;
+use pinocchio::program_error::ProgramError;
+
+/// A touched node while folding a multiproof: its position in its level and
+/// its value before and after the update.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BatchNode {
+    pub index: u64,
+    pub old: [u8; 32],
+    pub new: [u8; 32],
+}
+
+/// Fold `nodes` (sorted, unique leaf indices) and `proof` up to the root and
+/// return the `(old, new)` roots. `visit(level, node)` sees every touched
+/// node below the root, leaves first (level 0 is the leaf level).
+///
+/// `nodes` is used as scratch space.
+pub fn fold_multiproof<F>(
+    nodes: &mut [BatchNode],
+    proof: &[[u8; 32]],
+    mut visit: F,
+) -> Result<([u8; 32], [u8; 32]), ProgramError>
+where
+    F: FnMut(usize, &BatchNode),
+{
+    check_condition(!nodes.is_empty(), TapeError::InvalidProofLength)?;
+    check_condition(
+        nodes.windows(2).all(|w| w[0].index < w[1].index),
+        TapeError::InvalidInstructionData,
+    )?;
+
+    let mut proof = proof.iter();
+    let mut len = nodes.len();
+
+    for level in 0..SEGMENT_TREE_HEIGHT {
+        let mut read = 0;
+        let mut write = 0;
+
+        while read < len {
+            let node = nodes[read];
+            visit(level, &node);
+
+            let (old, new) = if read + 1 < len && nodes[read + 1].index == node.index ^ 1 {
+                let sibling = nodes[read + 1];
+                visit(level, &sibling);
+                read += 2;
+                (hash_pair(&node.old, &sibling.old), hash_pair(&node.new, &sibling.new))
+            } else {
+                let sibling = proof.next().ok_or(TapeError::InvalidProofLength)?;
+                read += 1;
+                (hash_pair(&node.old, sibling), hash_pair(&node.new, sibling))
+            };
+
+            nodes[write] = BatchNode { index: node.index >> 1, old, new };
+            write += 1;
+        }
+
+        len = write;
+    }
+
+    check_condition(proof.next().is_none(), TapeError::InvalidProofLength)?;
+
+    Ok((nodes[0].old, nodes[0].new))
+}
+
+/// Build the multiproof for `indices` (sorted, unique) out of each leaf's
+/// full proof. `proof_of(i)` returns the `SEGMENT_PROOF_LEN` proof of leaf
+/// `i`, for example from `MerkleTree::get_merkle_proof`.
+#[cfg(feature = "std")]
+pub fn build_multiproof<F>(indices: &[u64], mut proof_of: F) -> std::vec::Vec<[u8; 32]>
+where
+    F: FnMut(u64) -> [[u8; 32]; SEGMENT_PROOF_LEN],
+{
+    use std::vec::Vec;
+
+    let proofs: Vec<_> = indices.iter().map(|&i| proof_of(i)).collect();
+
+    // (position in level, a leaf below it whose proof to borrow from)
+    let mut level_nodes: Vec<(u64, usize)> = indices.iter().copied().zip(0..).collect();
+    let mut out = Vec::new();
+
+    for level in 0..SEGMENT_TREE_HEIGHT {
+        let mut next = Vec::with_capacity(level_nodes.len());
+        let mut i = 0;
+
+        while i < level_nodes.len() {
+            let (index, leaf) = level_nodes[i];
+            if i + 1 < level_nodes.len() && level_nodes[i + 1].0 == index ^ 1 {
+                i += 2;
+            } else {
+                out.push(proofs[leaf][level]);
+                i += 1;
+            }
+            next.push((index >> 1, leaf));
+        }
+
+        level_nodes = next;
+    }
+
+    out
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::types::SegmentTree;
+    use crate::utils::{compute_leaf, update_segment};
+    use brine_tree::Leaf;
+    use std::vec::Vec;
+
+    fn tree_with(count: u64) -> (SegmentTree, Vec<Leaf>) {
+        let mut tree = SegmentTree::new(&[b"test".as_ref()]);
+        let leaves: Vec<Leaf> = (0..count)
+            .map(|i| compute_leaf(i, &[i as u8; SEGMENT_SIZE]))
+            .collect();
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+        (tree, leaves)
+    }
+
+    fn proof_of(tree: &SegmentTree, leaves: &[Leaf], i: u64) -> [[u8; 32]; SEGMENT_PROOF_LEN] {
+        let proof: Vec<[u8; 32]> = tree
+            .get_merkle_proof(leaves, i as usize)
+            .iter()
+            .map(|h| h.to_bytes())
+            .collect();
+        proof.try_into().unwrap()
+    }
+
+    #[test]
+    fn multiproof_matches_sequential_updates() {
+        let (mut tree, leaves) = tree_with(40);
+        let indices: Vec<u64> = (10..30).collect();
+
+        let proof = build_multiproof(&indices, |i| proof_of(&tree, &leaves, i));
+
+        // Adjacent leaves share almost every hash.
+        assert!(proof.len() < 2 * SEGMENT_PROOF_LEN);
+
+        let mut nodes: Vec<BatchNode> = indices
+            .iter()
+            .map(|&i| BatchNode {
+                index: i,
+                old: leaves[i as usize].to_bytes(),
+                new: compute_leaf(i, &[0xAA; SEGMENT_SIZE]).to_bytes(),
+            })
+            .collect();
+
+        let (old_root, new_root) = fold_multiproof(&mut nodes, &proof, |_, _| {}).unwrap();
+        assert_eq!(old_root, tree.get_root().to_bytes());
+
+        // Apply the same updates one by one with full proofs.
+        let mut current = leaves.clone();
+        for &i in &indices {
+            let proof = proof_of(&tree, &current, i);
+            update_segment(&mut tree, i, &[i as u8; SEGMENT_SIZE], &[0xAA; SEGMENT_SIZE], &proof)
+                .unwrap();
+            current[i as usize] = compute_leaf(i, &[0xAA; SEGMENT_SIZE]);
+        }
+        assert_eq!(new_root, tree.get_root().to_bytes());
+    }
+
+    #[test]
+    fn single_leaf_is_a_full_proof() {
+        let (tree, leaves) = tree_with(3);
+        let proof = build_multiproof(&[1], |i| proof_of(&tree, &leaves, i));
+        assert_eq!(proof, proof_of(&tree, &leaves, 1).to_vec());
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let (tree, leaves) = tree_with(4);
+        let proof = build_multiproof(&[0, 2], |i| proof_of(&tree, &leaves, i));
+        let node = |i: u64| BatchNode {
+            index: i,
+            old: leaves[i as usize].to_bytes(),
+            new: [0; 32],
+        };
+
+        // Unsorted leaves
+        let mut nodes = [node(2), node(0)];
+        assert_eq!(
+            fold_multiproof(&mut nodes, &proof, |_, _| {}),
+            Err(TapeError::InvalidInstructionData.into())
+        );
+
+        // Missing and extra hashes
+        let mut nodes = [node(0), node(2)];
+        assert_eq!(
+            fold_multiproof(&mut nodes, &proof[1..], |_, _| {}),
+            Err(TapeError::InvalidProofLength.into())
+        );
+        let mut extra = proof.clone();
+        extra.push([0; 32]);
+        let mut nodes = [node(0), node(2)];
+        assert_eq!(
+            fold_multiproof(&mut nodes, &extra, |_, _| {}),
+            Err(TapeError::InvalidProofLength.into())
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::api::consts::*;
+use crate::consts::*;
 use pinocchio::pubkey::{Pubkey, find_program_address};
 use pinocchio_associated_token_account;
 
@@ -53,10 +53,10 @@ pub fn treasury_ata() -> (Pubkey, u8) {
     find_program_address(
         &[
             treasury_pda.as_ref(),
-            spl_token::ID.as_ref(),
+            pinocchio_token::ID.as_ref(),
             mint_pda.as_ref(),
         ],
-        &spl_associated_token_account::ID,
+        &pinocchio_associated_token_account::ID,
     )
 }
 
@@ -77,6 +77,17 @@ pub fn mint_pda() -> (Pubkey, u8) {
     (MINT_ADDRESS.into(), MINT_BUMP)
 }
 
+#[cfg(debug_assertions)]
+pub fn event_authority_pda() -> (Pubkey, u8) {
+    find_program_address(&[EVENT_AUTHORITY], &crate::id())
+}
+
+#[cfg(not(debug_assertions))]
+#[inline(always)]
+pub fn event_authority_pda() -> (Pubkey, u8) {
+    (EVENT_AUTHORITY_ADDRESS.into(), EVENT_AUTHORITY_BUMP)
+}
+
 pub fn metadata_pda(mint: Pubkey) -> (Pubkey, u8) {
     find_program_address(
         &[METADATA, MPL_TOKEN_METADATA_ID.as_ref(), mint.as_ref()],
@@ -134,6 +145,10 @@ mod tests {
         assert_eq!(bump, TREASURY_BUMP);
         assert_eq!(pda, TREASURY_ADDRESS);
 
+        let (pda, bump) = event_authority_pda();
+        assert_eq!(bump, EVENT_AUTHORITY_BUMP);
+        assert_eq!(pda, EVENT_AUTHORITY_ADDRESS);
+
         let (pda, _bump) = treasury_ata();
         assert_eq!(pda, TREASURY_ATA);
     }
@@ -1,5 +1,5 @@
-use crate::api::consts::*;
-use crate::api::state::{Tape, Archive};
+use crate::consts::*;
+use crate::state::{Tape, Archive};
 
 const BLOCKS_PER_YEAR: u64 = 60 * 60 * 24 * 365 / BLOCK_DURATION_SECONDS;
 
@@ -22,11 +22,11 @@ impl Initialized for Archive {
 
 impl Archive {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Archive>(data) }
+        load_acc::<Archive>(data)
     }
 
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Archive>(data) }
+        load_acc_mut::<Archive>(data)
     }
 }
 
@@ -29,11 +29,11 @@ impl Initialized for Block {
 
 impl Block {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Block>(data) }
+        load_acc::<Block>(data)
     }
 
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Block>(data) }
+        load_acc_mut::<Block>(data)
     }
 }
 
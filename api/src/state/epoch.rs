@@ -31,11 +31,11 @@ impl Initialized for Epoch {
 
 impl Epoch {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Epoch>(data) }
+        load_acc::<Epoch>(data)
     }
 
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Epoch>(data) }
+        load_acc_mut::<Epoch>(data)
     }
 }
 
@@ -1,6 +1,6 @@
 use super::AccountType;
 use bytemuck::{Pod, Zeroable};
-use crate::api::consts::*;
+use crate::consts::*;
 use crate::state::utils::{DataLen, Initialized, load_acc, load_acc_mut, try_from_account_info_mut};
 use pinocchio::{program_error::ProgramError, ProgramResult, account_info::AccountInfo, pubkey::Pubkey};
 
@@ -36,10 +36,10 @@ impl Initialized for Miner {
 
 impl Miner {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Miner>(data) }
+        load_acc::<Miner>(data)
     }
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Miner>(data) }
+        load_acc_mut::<Miner>(data)
     }
 
     pub fn initialize(
@@ -48,7 +48,7 @@ impl Miner {
         authority: Pubkey,
         challenge: [u8; 32],
     ) -> ProgramResult {
-        let miner_state = unsafe { try_from_account_info_mut::<Miner>(miner_info) }?;
+        let mut miner_state = try_from_account_info_mut::<Miner>(miner_info)?;
 
         miner_state.authority = authority;
         miner_state.name = name;
@@ -0,0 +1,71 @@
+mod archive;
+mod epoch;
+mod block;
+mod tape;
+mod treasury;
+mod writer;
+mod miner;
+mod spool;
+
+pub mod utils;
+
+pub use archive::*;
+pub use epoch::*;
+pub use block::*;
+pub use tape::*;
+pub use treasury::*;
+pub use writer::*;
+pub use miner::*;
+pub use spool::*;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccountType {
+    Unknown = 0,
+    Archive,
+    Spool,
+    Writer,
+    Tape,
+    Miner,
+    Epoch,
+    Block,
+    Treasury,
+}
+
+impl Into<u8> for AccountType {
+    fn into(self) -> u8 {
+        self as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::MAX_SUBTREE_DEPTH;
+    use crate::state::utils::DataLen;
+
+    // Accounts carry no discriminator; the loaders tell them apart by owner
+    // and exact length, so no two layouts may share a size.
+    #[test]
+    fn account_sizes_are_distinct() {
+        let mut sizes = [0usize; 7 + MAX_SUBTREE_DEPTH as usize + 1];
+        sizes[..7].copy_from_slice(&[
+            Archive::LEN,
+            Epoch::LEN,
+            Block::LEN,
+            Treasury::LEN,
+            Miner::LEN,
+            Spool::LEN,
+            writer_account_size(),
+        ]);
+        for depth in 0..=MAX_SUBTREE_DEPTH {
+            sizes[7 + depth as usize] = tape_account_size(depth);
+        }
+
+        for (i, a) in sizes.iter().enumerate() {
+            for b in &sizes[i + 1..] {
+                assert_ne!(a, b, "two account layouts are {a} bytes long");
+            }
+        }
+    }
+}
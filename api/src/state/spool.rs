@@ -1,6 +1,6 @@
 use super::AccountType;
 use bytemuck::{Pod, Zeroable};
-use crate::api::types::*;
+use crate::types::*;
 use crate::state::utils::{DataLen, Initialized, load_acc, load_acc_mut};
 use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
@@ -32,10 +32,10 @@ impl Initialized for Spool {
 
 impl Spool {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Spool>(data) }
+        load_acc::<Spool>(data)
     }
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Spool>(data) }
+        load_acc_mut::<Spool>(data)
     }
 }
 
@@ -0,0 +1,185 @@
+use super::AccountType;
+use bytemuck::{Pod, Zeroable};
+use crate::consts::*;
+use crate::types::*;
+use crate::state::utils::{DataLen, Initialized, load_acc, load_acc_mut};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// A cached node of the segment tree, stored after the fixed `Tape` fields.
+pub type SubtreeNode = [u8; 32];
+
+#[repr(C)] 
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Tape {
+    pub number: u64,
+    pub state: u64,
+
+    pub authority: Pubkey,
+
+    pub name:        [u8; NAME_LEN],
+    pub merkle_seed: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub header:      [u8; HEADER_SIZE],
+
+    pub first_slot:      u64,
+    pub tail_slot:       u64,
+    pub balance:         u64,
+    pub last_rent_block: u64,
+    pub total_segments:  u64,
+
+    // +Trailing [SubtreeNode] for the cached top of the segment tree
+    //  (see `tape_account_size`, up to MAX_SUBTREE_NODES).
+}
+
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TapeState {
+    Unknown = 0,
+    Created,
+    Writing,
+    Finalized,
+}
+
+impl From<u64> for TapeState {
+    fn from(value: u64) -> Self {
+        match value {
+            1 => Self::Created,
+            2 => Self::Writing,
+            3 => Self::Finalized,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl DataLen for Tape {
+    const LEN: usize = core::mem::size_of::<Tape>();
+}
+
+impl Initialized for Tape {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+}
+
+impl Tape {
+    pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
+        decode_tape(data).map(|(tape, _)| tape)
+    }
+    pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        decode_tape_mut(data).map(|(tape, _)| tape)
+    }
+}
+
+account!(AccountType, Tape);
+
+/// Number of nodes cached for a subtree of the given depth (levels 1..=depth
+/// below the root, stored in heap order).
+pub const fn subtree_node_count(depth: u64) -> usize {
+    (1usize << (depth + 1)) - 2
+}
+
+/// Account size of a tape caching a subtree of the given depth.
+pub const fn tape_account_size(depth: u64) -> usize {
+    Tape::LEN + subtree_node_count(depth) * core::mem::size_of::<SubtreeNode>()
+}
+
+/// Whether `len` bytes after the fixed fields hold a whole subtree of some
+/// depth up to `MAX_SUBTREE_DEPTH`. Tapes are told apart from other accounts
+/// by their size, so any other tail length is rejected.
+fn is_subtree_tail(len: usize) -> bool {
+    (0..=MAX_SUBTREE_DEPTH).any(|depth| tape_account_size(depth) == Tape::LEN + len)
+}
+
+fn nodes_from_tail(tail: &[u8]) -> Result<&[SubtreeNode], ProgramError> {
+    if !is_subtree_tail(tail.len()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    bytemuck::try_cast_slice(tail).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn nodes_from_tail_mut(tail: &mut [u8]) -> Result<&mut [SubtreeNode], ProgramError> {
+    if !is_subtree_tail(tail.len()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    bytemuck::try_cast_slice_mut(tail).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// View tape account data as its fixed fields and cached subtree nodes.
+pub fn decode_tape(data: &[u8]) -> Result<(&Tape, &[SubtreeNode]), ProgramError> {
+    if data.len() < Tape::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (head, tail) = data.split_at(Tape::LEN);
+    let nodes = nodes_from_tail(tail)?;
+    let tape = load_acc::<Tape>(head)?;
+    Ok((tape, nodes))
+}
+
+pub fn decode_tape_mut(data: &mut [u8]) -> Result<(&mut Tape, &mut [SubtreeNode]), ProgramError> {
+    if data.len() < Tape::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (head, tail) = data.split_at_mut(Tape::LEN);
+    let nodes = nodes_from_tail_mut(tail)?;
+    let tape = load_acc_mut::<Tape>(head)?;
+    Ok((tape, nodes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_for_depth() {
+        assert_eq!(subtree_node_count(0), 0);
+        assert_eq!(subtree_node_count(1), 2);
+        assert_eq!(subtree_node_count(MAX_SUBTREE_DEPTH), MAX_SUBTREE_NODES);
+        assert_eq!(tape_account_size(0), Tape::LEN);
+        assert_eq!(tape_account_size(2), Tape::LEN + 6 * 32);
+
+        // Accounts created through CPI are capped at 10 KiB.
+        assert!(tape_account_size(MAX_SUBTREE_DEPTH) <= 10 * 1024);
+    }
+
+    #[test]
+    fn decode_splits_tail() {
+        // u64-backed buffer so the fixed fields are aligned
+        let mut buf = [0u64; tape_account_size(2) / 8];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+
+        {
+            let (tape, nodes) = decode_tape_mut(data).unwrap();
+            tape.total_segments = 7;
+            nodes[5] = [9; 32];
+        }
+
+        let (tape, nodes) = decode_tape(data).unwrap();
+        assert_eq!(tape.total_segments, 7);
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(nodes[5], [9; 32]);
+    }
+
+    #[test]
+    fn decode_rejects_bad_lengths() {
+        let mut buf = [0u64; tape_account_size(1) / 8 + 1];
+        let data: &[u8] = bytemuck::cast_slice(&buf[..]);
+
+        assert_eq!(
+            decode_tape(&data[..Tape::LEN - 8]).err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+        assert_eq!(
+            decode_tape(&data[..Tape::LEN + 40]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        // (whole nodes, but not a whole subtree)
+        assert_eq!(
+            decode_tape(&data[..Tape::LEN + 32]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert!(decode_tape(&data[..Tape::LEN]).is_ok());
+
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut buf[..]);
+        assert!(decode_tape_mut(&mut data[..Tape::LEN + 64]).is_ok());
+    }
+}
@@ -19,10 +19,10 @@ impl Initialized for Treasury {
 
 impl Treasury {
     pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
-        unsafe { load_acc::<Treasury>(data) }
+        load_acc::<Treasury>(data)
     }
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        unsafe { load_acc_mut::<Treasury>(data) }
+        load_acc_mut::<Treasury>(data)
     }
 }
 
@@ -0,0 +1,190 @@
+//! Zero-copy views of account and instruction data.
+//!
+//! The runtime aligns every account's data to 8 bytes, enough for every
+//! layout in `state`, so accounts are viewed in place. Views are checked by
+//! `bytemuck`: a buffer of the wrong length or alignment is rejected, never
+//! read. Views of an `AccountInfo` hold its data borrow, so the account
+//! cannot be borrowed again (or closed, or passed to a CPI) until they drop.
+//!
+//! Instruction data has no alignment guarantee, so it is copied out instead.
+
+use bytemuck::Pod;
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    program_error::ProgramError,
+};
+
+use crate::error::TapeError;
+
+pub trait DataLen {
+    const LEN: usize;
+}
+
+pub trait Initialized {
+    fn is_initialized(&self) -> bool;
+}
+
+#[inline(always)]
+pub fn load_acc<T: Pod + DataLen + Initialized>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    load_acc_unchecked::<T>(bytes).and_then(|acc| {
+        if acc.is_initialized() {
+            Ok(acc)
+        } else {
+            Err(ProgramError::UninitializedAccount)
+        }
+    })
+}
+
+#[inline(always)]
+pub fn load_acc_unchecked<T: Pod + DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    bytemuck::try_from_bytes(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+#[inline(always)]
+pub fn load_acc_mut<T: Pod + DataLen + Initialized>(
+    bytes: &mut [u8],
+) -> Result<&mut T, ProgramError> {
+    load_acc_mut_unchecked::<T>(bytes).and_then(|acc| {
+        if acc.is_initialized() {
+            Ok(acc)
+        } else {
+            Err(ProgramError::UninitializedAccount)
+        }
+    })
+}
+
+#[inline(always)]
+pub fn load_acc_mut_unchecked<T: Pod + DataLen>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    bytemuck::try_from_bytes_mut(bytes).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Copy instruction data out as a `T`.
+#[inline(always)]
+pub fn load_ix_data<T: Pod + DataLen>(bytes: &[u8]) -> Result<T, ProgramError> {
+    if bytes.len() != T::LEN {
+        return Err(TapeError::InvalidInstructionData.into());
+    }
+    bytemuck::try_pod_read_unaligned(bytes).map_err(|_| TapeError::InvalidInstructionData.into())
+}
+
+/// Like `load_ix_data`, but for instructions that carry a fixed header
+/// followed by variable-length bytes.
+#[inline(always)]
+pub fn load_ix_data_with_tail<T: Pod + DataLen>(bytes: &[u8]) -> Result<(T, &[u8]), ProgramError> {
+    if bytes.len() < T::LEN {
+        return Err(TapeError::InvalidInstructionData.into());
+    }
+    let (head, tail) = bytes.split_at(T::LEN);
+    Ok((load_ix_data(head)?, tail))
+}
+
+pub fn to_bytes<T: Pod>(data: &T) -> &[u8] {
+    bytemuck::bytes_of(data)
+}
+
+pub fn to_mut_bytes<T: Pod>(data: &mut T) -> &mut [u8] {
+    bytemuck::bytes_of_mut(data)
+}
+
+/// Borrow the data of an account owned by this program.
+pub fn try_borrow_program_data(acc: &AccountInfo) -> Result<Ref<'_, [u8]>, ProgramError> {
+    if acc.owner() != &crate::ID {
+        return Err(TapeError::InvalidOwner.into());
+    }
+    acc.try_borrow_data()
+}
+
+pub fn try_borrow_program_data_mut(acc: &AccountInfo) -> Result<RefMut<'_, [u8]>, ProgramError> {
+    if acc.owner() != &crate::ID {
+        return Err(TapeError::InvalidOwner.into());
+    }
+    acc.try_borrow_mut_data()
+}
+
+pub fn try_from_account_info<T: Pod + DataLen>(
+    acc: &AccountInfo,
+) -> Result<Ref<'_, T>, ProgramError> {
+    let bytes = try_borrow_program_data(acc)?;
+    load_acc_unchecked::<T>(&bytes)?;
+
+    // (checked above, so the cast cannot fail)
+    Ok(Ref::map(bytes, |bytes| bytemuck::from_bytes(bytes)))
+}
+
+pub fn try_from_account_info_mut<T: Pod + DataLen>(
+    acc: &AccountInfo,
+) -> Result<RefMut<'_, T>, ProgramError> {
+    let mut bytes = try_borrow_program_data_mut(acc)?;
+    load_acc_mut_unchecked::<T>(&mut bytes)?;
+
+    Ok(RefMut::map(bytes, |bytes| bytemuck::from_bytes_mut(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+    struct Sample {
+        a: u64,
+        b: [u8; 8],
+    }
+
+    impl DataLen for Sample {
+        const LEN: usize = core::mem::size_of::<Sample>();
+    }
+
+    impl Initialized for Sample {
+        fn is_initialized(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn ix_data_is_read_at_any_offset() {
+        let mut buf = [0u8; Sample::LEN + 1];
+        buf[1..9].copy_from_slice(&7u64.to_le_bytes());
+        buf[9..].copy_from_slice(&[1; 8]);
+
+        // (one past an 8-byte boundary, so never aligned for `Sample`)
+        let sample = load_ix_data::<Sample>(&buf[1..]).unwrap();
+        assert_eq!(sample, Sample { a: 7, b: [1; 8] });
+
+        let (sample, tail) = load_ix_data_with_tail::<Sample>(&buf[1..]).unwrap();
+        assert_eq!(sample.a, 7);
+        assert!(tail.is_empty());
+
+        assert_eq!(
+            load_ix_data::<Sample>(&buf).err(),
+            Some(TapeError::InvalidInstructionData.into())
+        );
+        assert_eq!(
+            load_ix_data_with_tail::<Sample>(&buf[2..]).err(),
+            Some(TapeError::InvalidInstructionData.into())
+        );
+    }
+
+    #[test]
+    fn misaligned_accounts_are_rejected() {
+        let mut buf = [0u64; Sample::LEN / 8 + 1];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buf);
+
+        assert!(load_acc::<Sample>(&bytes[..Sample::LEN]).is_ok());
+        assert_eq!(
+            load_acc::<Sample>(&bytes[1..Sample::LEN + 1]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            load_acc_mut::<Sample>(&mut bytes[..Sample::LEN - 1]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+}
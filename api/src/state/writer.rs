@@ -0,0 +1,133 @@
+use super::AccountType;
+use bytemuck::{Pod, Zeroable};
+use crate::consts::*;
+use crate::types::*;
+use crate::state::utils::{DataLen, Initialized, load_acc, load_acc_mut};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Leaf of a segment written ahead of the end of the tape, waiting for the
+/// segments before it. All zeroes means the slot is empty.
+pub type SegmentSlot = [u8; 32];
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Writer {
+    pub tape: Pubkey,
+    pub state: SegmentTree,
+
+    // +Trailing [SegmentSlot; WRITE_WINDOW], indexed by
+    //  segment_number % WRITE_WINDOW.
+}
+
+impl DataLen for Writer {
+    const LEN: usize = core::mem::size_of::<Writer>();
+}
+
+impl Initialized for Writer {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+}
+
+impl Writer {
+    pub fn unpack(data: &[u8]) -> Result<&Self, ProgramError> {
+        load_acc::<Writer>(&data[..Writer::LEN.min(data.len())])
+    }
+    pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        let len = Writer::LEN.min(data.len());
+        load_acc_mut::<Writer>(&mut data[..len])
+    }
+}
+
+account!(AccountType, Writer);
+
+/// Account size of a writer, including its out-of-order slots.
+pub const fn writer_account_size() -> usize {
+    Writer::LEN + WRITE_WINDOW * core::mem::size_of::<SegmentSlot>()
+}
+
+/// Slot holding `segment_number` while it waits to be appended.
+#[inline(always)]
+pub const fn slot_index(segment_number: u64) -> usize {
+    (segment_number % WRITE_WINDOW as u64) as usize
+}
+
+fn slots_from_tail(tail: &[u8]) -> Result<&[SegmentSlot; WRITE_WINDOW], ProgramError> {
+    bytemuck::try_from_bytes(tail).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn slots_from_tail_mut(tail: &mut [u8]) -> Result<&mut [SegmentSlot; WRITE_WINDOW], ProgramError> {
+    bytemuck::try_from_bytes_mut(tail).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// View writer account data as its fixed fields and out-of-order slots.
+pub fn decode_writer(data: &[u8]) -> Result<(&Writer, &[SegmentSlot; WRITE_WINDOW]), ProgramError> {
+    if data.len() < Writer::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (head, tail) = data.split_at(Writer::LEN);
+    let slots = slots_from_tail(tail)?;
+    let writer = load_acc::<Writer>(head)?;
+    Ok((writer, slots))
+}
+
+pub fn decode_writer_mut(
+    data: &mut [u8],
+) -> Result<(&mut Writer, &mut [SegmentSlot; WRITE_WINDOW]), ProgramError> {
+    if data.len() < Writer::LEN {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    let (head, tail) = data.split_at_mut(Writer::LEN);
+    let slots = slots_from_tail_mut(tail)?;
+    let writer = load_acc_mut::<Writer>(head)?;
+    Ok((writer, slots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_fit_a_cpi_created_account() {
+        assert!(writer_account_size() <= 10 * 1024);
+    }
+
+    #[test]
+    fn out_of_order_writes_match_appends() {
+        use crate::utils::{compute_leaf, drain_slots, write_segment};
+
+        let segments: [[u8; SEGMENT_SIZE]; 4] = core::array::from_fn(|i| [i as u8; SEGMENT_SIZE]);
+
+        let mut appended = SegmentTree::new(&[b"tape".as_ref()]);
+        for (i, segment) in segments.iter().enumerate() {
+            write_segment(&mut appended, i as u64, segment).unwrap();
+        }
+
+        let mut tree = SegmentTree::new(&[b"tape".as_ref()]);
+        let mut slots = [[0u8; 32]; WRITE_WINDOW];
+        let mut total = 0;
+
+        // Segments 3 and 1 land first and wait behind the gap at 0.
+        for i in [3usize, 1] {
+            slots[slot_index(i as u64)] = compute_leaf(i as u64, &segments[i]).to_bytes();
+        }
+        assert_eq!(drain_slots(&mut tree, &mut slots, &mut total, &mut []).unwrap(), 0);
+
+        slots[slot_index(0)] = compute_leaf(0, &segments[0]).to_bytes();
+        assert_eq!(drain_slots(&mut tree, &mut slots, &mut total, &mut []).unwrap(), 2);
+        assert_eq!(total, 2);
+
+        slots[slot_index(2)] = compute_leaf(2, &segments[2]).to_bytes();
+        assert_eq!(drain_slots(&mut tree, &mut slots, &mut total, &mut []).unwrap(), 2);
+        assert_eq!(total, 4);
+
+        assert_eq!(tree.get_root(), appended.get_root());
+        assert!(slots.iter().all(|s| *s == [0; 32]));
+    }
+
+    #[test]
+    fn slot_index_wraps() {
+        assert_eq!(slot_index(3), 3);
+        assert_eq!(slot_index(WRITE_WINDOW as u64 + 3), 3);
+    }
+}
@@ -0,0 +1,276 @@
+//! Cached top of a tape's segment tree.
+//!
+//! A tape created with `subtree_depth = d` stores the nodes of levels 1..=d
+//! of its segment tree after the fixed `Tape` fields, in heap order: level
+//! `k` occupies indices `2^k - 2 .. 2^(k+1) - 2`. An all-zero entry means the
+//! node is not cached (it was never seen, or a write invalidated it).
+//!
+//! With those nodes on chain, a client only has to send the lower
+//! `SEGMENT_PROOF_LEN - d` hashes of a proof; `complete_proof` fills in the
+//! rest. The completed proof is still checked by `brine_tree`, so a stale or
+//! wrong cache can only make a proof fail, never pass.
+
+use crate::consts::*;
+use crate::error::*;
+use crate::state::SubtreeNode;
+use crate::utils::check_condition;
+use pinocchio::program_error::ProgramError;
+
+const EMPTY_NODE: SubtreeNode = [0; 32];
+
+/// Depth of the subtree cached in `nodes` (0 when the tape has no cache).
+#[inline(always)]
+pub const fn subtree_depth(nodes: &[SubtreeNode]) -> u64 {
+    ((nodes.len() + 2).trailing_zeros() - 1) as u64
+}
+
+/// Heap index of the node at `level` (1 = just below the root) that sits
+/// above `segment_id`.
+#[inline(always)]
+pub const fn subtree_index(level: u64, segment_id: u64) -> usize {
+    let position = segment_id >> (SEGMENT_TREE_HEIGHT as u64 - level);
+    (1usize << level) - 2 + position as usize
+}
+
+/// Parent of two segment-tree nodes. `brine_tree` hashes pairs in sorted
+/// order, which is why its proofs carry no left/right bits.
+#[inline(always)]
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    brine_tree::hashv(&[left, right]).to_bytes()
+}
+
+/// Extend a proof that stops at the cached level into a full
+/// `SEGMENT_PROOF_LEN` proof, using the siblings stored in `nodes`.
+pub fn complete_proof(
+    nodes: &[SubtreeNode],
+    segment_id: u64,
+    proof: &[[u8; 32]],
+) -> Result<[[u8; 32]; SEGMENT_PROOF_LEN], ProgramError> {
+    let depth = subtree_depth(nodes) as usize;
+
+    check_proof_len(proof.len(), depth)?;
+
+    let mut full = [EMPTY_NODE; SEGMENT_PROOF_LEN];
+    full[..proof.len()].copy_from_slice(proof);
+
+    for (i, sibling) in full.iter_mut().enumerate().skip(proof.len()) {
+        let level = (SEGMENT_TREE_HEIGHT - i) as u64;
+        let node = nodes[subtree_index(level, segment_id ^ (1 << i))];
+
+        check_condition(node != EMPTY_NODE, TapeError::SubtreeNotCached)?;
+        *sibling = node;
+    }
+
+    Ok(full)
+}
+
+/// Record the path from `leaf` to the root in the cache, given a full proof
+/// that has already been accepted against `root`. Nothing is stored if the
+/// path does not hash to `root`.
+pub fn cache_path(
+    nodes: &mut [SubtreeNode],
+    segment_id: u64,
+    leaf: &[u8; 32],
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+    root: &[u8; 32],
+) -> bool {
+    let depth = subtree_depth(nodes) as usize;
+    if depth == 0 {
+        return false;
+    }
+
+    // (ancestor, sibling) per cached level, index 0 is level 1
+    let mut path = [(EMPTY_NODE, EMPTY_NODE); MAX_SUBTREE_DEPTH as usize];
+    let mut node = *leaf;
+
+    for (i, sibling) in proof.iter().enumerate() {
+        let level = SEGMENT_TREE_HEIGHT - i;
+        if level <= depth {
+            path[level - 1] = (node, *sibling);
+        }
+        node = hash_pair(&node, sibling);
+    }
+
+    if node != *root {
+        return false;
+    }
+
+    for (i, (ancestor, sibling)) in path[..depth].iter().enumerate() {
+        let level = (i + 1) as u64;
+        let shift = SEGMENT_TREE_HEIGHT - i - 1;
+        nodes[subtree_index(level, segment_id)] = *ancestor;
+        nodes[subtree_index(level, segment_id ^ (1 << shift))] = *sibling;
+    }
+
+    true
+}
+
+/// Store `node` as the cached node at `level`, `position`, if the cache
+/// reaches that deep.
+#[inline(always)]
+pub fn cache_node(nodes: &mut [SubtreeNode], level: u64, position: u64, node: &[u8; 32]) {
+    if level >= 1 && level <= subtree_depth(nodes) {
+        nodes[(1usize << level) - 2 + position as usize] = *node;
+    }
+}
+
+/// Forget the cached ancestors of `segment_id`, after it was written without
+/// a proof.
+pub fn clear_path(nodes: &mut [SubtreeNode], segment_id: u64) {
+    let depth = subtree_depth(nodes);
+    for level in 1..=depth {
+        nodes[subtree_index(level, segment_id)] = EMPTY_NODE;
+    }
+}
+
+/// A proof may leave off at most the cached levels.
+#[inline(always)]
+pub fn check_proof_len(len: usize, depth: usize) -> Result<(), ProgramError> {
+    check_condition(
+        len <= SEGMENT_PROOF_LEN && len + depth >= SEGMENT_PROOF_LEN,
+        TapeError::InvalidProofLength,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::state::subtree_node_count;
+    use crate::types::SegmentTree;
+    use crate::utils::{compute_leaf, update_segment, verify_segment};
+    use brine_tree::Leaf;
+    use std::vec::Vec;
+
+    const DEPTH: u64 = 3;
+
+    fn tree_with(segments: &[[u8; SEGMENT_SIZE]]) -> (SegmentTree, Vec<Leaf>) {
+        let mut tree = SegmentTree::new(&[b"test".as_ref()]);
+        let leaves: Vec<Leaf> = segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| compute_leaf(i as u64, s))
+            .collect();
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+        (tree, leaves)
+    }
+
+    fn proof_for(tree: &SegmentTree, leaves: &[Leaf], i: usize) -> [[u8; 32]; SEGMENT_PROOF_LEN] {
+        let proof: Vec<[u8; 32]> = tree
+            .get_merkle_proof(leaves, i)
+            .iter()
+            .map(|h| h.to_bytes())
+            .collect();
+        proof.try_into().unwrap()
+    }
+
+    #[test]
+    fn depth_from_node_count() {
+        for depth in 0..=MAX_SUBTREE_DEPTH {
+            let nodes = [EMPTY_NODE; MAX_SUBTREE_NODES];
+            assert_eq!(subtree_depth(&nodes[..subtree_node_count(depth)]), depth);
+        }
+    }
+
+    #[test]
+    fn index_is_heap_ordered() {
+        let last = (1u64 << SEGMENT_TREE_HEIGHT) - 1;
+        assert_eq!(subtree_index(1, 0), 0);
+        assert_eq!(subtree_index(1, last), 1);
+        assert_eq!(subtree_index(2, 0), 2);
+        assert_eq!(subtree_index(2, last), 5);
+        assert_eq!(
+            subtree_index(MAX_SUBTREE_DEPTH, last),
+            MAX_SUBTREE_NODES - 1
+        );
+    }
+
+    #[test]
+    fn short_proof_matches_full_proof() {
+        let segments = [[1; SEGMENT_SIZE], [2; SEGMENT_SIZE], [3; SEGMENT_SIZE]];
+        let (tree, leaves) = tree_with(&segments);
+        let root = tree.get_root().to_bytes();
+        let mut nodes = [EMPTY_NODE; subtree_node_count(DEPTH)];
+
+        let proof = proof_for(&tree, &leaves, 1);
+        assert!(verify_segment(&root, 1, &segments[1], &proof));
+        assert!(cache_path(&mut nodes, 1, &leaves[1].to_bytes(), &proof, &root));
+
+        let short = &proof[..SEGMENT_PROOF_LEN - DEPTH as usize];
+        assert_eq!(complete_proof(&nodes, 1, short).unwrap(), proof);
+
+        // Siblings along one path also complete the neighbour's path.
+        let proof = proof_for(&tree, &leaves, 0);
+        let short = &proof[..SEGMENT_PROOF_LEN - DEPTH as usize];
+        assert_eq!(complete_proof(&nodes, 0, short).unwrap(), proof);
+    }
+
+    #[test]
+    fn short_update_matches_full_update() {
+        let segments = [[1; SEGMENT_SIZE], [2; SEGMENT_SIZE], [3; SEGMENT_SIZE]];
+        let new_segment = [9; SEGMENT_SIZE];
+
+        let (mut full_tree, leaves) = tree_with(&segments);
+        let mut short_tree = full_tree;
+        let proof = proof_for(&full_tree, &leaves, 2);
+
+        update_segment(&mut full_tree, 2, &segments[2], &new_segment, &proof).unwrap();
+
+        let mut nodes = [EMPTY_NODE; subtree_node_count(DEPTH)];
+        let root = short_tree.get_root().to_bytes();
+        assert!(cache_path(&mut nodes, 2, &leaves[2].to_bytes(), &proof, &root));
+
+        let short = &proof[..SEGMENT_PROOF_LEN - DEPTH as usize];
+        let completed = complete_proof(&nodes, 2, short).unwrap();
+        update_segment(&mut short_tree, 2, &segments[2], &new_segment, &completed).unwrap();
+
+        assert_eq!(short_tree.get_root(), full_tree.get_root());
+
+        // The cache follows the update.
+        let new_leaf = compute_leaf(2, &new_segment).to_bytes();
+        let new_root = short_tree.get_root().to_bytes();
+        assert!(cache_path(&mut nodes, 2, &new_leaf, &completed, &new_root));
+        assert_eq!(complete_proof(&nodes, 2, short).unwrap(), completed);
+    }
+
+    #[test]
+    fn rejects_uncached_and_short_proofs() {
+        let nodes = [EMPTY_NODE; subtree_node_count(DEPTH)];
+        let proof = [[7; 32]; SEGMENT_PROOF_LEN];
+
+        assert_eq!(
+            complete_proof(&nodes, 0, &proof[..SEGMENT_PROOF_LEN - 1]),
+            Err(TapeError::SubtreeNotCached.into())
+        );
+        assert_eq!(
+            complete_proof(&nodes, 0, &proof[..SEGMENT_PROOF_LEN - DEPTH as usize - 1]),
+            Err(TapeError::InvalidProofLength.into())
+        );
+        assert_eq!(complete_proof(&nodes, 0, &proof).unwrap(), proof);
+    }
+
+    #[test]
+    fn wrong_root_is_not_cached() {
+        let mut nodes = [EMPTY_NODE; subtree_node_count(DEPTH)];
+        let proof = [[7; 32]; SEGMENT_PROOF_LEN];
+
+        assert!(!cache_path(&mut nodes, 0, &[1; 32], &proof, &[2; 32]));
+        assert!(nodes.iter().all(|n| *n == EMPTY_NODE));
+    }
+
+    #[test]
+    fn clear_path_forgets_ancestors() {
+        let mut nodes = [[1; 32]; subtree_node_count(DEPTH)];
+        clear_path(&mut nodes, 0);
+
+        for level in 1..=DEPTH {
+            assert_eq!(nodes[subtree_index(level, 0)], EMPTY_NODE);
+            let neighbour = 1 << (SEGMENT_TREE_HEIGHT as u64 - level);
+            assert_eq!(nodes[subtree_index(level, neighbour)], [1; 32]);
+        }
+    }
+}
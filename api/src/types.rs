@@ -1,5 +1,5 @@
 use core::ops::{Deref, Index};
-use crate::api::consts::*;
+use crate::consts::*;
 use pinocchio::program_error::ProgramError;
 use brine_tree::MerkleTree;
 use bytemuck::{Pod, Zeroable};
@@ -7,7 +7,7 @@ pub type SegmentTree = MerkleTree<{SEGMENT_TREE_HEIGHT}>;
 pub type TapeTree = MerkleTree<{TAPE_TREE_HEIGHT}>;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, shank::ShankType)]
 /// Proof-of-work solution needed to mine a block using CrankX
 pub struct PoW {
     pub digest: [u8; 16],
@@ -28,12 +28,13 @@ impl PoW {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable, shank::ShankType)]
 /// Proof-of-access solution for the tape segment, cryptographically tied to the miner using PackX.
 pub struct PoA {
     pub bump: [u8; 8],
     pub seed: [u8; 16],
     pub nonce: [u8; 128],
+    #[idl_type("[[u8; 32]; 18]")]
     pub path: ProofPath,
 }
 
@@ -1,13 +1,15 @@
 use bytemuck::{Pod, Zeroable};
-use crate::api::consts::*;
-use crate::api::error::*;
-use crate::api::types::*;
+use crate::consts::*;
+use crate::error::*;
+use crate::multiproof::*;
+use crate::state::{slot_index, SegmentSlot, SubtreeNode};
+use crate::subtree::clear_path;
+use crate::types::*;
 use brine_tree::Leaf;
 use pinocchio::{
     account_info::AccountInfo,
     ProgramResult,
     program_error::ProgramError,
-    sysvars::{clock::Clock, Sysvar},
 };
 use pinocchio_system::instructions::CreateAccount;
 use core::cmp::min;
@@ -65,6 +67,29 @@ pub fn compute_leaf(
     ])
 }
 
+#[inline(always)]
+pub fn compute_tape_leaf(
+    tape_number: u64,
+    value: &[u8; 32],
+) -> Leaf {
+    let tape_number = tape_number.to_le_bytes();
+    Leaf::new(&[
+        tape_number.as_ref(),
+        value,
+    ])
+}
+
+#[inline(always)]
+pub fn verify_segment(
+    merkle_root: &[u8; 32],
+    segment_id: u64,
+    segment: &[u8; SEGMENT_SIZE],
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+) -> bool {
+    let leaf = compute_leaf(segment_id, segment);
+    brine_tree::verify(*merkle_root, proof, leaf)
+}
+
 #[inline(always)]
 pub fn write_segment(
     tree: &mut SegmentTree,
@@ -79,6 +104,37 @@ pub fn write_segment(
     Ok(())
 }
 
+/// Append pending segments from the writer's out-of-order slots while the
+/// next one in line is present, at most `MAX_DRAIN_PER_IX` per call. Returns
+/// how many were appended.
+pub fn drain_slots(
+    tree: &mut SegmentTree,
+    slots: &mut [SegmentSlot; WRITE_WINDOW],
+    total_segments: &mut u64,
+    cache: &mut [SubtreeNode],
+) -> Result<u64, ProgramError> {
+    let mut drained = 0;
+
+    while drained < MAX_DRAIN_PER_IX as u64 {
+        let slot = &mut slots[slot_index(*total_segments)];
+        if *slot == [0; 32] {
+            break;
+        }
+
+        check_condition(
+            tree.try_add_leaf(Leaf::from(*slot)).is_ok(),
+            TapeError::WriteFailed,
+        )?;
+        clear_path(cache, *total_segments);
+
+        *slot = [0; 32];
+        *total_segments += 1;
+        drained += 1;
+    }
+
+    Ok(drained)
+}
+
 #[inline(always)]
 pub fn update_segment(
     tree: &mut SegmentTree,
@@ -96,19 +152,61 @@ pub fn update_segment(
     Ok(())
 }
 
+/// Replace several leaves in one pass, verified against the current root with
+/// a multiproof (see `fold_multiproof`). `visit` sees every changed node.
+pub fn update_segments<F>(
+    tree: &mut SegmentTree,
+    nodes: &mut [BatchNode],
+    proof: &[[u8; 32]],
+    mut visit: F,
+) -> ProgramResult
+where
+    F: FnMut(usize, &BatchNode),
+{
+    let mut filled_subtrees = tree.filled_subtrees;
+
+    let (old_root, new_root) = fold_multiproof(nodes, proof, |level, node| {
+        // Same bookkeeping as `MerkleTree::try_replace_leaf`: if a changed
+        // node is the pending left node of its level, later appends must
+        // see the new value.
+        if filled_subtrees[level].to_bytes() == node.old {
+            filled_subtrees[level] = node.new.into();
+        }
+        visit(level, node);
+    })?;
+
+    check_condition(
+        old_root == tree.get_root().to_bytes(),
+        TapeError::WriteFailed,
+    )?;
+
+    tree.filled_subtrees = filled_subtrees;
+    tree.root = new_root.into();
+
+    Ok(())
+}
+
+/// Mix the most recent entry of the SlotHashes sysvar into `current_challenge`.
+///
+/// SlotHashes is a `u64` entry count followed by `(slot, hash)` entries,
+/// newest first; only the first entry is read.
 #[inline(always)]
 pub fn compute_next_challenge(
     current_challenge: &[u8; 32],
     slot_hashes_info: &AccountInfo,
 ) -> Result<[u8; 32], ProgramError> {
 
-    let clock = Clock::from_account_info(slot_hashes_info)?;
-    let slot_bytes = clock.slot.to_le_bytes();
+    if slot_hashes_info.key() != &SLOT_HASHES_ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let data = slot_hashes_info.try_borrow_data()?;
+    let recent = data.get(8..48).ok_or(ProgramError::InvalidAccountData)?;
 
     let mut hasher = Hasher::new();
 
     hasher.update(current_challenge);
-    hasher.update(&slot_bytes);
+    hasher.update(recent);
     let challenge = hasher.finalize();
 
     Ok(challenge.into())
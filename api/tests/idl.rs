@@ -0,0 +1,300 @@
+//! The IDL's instruction layouts against the program's decoders.
+//!
+//! `encode` lays out instruction data from `client/idl/tape.json` the way the
+//! generated client does (see `client/generate-client.ts`): the u8
+//! discriminant, then each argument in order, with the last `Vec` running to
+//! the end of the data and an earlier one led by a u64 count. Each test then
+//! splits the data with `load_ix_data_with_tail`, as the processor does.
+
+use bytemuck::Pod;
+use serde_json::{json, Value};
+use tape_api::consts::SEGMENT_PROOF_LEN;
+use tape_api::instruction::*;
+use tape_api::state::utils::{load_ix_data_with_tail, DataLen};
+
+const IDL: &str = include_str!("../../client/idl/tape.json");
+
+struct Idl(Value);
+
+impl Idl {
+    fn load() -> Self {
+        Self(serde_json::from_str(IDL).unwrap())
+    }
+
+    fn instructions(&self) -> &[Value] {
+        self.0["instructions"].as_array().unwrap()
+    }
+
+    fn instruction(&self, name: &str) -> &Value {
+        self.instructions()
+            .iter()
+            .find(|ix| ix["name"] == name)
+            .unwrap_or_else(|| panic!("{name} is not in the IDL"))
+    }
+
+    fn defined(&self, name: &str) -> &Value {
+        self.0["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|ty| ty["name"] == name)
+            .unwrap_or_else(|| panic!("type {name} is not in the IDL"))
+    }
+
+    /// Instruction data for `name` with `args` keyed by argument name.
+    fn encode(&self, name: &str, args: &Value) -> Vec<u8> {
+        let ix = self.instruction(name);
+        let mut out = vec![ix["discriminant"]["value"].as_u64().unwrap() as u8];
+
+        let params = ix["args"].as_array().unwrap();
+        for (i, param) in params.iter().enumerate() {
+            let value = &args[param["name"].as_str().unwrap()];
+            let trailing = i == params.len() - 1;
+            self.write(&param["type"], value, trailing, &mut out);
+        }
+        out
+    }
+
+    fn write(&self, ty: &Value, value: &Value, trailing: bool, out: &mut Vec<u8>) {
+        match ty {
+            Value::String(ty) if ty == "u8" => out.push(value.as_u64().unwrap() as u8),
+            Value::String(ty) if ty == "u64" => {
+                out.extend_from_slice(&value.as_u64().unwrap().to_le_bytes())
+            }
+            Value::String(ty) if ty == "bytes" => {
+                assert!(trailing, "only trailing bytes are supported");
+                for byte in value.as_array().unwrap() {
+                    out.push(byte.as_u64().unwrap() as u8);
+                }
+            }
+            Value::Object(ty) if ty.contains_key("array") => {
+                let item = &ty["array"][0];
+                let items = value.as_array().unwrap();
+                assert_eq!(items.len() as u64, ty["array"][1].as_u64().unwrap());
+                for value in items {
+                    self.write(item, value, false, out);
+                }
+            }
+            Value::Object(ty) if ty.contains_key("vec") => {
+                let items = value.as_array().unwrap();
+                if !trailing {
+                    out.extend_from_slice(&(items.len() as u64).to_le_bytes());
+                }
+                for value in items {
+                    self.write(&ty["vec"], value, false, out);
+                }
+            }
+            Value::Object(ty) if ty.contains_key("defined") => {
+                let defined = self.defined(ty["defined"].as_str().unwrap());
+                for field in defined["type"]["fields"].as_array().unwrap() {
+                    let value = &value[field["name"].as_str().unwrap()];
+                    self.write(&field["type"], value, false, out);
+                }
+            }
+            _ => panic!("unsupported IDL type {ty}"),
+        }
+    }
+
+    /// A value of type `ty`, numbered from `next` so fields differ.
+    fn sample(&self, ty: &Value, next: &mut u8) -> Value {
+        match ty {
+            Value::String(_) => {
+                *next = next.wrapping_add(1);
+                json!(*next)
+            }
+            Value::Object(ty) if ty.contains_key("array") => {
+                let len = ty["array"][1].as_u64().unwrap();
+                (0..len).map(|_| self.sample(&ty["array"][0], next)).collect()
+            }
+            Value::Object(ty) if ty.contains_key("defined") => {
+                let defined = self.defined(ty["defined"].as_str().unwrap());
+                let mut fields = serde_json::Map::new();
+                for field in defined["type"]["fields"].as_array().unwrap() {
+                    let name = field["name"].as_str().unwrap().to_string();
+                    fields.insert(name, self.sample(&field["type"], next));
+                }
+                Value::Object(fields)
+            }
+            _ => panic!("no sample for IDL type {ty}"),
+        }
+    }
+}
+
+fn bytes(len: usize, fill: u8) -> Value {
+    json!(vec![fill; len])
+}
+
+fn hashes(count: usize) -> Value {
+    (0..count).map(|i| bytes(32, i as u8 + 1)).collect()
+}
+
+fn flatten(hashes: &Value) -> Vec<u8> {
+    hashes
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|hash| hash.as_array().unwrap().iter())
+        .map(|byte| byte.as_u64().unwrap() as u8)
+        .collect()
+}
+
+/// Split `data` like the processor for `ix`, after its discriminant.
+fn split<T: Pod + DataLen>(data: &[u8], ix: TapeInstruction) -> (T, &[u8]) {
+    assert_eq!(data[0], ix as u8);
+    load_ix_data_with_tail::<T>(&data[1..]).unwrap()
+}
+
+#[test]
+fn fixed_instructions_carry_just_their_header() {
+    fn check<T: Pod + DataLen>(idl: &Idl, name: &str, ix: TapeInstruction) {
+        let params = idl.instruction(name)["args"].as_array().unwrap();
+        assert_eq!(params.len(), 1, "{name}");
+
+        let mut next = 0;
+        let mut args = serde_json::Map::new();
+        let key = params[0]["name"].as_str().unwrap().to_string();
+        args.insert(key, idl.sample(&params[0]["type"], &mut next));
+        let data = idl.encode(name, &Value::Object(args));
+
+        let (header, tail) = split::<T>(&data, ix);
+        assert!(tail.is_empty(), "{name}");
+        assert_eq!(bytemuck::bytes_of(&header), &data[1..], "{name}");
+    }
+
+    let idl = Idl::load();
+    check::<InitializeIxData>(&idl, "Initialize", TapeInstruction::Initialize);
+    check::<AirdropIxData>(&idl, "Airdrop", TapeInstruction::Airdrop);
+    check::<CreateTapeIxData>(&idl, "TapeCreate", TapeInstruction::TapeCreate);
+    check::<FinalizeTapeIxData>(&idl, "TapeFinalize", TapeInstruction::TapeFinalize);
+    check::<SetTapeHeaderIxData>(&idl, "TapeSetHeader", TapeInstruction::TapeSetHeader);
+    check::<SubsidizeTapeIxData>(&idl, "TapeSubsidize", TapeInstruction::TapeSubsidize);
+    check::<RegisterMinerIxData>(&idl, "MinerRegister", TapeInstruction::MinerRegister);
+    check::<UnregisterMinerIxData>(&idl, "MinerUnregister", TapeInstruction::MinerUnregister);
+    check::<ClaimIxData>(&idl, "MinerClaim", TapeInstruction::MinerClaim);
+    check::<CreateSpoolIxData>(&idl, "SpoolCreate", TapeInstruction::SpoolCreate);
+    check::<DestroySpoolIxData>(&idl, "SpoolDestroy", TapeInstruction::SpoolDestroy);
+    check::<PackSpoolIxData>(&idl, "SpoolPack", TapeInstruction::SpoolPack);
+    check::<UnpackSpoolIxData>(&idl, "SpoolUnpack", TapeInstruction::SpoolUnpack);
+    check::<CommitSpoolIxData>(&idl, "SpoolCommit", TapeInstruction::SpoolCommit);
+}
+
+#[test]
+fn every_instruction_is_checked() {
+    let idl = Idl::load();
+    let checked = [
+        "Initialize", "Airdrop", "TapeCreate", "TapeWrite", "TapeUpdate", "TapeFinalize",
+        "TapeSetHeader", "TapeSubsidize", "TapeUpdateBatch", "TapeWriteAt", "MinerRegister",
+        "MinerUnregister", "MinerMine", "MinerClaim", "SpoolCreate", "SpoolDestroy",
+        "SpoolPack", "SpoolUnpack", "SpoolCommit", "EmitEvent",
+    ];
+    for ix in idl.instructions() {
+        let name = ix["name"].as_str().unwrap();
+        assert!(checked.contains(&name), "{name} has no encoding test");
+    }
+}
+
+#[test]
+fn tape_write_carries_the_segment_bytes() {
+    let idl = Idl::load();
+    let data = idl.encode("TapeWrite", &json!({ "segments": bytes(300, 7) }));
+
+    let (_, tail) = split::<WriteTapeIxData>(&data, TapeInstruction::TapeWrite);
+    assert_eq!(tail, &[7; 300][..]);
+}
+
+#[test]
+fn tape_write_at_carries_the_segment_bytes() {
+    let idl = Idl::load();
+    let data = idl.encode(
+        "TapeWriteAt",
+        &json!({
+            "writeTapeAtIxData": { "segmentNumber": 12 },
+            "segments": bytes(256, 3),
+        }),
+    );
+
+    let (header, tail) = split::<WriteTapeAtIxData>(&data, TapeInstruction::TapeWriteAt);
+    assert_eq!(header.segment_number, 12);
+    assert_eq!(tail, &[3; 256][..]);
+}
+
+#[test]
+fn tape_update_carries_the_proof() {
+    let idl = Idl::load();
+    let proof = hashes(SEGMENT_PROOF_LEN - 2);
+    let data = idl.encode(
+        "TapeUpdate",
+        &json!({
+            "updateTapeIxData": {
+                "segmentNumber": 5,
+                "oldData": bytes(128, 1),
+                "newData": bytes(128, 2),
+            },
+            "proof": proof,
+        }),
+    );
+
+    let (header, tail) = split::<UpdateTapeIxData>(&data, TapeInstruction::TapeUpdate);
+    assert_eq!(header.segment_number, 5);
+    assert_eq!(header.old_data, [1; 128]);
+    assert_eq!(header.new_data, [2; 128]);
+    assert_eq!(tail, flatten(&proof));
+}
+
+#[test]
+fn tape_update_batch_counts_its_updates_and_carries_the_proof() {
+    let idl = Idl::load();
+    let update = |number: u64| {
+        json!({
+            "segmentNumber": number,
+            "oldData": bytes(128, number as u8),
+            "newData": bytes(128, 0xff),
+        })
+    };
+    let proof = hashes(4);
+    let data = idl.encode(
+        "TapeUpdateBatch",
+        &json!({ "updates": [update(2), update(9)], "proof": proof }),
+    );
+
+    let (header, tail) = split::<UpdateTapeBatchIxData>(&data, TapeInstruction::TapeUpdateBatch);
+    assert_eq!(header.count, 2);
+
+    let (first, tail) = load_ix_data_with_tail::<SegmentUpdate>(tail).unwrap();
+    let (second, tail) = load_ix_data_with_tail::<SegmentUpdate>(tail).unwrap();
+    assert_eq!((first.segment_number, first.old_data), (2, [2; 128]));
+    assert_eq!((second.segment_number, second.new_data), (9, [0xff; 128]));
+    assert_eq!(tail, flatten(&proof));
+}
+
+#[test]
+fn miner_mine_carries_the_path_up_to_its_full_length() {
+    let idl = Idl::load();
+    let mine = |path: &Value| {
+        idl.encode(
+            "MinerMine",
+            &json!({
+                "pow": { "digest": bytes(16, 1), "nonce": bytes(8, 2) },
+                "bump": bytes(8, 3),
+                "seed": bytes(16, 4),
+                "nonce": bytes(128, 5),
+                "path": path,
+            }),
+        )
+    };
+
+    let path = hashes(SEGMENT_PROOF_LEN);
+    let data = mine(&path);
+    let (ix, tail) = split::<MineIxData>(&data, TapeInstruction::MinerMine);
+    assert!(tail.is_empty());
+    assert_eq!(ix.pow.digest, [1; 16]);
+    assert_eq!(ix.pow.nonce, [2; 8]);
+    assert_eq!(ix.poa.bump, [3; 8]);
+    assert_eq!(ix.poa.seed, [4; 16]);
+    assert_eq!(ix.poa.nonce, [5; 128]);
+    assert_eq!(flatten(&path), bytemuck::bytes_of(ix.poa.path.as_array()));
+
+    // (a path cut short by the tape's cached subtree)
+    let short = mine(&hashes(SEGMENT_PROOF_LEN - 3));
+    assert_eq!(short.len(), data.len() - 3 * 32);
+}
@@ -0,0 +1,208 @@
+//! The on-chain Merkle trees against a reference implementation.
+//!
+//! `Reference` recomputes every level from the leaves on each call, with
+//! none of `brine_tree`'s incremental bookkeeping (filled subtrees, leaf
+//! replacement). It shares only the hash primitive, the sorted-pair rule
+//! and the seeded empty subtrees, which together define the tree. Leaves
+//! come from `compute_leaf` and `compute_tape_leaf`, so a change to either
+//! format on one side shows up here as a root mismatch.
+
+use proptest::prelude::*;
+use tape_api::consts::*;
+use tape_api::types::{SegmentTree, TapeTree};
+use tape_api::utils::{
+    compute_leaf, compute_tape_leaf, update_segment, verify_segment, write_segment,
+};
+
+/// A Merkle tree of height `HEIGHT`, rebuilt from scratch on every query.
+struct Reference<const HEIGHT: usize> {
+    leaves: Vec<[u8; 32]>,
+    /// Root of an empty subtree at each level.
+    empty: [[u8; 32]; HEIGHT],
+}
+
+impl<const HEIGHT: usize> Reference<HEIGHT> {
+    /// The empty leaf is the hash of the seeds, and each empty subtree above
+    /// it is two copies of the one below.
+    fn new(seeds: &[&[u8]]) -> Self {
+        let mut empty = [[0; 32]; HEIGHT];
+        empty[0] = brine_tree::hashv(seeds).to_bytes();
+        for level in 1..HEIGHT {
+            empty[level] = Self::hash(&empty[level - 1], &empty[level - 1]);
+        }
+
+        Self {
+            leaves: Vec::new(),
+            empty,
+        }
+    }
+
+    fn hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        brine_tree::hashv(&[left, right]).to_bytes()
+    }
+
+    /// Every level, leaves first, each padded to an even length.
+    fn levels(&self) -> Vec<Vec<[u8; 32]>> {
+        let mut levels = Vec::with_capacity(HEIGHT + 1);
+        let mut level = self.leaves.clone();
+
+        for empty in self.empty {
+            if level.is_empty() {
+                level.push(empty);
+            }
+            if level.len() % 2 == 1 {
+                level.push(empty);
+            }
+            let parents = level.chunks(2).map(|pair| Self::hash(&pair[0], &pair[1])).collect();
+            levels.push(level);
+            level = parents;
+        }
+        levels.push(level);
+        levels
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.levels()[HEIGHT][0]
+    }
+
+    fn proof(&self, index: usize) -> [[u8; 32]; HEIGHT] {
+        let levels = self.levels();
+        core::array::from_fn(|level| levels[level][(index >> level) ^ 1])
+    }
+}
+
+fn segment() -> impl Strategy<Value = [u8; SEGMENT_SIZE]> {
+    prop::collection::vec(any::<u8>(), SEGMENT_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+/// An operation on a spool: pack a tape, or unpack one picked by position.
+#[derive(Clone, Debug)]
+enum SpoolOp {
+    Pack(u64, [u8; 32]),
+    Unpack(usize),
+}
+
+fn spool_op() -> impl Strategy<Value = SpoolOp> {
+    prop_oneof![
+        3 => (1..1_000u64, any::<[u8; 32]>()).prop_map(|(tape, value)| SpoolOp::Pack(tape, value)),
+        1 => any::<usize>().prop_map(SpoolOp::Unpack),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn segment_writes_match_the_reference(
+        seed in any::<[u8; 32]>(),
+        segments in prop::collection::vec(segment(), 1..80),
+    ) {
+        let mut tree = SegmentTree::new(&[seed.as_ref()]);
+        let mut reference = Reference::<SEGMENT_TREE_HEIGHT>::new(&[seed.as_ref()]);
+
+        for (i, segment) in segments.iter().enumerate() {
+            write_segment(&mut tree, i as u64, segment).unwrap();
+            reference.leaves.push(compute_leaf(i as u64, segment).to_bytes());
+            prop_assert_eq!(tree.get_root().to_bytes(), reference.root());
+        }
+
+        let root = reference.root();
+        for (i, segment) in segments.iter().enumerate() {
+            prop_assert!(verify_segment(&root, i as u64, segment, &reference.proof(i)));
+        }
+    }
+
+    #[test]
+    fn segment_updates_match_the_reference(
+        seed in any::<[u8; 32]>(),
+        mut segments in prop::collection::vec(segment(), 1..40),
+        updates in prop::collection::vec((any::<usize>(), segment()), 1..20),
+    ) {
+        let mut tree = SegmentTree::new(&[seed.as_ref()]);
+        let mut reference = Reference::<SEGMENT_TREE_HEIGHT>::new(&[seed.as_ref()]);
+        for (i, segment) in segments.iter().enumerate() {
+            write_segment(&mut tree, i as u64, segment).unwrap();
+            reference.leaves.push(compute_leaf(i as u64, segment).to_bytes());
+        }
+
+        for (pick, new) in updates {
+            let i = pick % segments.len();
+            let proof = reference.proof(i);
+            update_segment(&mut tree, i as u64, &segments[i], &new, &proof).unwrap();
+
+            segments[i] = new;
+            reference.leaves[i] = compute_leaf(i as u64, &new).to_bytes();
+            prop_assert_eq!(tree.get_root().to_bytes(), reference.root());
+        }
+
+        // Appends after updates still agree.
+        let next = segments.len();
+        write_segment(&mut tree, next as u64, &[0xA5; SEGMENT_SIZE]).unwrap();
+        reference.leaves.push(compute_leaf(next as u64, &[0xA5; SEGMENT_SIZE]).to_bytes());
+        prop_assert_eq!(tree.get_root().to_bytes(), reference.root());
+    }
+
+    #[test]
+    fn stale_proofs_are_rejected(
+        seed in any::<[u8; 32]>(),
+        mut segments in prop::collection::vec(segment(), 2..40),
+        pick in any::<usize>(),
+        new in segment(),
+    ) {
+        let mut tree = SegmentTree::new(&[seed.as_ref()]);
+        let mut reference = Reference::<SEGMENT_TREE_HEIGHT>::new(&[seed.as_ref()]);
+        for (i, segment) in segments.iter().enumerate() {
+            write_segment(&mut tree, i as u64, segment).unwrap();
+            reference.leaves.push(compute_leaf(i as u64, segment).to_bytes());
+        }
+
+        let i = pick % segments.len();
+        let j = (i + 1) % segments.len();
+        prop_assume!(new != segments[j]);
+        let stale = reference.proof(i);
+
+        // Changing any other leaf changes some sibling on `i`'s path.
+        update_segment(&mut tree, j as u64, &segments[j], &new, &reference.proof(j)).unwrap();
+        segments[j] = new;
+        reference.leaves[j] = compute_leaf(j as u64, &new).to_bytes();
+
+        let result = update_segment(&mut tree, i as u64, &segments[i], &new, &stale);
+        prop_assert!(result.is_err());
+        prop_assert_eq!(tree.get_root().to_bytes(), reference.root());
+    }
+
+    #[test]
+    fn spools_match_the_reference(
+        seed in any::<[u8; 32]>(),
+        ops in prop::collection::vec(spool_op(), 1..60),
+    ) {
+        let mut tree = TapeTree::new(&[seed.as_ref()]);
+        let mut reference = Reference::<TAPE_TREE_HEIGHT>::new(&[seed.as_ref()]);
+        let mut packed: Vec<(usize, u64, [u8; 32])> = Vec::new();
+
+        for op in ops {
+            match op {
+                SpoolOp::Pack(tape, value) => {
+                    let leaf = compute_tape_leaf(tape, &value);
+                    tree.try_add_leaf(leaf).unwrap();
+                    packed.push((reference.leaves.len(), tape, value));
+                    reference.leaves.push(leaf.to_bytes());
+                }
+                SpoolOp::Unpack(pick) => {
+                    if packed.is_empty() {
+                        continue;
+                    }
+                    let (index, tape, value) = packed.remove(pick % packed.len());
+                    let proof = reference.proof(index);
+                    tree.try_remove_leaf(&proof, compute_tape_leaf(tape, &value)).unwrap();
+                    reference.leaves[index] = reference.empty[0];
+                }
+            }
+            prop_assert_eq!(tree.get_root().to_bytes(), reference.root());
+        }
+
+        for (index, tape, value) in packed {
+            let proof = reference.proof(index);
+            prop_assert!(tree.contains_leaf(&proof, compute_tape_leaf(tape, &value)));
+        }
+    }
+}
@@ -1,35 +1,96 @@
+import {
+  arrayTypeNode,
+  bytesTypeNode,
+  instructionArgumentNode,
+  instructionNode,
+  isNode,
+  numberTypeNode,
+  prefixedCountNode,
+  remainderCountNode,
+  RootNode,
+  TypeNode,
+} from "@codama/nodes";
 import { rootNodeFromAnchorWithoutDefaultVisitor, AnchorIdl } from "@codama/nodes-from-anchor";
-import { renderJavaScriptVisitor, renderRustVisitor } from "@codama/renderers";
-import { visit } from "@codama/visitors-core";
-import idl from "./idl/solana_pinocchio_starter.json";
+import { renderJavaScriptVisitor } from "@codama/renderers";
+import { bottomUpTransformerVisitor, visit } from "@codama/visitors-core";
+import { readFileSync, writeFileSync } from "fs";
+
+const IDL_PATH = "./idl/tape.json";
+const INSTRUCTION_RS = "../api/src/instruction.rs";
+
+// Shank numbers instructions by their position in the enum, but the program's
+// discriminants are sparse (0x10.., 0x20.., 0xE0). Take them from the
+// `TapeInstruction` enum instead and write them back into the IDL.
+function pinDiscriminants(idl: any) {
+  const source = readFileSync(INSTRUCTION_RS, "utf8");
+  const body = source.match(/pub enum TapeInstruction \{([\s\S]*?)\n\}/);
+  if (!body) throw new Error(`TapeInstruction not found in ${INSTRUCTION_RS}`);
+
+  const values = new Map<string, number>();
+  for (const [, name, value] of body[1].matchAll(/^\s*(\w+)\s*=\s*(0x[0-9a-fA-F]+|\d+)/gm)) {
+    values.set(name, Number(value));
+  }
+
+  for (const ix of idl.instructions) {
+    const value = values.get(ix.name);
+    if (value === undefined) throw new Error(`no TapeInstruction discriminant for ${ix.name}`);
+    ix.discriminant = { type: "u8", value };
+  }
+}
+
+// Anchor IDLs give every `Vec` a u32 length prefix, but the program reads the
+// data after an instruction's fixed header as is (`load_ix_data_with_tail`):
+// the last `Vec` runs to the end of the data, and an earlier one (the
+// updates of `TapeUpdateBatch`) is counted by a leading u64.
+function trailingType(type: TypeNode, last: boolean): TypeNode {
+  if (isNode(type, "sizePrefixTypeNode") && isNode(type.type, "bytesTypeNode")) {
+    return last ? bytesTypeNode() : type;
+  }
+  if (isNode(type, "arrayTypeNode") && isNode(type.count, "prefixedCountNode")) {
+    const count = last ? remainderCountNode() : prefixedCountNode(numberTypeNode("u64"));
+    return arrayTypeNode(type.item, count);
+  }
+  return type;
+}
+
+const readTrailingData = bottomUpTransformerVisitor([
+  {
+    select: "[instructionNode]",
+    transform: (node) => {
+      if (!isNode(node, "instructionNode")) return node;
+      const last = node.arguments.length - 1;
+      return instructionNode({
+        ...node,
+        arguments: node.arguments.map((arg, i) =>
+          instructionArgumentNode({ ...arg, type: trailingType(arg.type, i === last) }),
+        ),
+      });
+    },
+  },
+]);
 
 async function generateTypeScriptClient() {
-  const node = rootNodeFromAnchorWithoutDefaultVisitor(idl as AnchorIdl);
+  const idl = JSON.parse(readFileSync(IDL_PATH, "utf8"));
+  pinDiscriminants(idl);
+  writeFileSync(IDL_PATH, JSON.stringify(idl, null, 2) + "\n");
+
+  const node = visit(rootNodeFromAnchorWithoutDefaultVisitor(idl as AnchorIdl), readTrailingData) as RootNode;
   await visit(node, await renderJavaScriptVisitor("./ts/generated"));
   console.log("✅ Codama TypeScript client generated in client/ts/generated!");
 }
 
-async function generateRustClient() {
-  const node = rootNodeFromAnchorWithoutDefaultVisitor(idl as AnchorIdl);
-  await visit(node, await renderRustVisitor("./rust/generated"));
-  console.log("✅ Codama Rust client generated in client/rust/generated!");
-}
-
 async function main() {
   const lang = process.argv[2];
-  
+
   if (!lang) {
-    console.log("Usage: tsx generate-clients.ts <typescript|rust|all>");
+    console.log("Usage: tsx generate-clients.ts <typescript>");
     process.exit(1);
   }
 
   try {
     if (lang === "typescript") {
       await generateTypeScriptClient();
-    } else if (lang === "rust") {
-      await generateRustClient();
-    }
-      else {
+    } else {
       console.log("Unknown language:", lang);
       process.exit(1);
     }
@@ -40,4 +101,3 @@ async function main() {
 }
 
 main();
-
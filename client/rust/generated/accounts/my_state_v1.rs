@@ -1,138 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-use crate::generated::types::State;
-use borsh::BorshDeserialize;
-use borsh::BorshSerialize;
-use solana_pubkey::Pubkey;
-
-#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct MyStateV1 {
-    pub is_initialized: u8,
-    #[cfg_attr(
-        feature = "serde",
-        serde(with = "serde_with::As::<serde_with::DisplayFromStr>")
-    )]
-    pub owner: Pubkey,
-    pub state: State,
-    pub data: [u8; 32],
-    pub update_count: u32,
-    pub bump: u8,
-}
-
-impl MyStateV1 {
-    #[inline(always)]
-    pub fn from_bytes(data: &[u8]) -> Result<Self, std::io::Error> {
-        let mut data = data;
-        Self::deserialize(&mut data)
-    }
-}
-
-impl<'a> TryFrom<&solana_account_info::AccountInfo<'a>> for MyStateV1 {
-    type Error = std::io::Error;
-
-    fn try_from(account_info: &solana_account_info::AccountInfo<'a>) -> Result<Self, Self::Error> {
-        let mut data: &[u8] = &(*account_info.data).borrow();
-        Self::deserialize(&mut data)
-    }
-}
-
-#[cfg(feature = "fetch")]
-pub fn fetch_my_state_v1(
-    rpc: &solana_client::rpc_client::RpcClient,
-    address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::DecodedAccount<MyStateV1>, std::io::Error> {
-    let accounts = fetch_all_my_state_v1(rpc, &[*address])?;
-    Ok(accounts[0].clone())
-}
-
-#[cfg(feature = "fetch")]
-pub fn fetch_all_my_state_v1(
-    rpc: &solana_client::rpc_client::RpcClient,
-    addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::DecodedAccount<MyStateV1>>, std::io::Error> {
-    let accounts = rpc
-        .get_multiple_accounts(addresses)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-    let mut decoded_accounts: Vec<crate::shared::DecodedAccount<MyStateV1>> = Vec::new();
-    for i in 0..addresses.len() {
-        let address = addresses[i];
-        let account = accounts[i].as_ref().ok_or(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Account not found: {}", address),
-        ))?;
-        let data = MyStateV1::from_bytes(&account.data)?;
-        decoded_accounts.push(crate::shared::DecodedAccount {
-            address,
-            account: account.clone(),
-            data,
-        });
-    }
-    Ok(decoded_accounts)
-}
-
-#[cfg(feature = "fetch")]
-pub fn fetch_maybe_my_state_v1(
-    rpc: &solana_client::rpc_client::RpcClient,
-    address: &solana_pubkey::Pubkey,
-) -> Result<crate::shared::MaybeAccount<MyStateV1>, std::io::Error> {
-    let accounts = fetch_all_maybe_my_state_v1(rpc, &[*address])?;
-    Ok(accounts[0].clone())
-}
-
-#[cfg(feature = "fetch")]
-pub fn fetch_all_maybe_my_state_v1(
-    rpc: &solana_client::rpc_client::RpcClient,
-    addresses: &[solana_pubkey::Pubkey],
-) -> Result<Vec<crate::shared::MaybeAccount<MyStateV1>>, std::io::Error> {
-    let accounts = rpc
-        .get_multiple_accounts(addresses)
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-    let mut decoded_accounts: Vec<crate::shared::MaybeAccount<MyStateV1>> = Vec::new();
-    for i in 0..addresses.len() {
-        let address = addresses[i];
-        if let Some(account) = accounts[i].as_ref() {
-            let data = MyStateV1::from_bytes(&account.data)?;
-            decoded_accounts.push(crate::shared::MaybeAccount::Exists(
-                crate::shared::DecodedAccount {
-                    address,
-                    account: account.clone(),
-                    data,
-                },
-            ));
-        } else {
-            decoded_accounts.push(crate::shared::MaybeAccount::NotFound(address));
-        }
-    }
-    Ok(decoded_accounts)
-}
-
-#[cfg(feature = "anchor")]
-impl anchor_lang::AccountDeserialize for MyStateV1 {
-    fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
-        Ok(Self::deserialize(buf)?)
-    }
-}
-
-#[cfg(feature = "anchor")]
-impl anchor_lang::AccountSerialize for MyStateV1 {}
-
-#[cfg(feature = "anchor")]
-impl anchor_lang::Owner for MyStateV1 {
-    fn owner() -> Pubkey {
-        crate::SOLANA_PINOCCHIO_STARTER_ID
-    }
-}
-
-#[cfg(feature = "anchor-idl-build")]
-impl anchor_lang::IdlBuild for MyStateV1 {}
-
-#[cfg(feature = "anchor-idl-build")]
-impl anchor_lang::Discriminator for MyStateV1 {
-    const DISCRIMINATOR: [u8; 8] = [0; 8];
-}
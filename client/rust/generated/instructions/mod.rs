@@ -1,12 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-pub(crate) mod r#initialize_state;
-pub(crate) mod r#update_state;
-
-pub use self::r#initialize_state::*;
-pub use self::r#update_state::*;
@@ -1,12 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-use solana_pubkey::{pubkey, Pubkey};
-
-/// `solana_pinocchio_starter` program ID.
-pub const SOLANA_PINOCCHIO_STARTER_ID: Pubkey =
-    pubkey!("ENrRns55VechXJiq4bMbdx7idzQh7tvaEJoYeWxRNe7Y");
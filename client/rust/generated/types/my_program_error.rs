@@ -1,30 +0,0 @@
-//! This code was AUTOGENERATED using the codama library.
-//! Please DO NOT EDIT THIS FILE, instead use visitors
-//! to add features, then rerun codama to update it.
-//!
-//! <https://github.com/codama-idl/codama>
-//!
-
-use borsh::BorshDeserialize;
-use borsh::BorshSerialize;
-use num_derive::FromPrimitive;
-
-#[derive(
-    BorshSerialize,
-    BorshDeserialize,
-    Clone,
-    Debug,
-    Eq,
-    PartialEq,
-    Copy,
-    PartialOrd,
-    Hash,
-    FromPrimitive,
-)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub enum MyProgramError {
-    WriteOverflow,
-    InvalidInstructionData,
-    PdaMismatch,
-    InvalidOwner,
-}
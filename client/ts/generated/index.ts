@@ -1,12 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-export * from './accounts';
-export * from './instructions';
-export * from './programs';
-export * from './types';
@@ -1,10 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-export * from './initializeState';
-export * from './updateState';
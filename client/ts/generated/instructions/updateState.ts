@@ -1,174 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-import {
-  combineCodec,
-  getStructDecoder,
-  getStructEncoder,
-  getU8Decoder,
-  getU8Encoder,
-  transformEncoder,
-  type AccountMeta,
-  type AccountSignerMeta,
-  type Address,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
-  type Instruction,
-  type InstructionWithAccounts,
-  type InstructionWithData,
-  type ReadonlyUint8Array,
-  type TransactionSigner,
-  type WritableAccount,
-  type WritableSignerAccount,
-} from '@solana/kit';
-import { SOLANA_PINOCCHIO_STARTER_PROGRAM_ADDRESS } from '../programs';
-import { getAccountMetaFactory, type ResolvedAccount } from '../shared';
-
-export const UPDATE_STATE_DISCRIMINATOR = 1;
-
-export function getUpdateStateDiscriminatorBytes() {
-  return getU8Encoder().encode(UPDATE_STATE_DISCRIMINATOR);
-}
-
-export type UpdateStateInstruction<
-  TProgram extends string = typeof SOLANA_PINOCCHIO_STARTER_PROGRAM_ADDRESS,
-  TAccountPayerAcc extends string | AccountMeta<string> = string,
-  TAccountStateAcc extends string | AccountMeta<string> = string,
-  TRemainingAccounts extends readonly AccountMeta<string>[] = [],
-> = Instruction<TProgram> &
-  InstructionWithData<ReadonlyUint8Array> &
-  InstructionWithAccounts<
-    [
-      TAccountPayerAcc extends string
-        ? WritableSignerAccount<TAccountPayerAcc> &
-            AccountSignerMeta<TAccountPayerAcc>
-        : TAccountPayerAcc,
-      TAccountStateAcc extends string
-        ? WritableAccount<TAccountStateAcc>
-        : TAccountStateAcc,
-      ...TRemainingAccounts,
-    ]
-  >;
-
-export type UpdateStateInstructionData = { discriminator: number };
-
-export type UpdateStateInstructionDataArgs = {};
-
-export function getUpdateStateInstructionDataEncoder(): FixedSizeEncoder<UpdateStateInstructionDataArgs> {
-  return transformEncoder(
-    getStructEncoder([['discriminator', getU8Encoder()]]),
-    (value) => ({ ...value, discriminator: UPDATE_STATE_DISCRIMINATOR })
-  );
-}
-
-export function getUpdateStateInstructionDataDecoder(): FixedSizeDecoder<UpdateStateInstructionData> {
-  return getStructDecoder([['discriminator', getU8Decoder()]]);
-}
-
-export function getUpdateStateInstructionDataCodec(): FixedSizeCodec<
-  UpdateStateInstructionDataArgs,
-  UpdateStateInstructionData
-> {
-  return combineCodec(
-    getUpdateStateInstructionDataEncoder(),
-    getUpdateStateInstructionDataDecoder()
-  );
-}
-
-export type UpdateStateInput<
-  TAccountPayerAcc extends string = string,
-  TAccountStateAcc extends string = string,
-> = {
-  /** Fee payer account */
-  payerAcc: TransactionSigner<TAccountPayerAcc>;
-  /** State account */
-  stateAcc: Address<TAccountStateAcc>;
-};
-
-export function getUpdateStateInstruction<
-  TAccountPayerAcc extends string,
-  TAccountStateAcc extends string,
-  TProgramAddress extends
-    Address = typeof SOLANA_PINOCCHIO_STARTER_PROGRAM_ADDRESS,
->(
-  input: UpdateStateInput<TAccountPayerAcc, TAccountStateAcc>,
-  config?: { programAddress?: TProgramAddress }
-): UpdateStateInstruction<TProgramAddress, TAccountPayerAcc, TAccountStateAcc> {
-  // Program address.
-  const programAddress =
-    config?.programAddress ?? SOLANA_PINOCCHIO_STARTER_PROGRAM_ADDRESS;
-
-  // Original accounts.
-  const originalAccounts = {
-    payerAcc: { value: input.payerAcc ?? null, isWritable: true },
-    stateAcc: { value: input.stateAcc ?? null, isWritable: true },
-  };
-  const accounts = originalAccounts as Record<
-    keyof typeof originalAccounts,
-    ResolvedAccount
-  >;
-
-  const getAccountMeta = getAccountMetaFactory(programAddress, 'programId');
-  const instruction = {
-    accounts: [
-      getAccountMeta(accounts.payerAcc),
-      getAccountMeta(accounts.stateAcc),
-    ],
-    programAddress,
-    data: getUpdateStateInstructionDataEncoder().encode({}),
-  } as UpdateStateInstruction<
-    TProgramAddress,
-    TAccountPayerAcc,
-    TAccountStateAcc
-  >;
-
-  return instruction;
-}
-
-export type ParsedUpdateStateInstruction<
-  TProgram extends string = typeof SOLANA_PINOCCHIO_STARTER_PROGRAM_ADDRESS,
-  TAccountMetas extends readonly AccountMeta[] = readonly AccountMeta[],
-> = {
-  programAddress: Address<TProgram>;
-  accounts: {
-    /** Fee payer account */
-    payerAcc: TAccountMetas[0];
-    /** State account */
-    stateAcc: TAccountMetas[1];
-  };
-  data: UpdateStateInstructionData;
-};
-
-export function parseUpdateStateInstruction<
-  TProgram extends string,
-  TAccountMetas extends readonly AccountMeta[],
->(
-  instruction: Instruction<TProgram> &
-    InstructionWithAccounts<TAccountMetas> &
-    InstructionWithData<ReadonlyUint8Array>
-): ParsedUpdateStateInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 2) {
-    // TODO: Coded error.
-    throw new Error('Not enough accounts');
-  }
-  let accountIndex = 0;
-  const getNextAccount = () => {
-    const accountMeta = instruction.accounts![accountIndex]!;
-    accountIndex += 1;
-    return accountMeta;
-  };
-  return {
-    programAddress: instruction.programAddress,
-    accounts: {
-      payerAcc: getNextAccount(),
-      stateAcc: getNextAccount(),
-    },
-    data: getUpdateStateInstructionDataDecoder().decode(instruction.data),
-  };
-}
@@ -1,51 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-import {
-  combineCodec,
-  getAddressDecoder,
-  getAddressEncoder,
-  getArrayDecoder,
-  getArrayEncoder,
-  getStructDecoder,
-  getStructEncoder,
-  getU8Decoder,
-  getU8Encoder,
-  type Address,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
-} from '@solana/kit';
-
-export type InitializeMyStateIxData = { owner: Address; data: Array<number> };
-
-export type InitializeMyStateIxDataArgs = InitializeMyStateIxData;
-
-export function getInitializeMyStateIxDataEncoder(): FixedSizeEncoder<InitializeMyStateIxDataArgs> {
-  return getStructEncoder([
-    ['owner', getAddressEncoder()],
-    ['data', getArrayEncoder(getU8Encoder(), { size: 32 })],
-  ]);
-}
-
-export function getInitializeMyStateIxDataDecoder(): FixedSizeDecoder<InitializeMyStateIxData> {
-  return getStructDecoder([
-    ['owner', getAddressDecoder()],
-    ['data', getArrayDecoder(getU8Decoder(), { size: 32 })],
-  ]);
-}
-
-export function getInitializeMyStateIxDataCodec(): FixedSizeCodec<
-  InitializeMyStateIxDataArgs,
-  InitializeMyStateIxData
-> {
-  return combineCodec(
-    getInitializeMyStateIxDataEncoder(),
-    getInitializeMyStateIxDataDecoder()
-  );
-}
@@ -1,40 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-import {
-  combineCodec,
-  getEnumDecoder,
-  getEnumEncoder,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
-} from '@solana/kit';
-
-export enum MyProgramError {
-  WriteOverflow,
-  InvalidInstructionData,
-  PdaMismatch,
-  InvalidOwner,
-}
-
-export type MyProgramErrorArgs = MyProgramError;
-
-export function getMyProgramErrorEncoder(): FixedSizeEncoder<MyProgramErrorArgs> {
-  return getEnumEncoder(MyProgramError);
-}
-
-export function getMyProgramErrorDecoder(): FixedSizeDecoder<MyProgramError> {
-  return getEnumDecoder(MyProgramError);
-}
-
-export function getMyProgramErrorCodec(): FixedSizeCodec<
-  MyProgramErrorArgs,
-  MyProgramError
-> {
-  return combineCodec(getMyProgramErrorEncoder(), getMyProgramErrorDecoder());
-}
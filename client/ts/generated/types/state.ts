@@ -1,36 +0,0 @@
-/**
- * This code was AUTOGENERATED using the codama library.
- * Please DO NOT EDIT THIS FILE, instead use visitors
- * to add features, then rerun codama to update it.
- *
- * @see https://github.com/codama-idl/codama
- */
-
-import {
-  combineCodec,
-  getEnumDecoder,
-  getEnumEncoder,
-  type FixedSizeCodec,
-  type FixedSizeDecoder,
-  type FixedSizeEncoder,
-} from '@solana/kit';
-
-export enum State {
-  Uninitialized,
-  Initialized,
-  Updated,
-}
-
-export type StateArgs = State;
-
-export function getStateEncoder(): FixedSizeEncoder<StateArgs> {
-  return getEnumEncoder(State);
-}
-
-export function getStateDecoder(): FixedSizeDecoder<State> {
-  return getEnumDecoder(State);
-}
-
-export function getStateCodec(): FixedSizeCodec<StateArgs, State> {
-  return combineCodec(getStateEncoder(), getStateDecoder());
-}
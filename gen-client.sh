@@ -3,7 +3,13 @@ set -e
 LANG="$1"
 
 if [[ -z "$LANG" ]]; then
-  echo "Usage: $0 <typescript|rust|all>"
+  echo "Usage: $0 <typescript>"
+  exit 1
+fi
+
+if [[ "$LANG" == "rust" || "$LANG" == "all" ]]; then
+  echo "The Rust SDK (sdk/, tape-sdk) is written by hand to match the program's"
+  echo "zero-copy layouts and is not generated. Use: $0 typescript"
   exit 1
 fi
 
@@ -13,14 +19,11 @@ if [[ ! -x client/.crates/bin/shank ]]; then
   cargo install shank-cli --root client/.crates --version 0.4.2
 fi
 
+# The instruction enum, ix-data types and program id all live in tape-api.
 mkdir -p client/idl
-client/.crates/bin/shank idl --crate-root program --out-dir client/idl --out-filename solana_pinocchio_starter.json
+client/.crates/bin/shank idl --crate-root api --out-dir client/idl --out-filename tape.json
 
 # Generate client
 cd client
 bun install
 bun run gen-client "$LANG"
-
-if [[ "$LANG" == "rust" || "$LANG" == "all" ]]; then
-  rustfmt rust/generated/*.rs rust/generated/**/*.rs
-fi
\ No newline at end of file
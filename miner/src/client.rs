@@ -0,0 +1,300 @@
+//! Register, mine, claim, unregister.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use solana_sdk::signature::{Keypair, Signer};
+use tape_api::rent::rent_per_block;
+use tape_api::utils::{compute_challenge, compute_recall_segment, compute_recall_tape};
+use tape_sdk::accounts::{decode_tape_with_nodes, Block, Epoch, Miner, TapeAccount};
+use tape_sdk::consts::*;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::types::PoA;
+use tape_sdk::Pubkey;
+
+use crate::recall::SegmentSource;
+use crate::rpc::Rpc;
+use crate::solver::{self, SolverConfig};
+use crate::MinerError;
+
+/// How `MinerClient::run` paces itself.
+#[derive(Clone, Debug)]
+pub struct RunConfig {
+    /// Wait between polls while the current block is already proven.
+    pub poll: Duration,
+    /// Claim all unclaimed rewards after this many proofs (0 never claims).
+    pub claim_every: u64,
+    /// Stop after this many proofs.
+    pub max_proofs: Option<u64>,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            poll: Duration::from_secs(5),
+            claim_every: 10,
+            max_proofs: None,
+        }
+    }
+}
+
+/// One accepted proof.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MineReport {
+    pub block: u64,
+    pub tape: u64,
+    /// Recalled segment, or `None` when the tape pays no rent.
+    pub segment: Option<u64>,
+    pub difficulty: u32,
+    pub hashes_per_second: f64,
+}
+
+/// How often a running search stops to check whether the block moved on.
+const CHALLENGE_CHECK: Duration = Duration::from_secs(1);
+
+pub struct MinerClient<R: Rpc, S: SegmentSource> {
+    rpc: R,
+    signer: Keypair,
+    name: [u8; NAME_LEN],
+    segments: S,
+    solver: SolverConfig,
+    cancel: Arc<AtomicBool>,
+}
+
+impl<R: Rpc, S: SegmentSource> MinerClient<R, S> {
+    pub fn new(rpc: R, signer: Keypair, name: [u8; NAME_LEN], segments: S) -> Self {
+        Self {
+            rpc,
+            signer,
+            name,
+            segments,
+            solver: SolverConfig::default(),
+            cancel: Arc::default(),
+        }
+    }
+
+    /// Use `config` for the `PoW` search; its deadline is ignored.
+    pub fn with_solver(mut self, config: SolverConfig) -> Self {
+        self.solver = config;
+        self
+    }
+
+    /// Raising this flag abandons the search in progress; `mine_once` then
+    /// returns `Ok(None)`. Lower it again before mining further.
+    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
+        self.cancel.clone()
+    }
+
+    pub fn rpc(&self) -> &R {
+        &self.rpc
+    }
+
+    pub fn rpc_mut(&mut self) -> &mut R {
+        &mut self.rpc
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.signer.pubkey()
+    }
+
+    /// Address of this miner's account.
+    pub fn address(&self) -> Pubkey {
+        miner_pda(self.authority(), self.name).0
+    }
+
+    fn account<T: TapeAccount>(&self, address: &Pubkey) -> Result<T, MinerError> {
+        let data = self
+            .rpc
+            .account_data(address)?
+            .ok_or(MinerError::MissingAccount(*address))?;
+        Ok(T::decode(&data)?)
+    }
+
+    pub fn miner(&self) -> Result<Miner, MinerError> {
+        self.account(&self.address())
+    }
+
+    /// Register the miner unless it already exists.
+    pub fn register(&mut self) -> Result<(), MinerError> {
+        if self.rpc.account_data(&self.address())?.is_some() {
+            return Ok(());
+        }
+        let ix = build_register_ix(self.authority(), self.name);
+        self.rpc.send(&self.signer, &[ix])
+    }
+
+    /// Close the miner account, returning its rent to the authority.
+    /// Unclaimed rewards are lost, so claim first.
+    pub fn unregister(&mut self) -> Result<(), MinerError> {
+        let ix = build_unregister_ix(self.authority(), self.address());
+        self.rpc.send(&self.signer, &[ix])
+    }
+
+    /// Claim `amount` (everything, if `None`) to the authority's token
+    /// account. Returns the amount claimed.
+    pub fn claim(&mut self, amount: Option<u64>) -> Result<u64, MinerError> {
+        let amount = match amount {
+            Some(amount) => amount,
+            None => self.miner()?.unclaimed_rewards,
+        };
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        let beneficiary = tape_ata(self.authority()).0;
+        let ix = build_claim_ix(self.authority(), beneficiary, self.address(), amount);
+        self.rpc.send(&self.signer, &[ix])?;
+        Ok(amount)
+    }
+
+    /// Prove the current block, or return `None` if this miner already has
+    /// (or the search was cancelled). A block that moves on mid-search is
+    /// picked up and mined instead.
+    pub fn mine_once(&mut self) -> Result<Option<MineReport>, MinerError> {
+        loop {
+            let epoch: Epoch = self.account(&epoch_pda().0)?;
+            let block: Block = self.account(&block_pda().0)?;
+            let miner = self.miner()?;
+
+            if miner.total_proofs > 0 && miner.last_proof_block >= block.number {
+                return Ok(None);
+            }
+
+            let challenge = compute_challenge(&block.challenge, &miner.challenge);
+            let tape_number = compute_recall_tape(&challenge, block.challenge_set);
+            let tape_address = self
+                .rpc
+                .find_tape(tape_number)?
+                .ok_or(MinerError::UnknownTape(tape_number))?;
+            let tape_data = self
+                .rpc
+                .account_data(&tape_address)?
+                .ok_or(MinerError::MissingAccount(tape_address))?;
+            let (tape, _nodes) = decode_tape_with_nodes(&tape_data)?;
+
+            // Mirrors the program: tapes below minimum rent are proven against
+            // an empty segment and need no PoA.
+            let has_minimum_rent = tape.balance >= rent_per_block(tape.total_segments);
+
+            let (segment_number, segment, poa) = if has_minimum_rent {
+                let number = compute_recall_segment(&challenge, tape.total_segments);
+                let (segment, proof) = self.segments.segment(tape_number, number).ok_or(
+                    MinerError::MissingSegment {
+                        tape: tape_number,
+                        segment: number,
+                    },
+                )?;
+
+                // Pre-packed records are used as long as they are still hard
+                // enough; anything else is packed now.
+                let packed = self
+                    .segments
+                    .packed_segment(tape_number, number)
+                    .filter(|packed| packed.difficulty() as u64 >= epoch.packing_difficulty)
+                    .or_else(|| {
+                        packx::solve(
+                            &self.address().to_bytes(),
+                            &segment,
+                            epoch.packing_difficulty as u32,
+                        )
+                    })
+                    .ok_or(MinerError::PackFailed {
+                        tape: tape_number,
+                        segment: number,
+                    })?;
+
+                (Some(number), segment, PoA::from_solution(&packed, proof))
+            } else {
+                (None, EMPTY_SEGMENT, bytemuck::Zeroable::zeroed())
+            };
+
+            let Some((solved, hashes_per_second)) =
+                self.solve_pow(&block, &challenge, &segment, epoch.mining_difficulty)?
+            else {
+                if self.cancel.load(Ordering::Relaxed) {
+                    return Ok(None);
+                }
+                // (the block moved on; start over on the new one)
+                continue;
+            };
+
+            let ix = build_mine_ix(
+                self.authority(),
+                self.address(),
+                tape_address,
+                solved.pow(),
+                poa,
+            );
+            self.rpc.send(&self.signer, &[ix])?;
+
+            return Ok(Some(MineReport {
+                block: block.number,
+                tape: tape_number,
+                segment: segment_number,
+                difficulty: solved.difficulty,
+                hashes_per_second,
+            }));
+        }
+    }
+
+    /// Search in `CHALLENGE_CHECK` slices, giving up with `None` if the
+    /// cancel flag is raised or `block` stops being the current one.
+    fn solve_pow(
+        &self,
+        block: &Block,
+        challenge: &[u8; 32],
+        segment: &[u8; SEGMENT_SIZE],
+        difficulty: u64,
+    ) -> Result<Option<(solver::Solved, f64)>, MinerError> {
+        let mut config = self.solver;
+        let mut hashes = 0;
+        let started = Instant::now();
+
+        loop {
+            config.deadline = Some(Instant::now() + CHALLENGE_CHECK);
+            let search = solver::solve(challenge, segment, difficulty, &config, &self.cancel);
+            hashes += search.hashes;
+
+            if self.cancel.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+            if let Some(solved) = search.best.filter(|b| b.difficulty as u64 >= difficulty) {
+                let rate = hashes as f64 / started.elapsed().as_secs_f64().max(f64::EPSILON);
+                return Ok(Some((solved, rate)));
+            }
+
+            let current: Block = self.account(&block_pda().0)?;
+            if current.challenge != block.challenge {
+                return Ok(None);
+            }
+            config.start_nonce = search.next_nonce;
+        }
+    }
+
+    /// Mine until `config.max_proofs` (or forever), claiming along the way.
+    /// `on_proof` sees every accepted proof.
+    pub fn run(
+        &mut self,
+        config: &RunConfig,
+        mut on_proof: impl FnMut(&MineReport),
+    ) -> Result<(), MinerError> {
+        let mut proofs = 0;
+
+        while config.max_proofs != Some(proofs) {
+            match self.mine_once()? {
+                Some(report) => {
+                    on_proof(&report);
+                    proofs += 1;
+                    if config.claim_every > 0 && proofs % config.claim_every == 0 {
+                        self.claim(None)?;
+                    }
+                }
+                None => std::thread::sleep(config.poll),
+            }
+        }
+
+        Ok(())
+    }
+}
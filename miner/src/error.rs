@@ -0,0 +1,55 @@
+use tape_sdk::accounts::AccountDecodeError;
+use tape_sdk::errors::TapeError;
+use tape_sdk::Pubkey;
+
+#[derive(Debug)]
+pub enum MinerError {
+    /// The RPC backend failed.
+    Rpc(String),
+    /// The program rejected a transaction.
+    Program(TapeError),
+    /// An account the miner needs does not exist.
+    MissingAccount(Pubkey),
+    Decode(AccountDecodeError),
+    /// No tape with this number was found.
+    UnknownTape(u64),
+    /// The recalled segment is not available locally.
+    MissingSegment { tape: u64, segment: u64 },
+    /// No PackX solution was found for the recalled segment.
+    PackFailed { tape: u64, segment: u64 },
+    /// The local segment store failed.
+    Io(std::io::Error),
+}
+
+impl core::fmt::Display for MinerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Rpc(e) => write!(f, "rpc: {e}"),
+            Self::Program(e) => write!(f, "program: {e}"),
+            Self::MissingAccount(key) => write!(f, "account {key} does not exist"),
+            Self::Decode(e) => write!(f, "{e}"),
+            Self::UnknownTape(number) => write!(f, "no tape numbered {number}"),
+            Self::MissingSegment { tape, segment } => {
+                write!(f, "segment {segment} of tape {tape} is not stored locally")
+            }
+            Self::PackFailed { tape, segment } => {
+                write!(f, "could not pack segment {segment} of tape {tape}")
+            }
+            Self::Io(e) => write!(f, "segment store: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MinerError {}
+
+impl From<AccountDecodeError> for MinerError {
+    fn from(e: AccountDecodeError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<std::io::Error> for MinerError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -0,0 +1,19 @@
+//! Mining client for the tape program.
+//!
+//! `client::MinerClient` registers a miner, mines one proof per block and
+//! claims rewards. It talks to the cluster only through `rpc::Rpc`, so the
+//! same loop runs against a validator (`RpcClient`) or an in-process
+//! stand-in in tests. Segments for recalled tapes come from a
+//! `recall::SegmentSource`; `store::PackedStore` keeps them pre-packed on
+//! disk. `solver` runs the CrankX search on every core.
+
+pub mod client;
+pub mod error;
+pub mod recall;
+pub mod rpc;
+pub mod solver;
+pub mod store;
+
+pub use client::{MinerClient, MineReport, RunConfig};
+pub use error::MinerError;
+pub use store::PackedStore;
@@ -0,0 +1,198 @@
+//! ```text
+//! tape-miner [--rpc <URL>] [--keypair <PATH>] [--name <NAME>] [--store <DIR>] <COMMAND>
+//!
+//! commands:
+//!   register               create the miner account
+//!   mine [--claim-every N] mine a proof every block, claiming every N proofs
+//!   claim [AMOUNT]         claim rewards (all of them by default)
+//!   unregister             close the miner account
+//!   scan                   check every tape in the packed segment store
+//! ```
+//!
+//! Without `--store`, only tapes that pay no rent can be mined.
+
+use std::process::ExitCode;
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use tape_miner::recall::{NoSegments, SegmentSource};
+use tape_miner::rpc::Rpc;
+use tape_miner::{MinerClient, PackedStore, RunConfig};
+use tape_sdk::consts::NAME_LEN;
+use tape_sdk::pda::miner_pda;
+use tape_sdk::types::to_name;
+
+const USAGE: &str = "usage: tape-miner [--rpc <URL>] [--keypair <PATH>] [--name <NAME>] \
+                     [--store <DIR>] \
+                     (register | mine [--claim-every N] | claim [AMOUNT] | unregister | scan)";
+
+enum Command {
+    Register,
+    Mine { claim_every: u64 },
+    Claim { amount: Option<u64> },
+    Unregister,
+    Scan,
+}
+
+struct Args {
+    rpc: String,
+    keypair: String,
+    name: String,
+    store: Option<String>,
+    command: Command,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut rpc = "http://127.0.0.1:8899".to_string();
+    let mut keypair = match std::env::var("HOME") {
+        Ok(home) => format!("{home}/.config/solana/id.json"),
+        Err(_) => "id.json".to_string(),
+    };
+    let mut name = "miner".to_string();
+    let mut store = None;
+    let mut command = None;
+    let mut claim_every = RunConfig::default().claim_every;
+    let mut amount = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--rpc" => rpc = value("--rpc")?,
+            "--keypair" => keypair = value("--keypair")?,
+            "--name" => name = value("--name")?,
+            "--store" => store = Some(value("--store")?),
+            "--claim-every" => {
+                claim_every = value("--claim-every")?
+                    .parse()
+                    .map_err(|e| format!("--claim-every: {e}"))?;
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            "register" | "mine" | "claim" | "unregister" | "scan" if command.is_none() => {
+                command = Some(arg);
+            }
+            _ if command.as_deref() == Some("claim") && amount.is_none() => {
+                amount = Some(arg.parse().map_err(|e| format!("amount: {e}"))?);
+            }
+            _ => return Err(format!("unexpected argument {arg}\n{USAGE}")),
+        }
+    }
+
+    if name.len() > NAME_LEN {
+        return Err(format!("--name must be at most {NAME_LEN} bytes"));
+    }
+
+    let command = match command.as_deref() {
+        Some("register") => Command::Register,
+        Some("mine") => Command::Mine { claim_every },
+        Some("claim") => Command::Claim { amount },
+        Some("unregister") => Command::Unregister,
+        Some("scan") => Command::Scan,
+        _ => return Err(USAGE.to_string()),
+    };
+
+    Ok(Args {
+        rpc,
+        keypair,
+        name,
+        store,
+        command,
+    })
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let signer =
+        read_keypair_file(&args.keypair).map_err(|e| format!("reading {}: {e}", args.keypair))?;
+    let rpc = RpcClient::new_with_commitment(args.rpc, CommitmentConfig::confirmed());
+    let name = to_name(args.name.as_str());
+
+    // Records are packed against the miner account, not the authority.
+    let miner = miner_pda(signer.pubkey(), name).0;
+    let store = match &args.store {
+        Some(dir) => {
+            Some(PackedStore::open(dir, miner).map_err(|e| format!("opening {dir}: {e}"))?)
+        }
+        None => None,
+    };
+
+    match store.clone() {
+        Some(segments) => execute(
+            MinerClient::new(rpc, signer, name, segments),
+            args.command,
+            store.as_ref(),
+        ),
+        None => execute(MinerClient::new(rpc, signer, name, NoSegments), args.command, None),
+    }
+}
+
+fn execute<R: Rpc, S: SegmentSource>(
+    mut client: MinerClient<R, S>,
+    command: Command,
+    store: Option<&PackedStore>,
+) -> Result<(), String> {
+    match command {
+        Command::Register => {
+            client.register().map_err(|e| e.to_string())?;
+            println!("miner {}", client.address());
+        }
+        Command::Mine { claim_every } => {
+            let config = RunConfig {
+                claim_every,
+                ..RunConfig::default()
+            };
+            client
+                .run(&config, |report| match report.segment {
+                    Some(segment) => println!(
+                        "block {}: tape {} segment {}, difficulty {} ({:.0} H/s)",
+                        report.block,
+                        report.tape,
+                        segment,
+                        report.difficulty,
+                        report.hashes_per_second
+                    ),
+                    None => println!(
+                        "block {}: tape {} (no rent), difficulty {} ({:.0} H/s)",
+                        report.block, report.tape, report.difficulty, report.hashes_per_second
+                    ),
+                })
+                .map_err(|e| e.to_string())?;
+        }
+        Command::Claim { amount } => {
+            let claimed = client.claim(amount).map_err(|e| e.to_string())?;
+            println!("claimed {claimed}");
+        }
+        Command::Unregister => {
+            client.unregister().map_err(|e| e.to_string())?;
+            println!("closed {}", client.address());
+        }
+        Command::Scan => {
+            let store = store.ok_or("scan needs --store")?;
+            for number in store.tapes().map_err(|e| e.to_string())? {
+                let report = store.scan(number).map_err(|e| e.to_string())?;
+                if report.is_clean() {
+                    println!("tape {number}: {} segments ok", report.total_segments);
+                } else {
+                    println!(
+                        "tape {number}: {} of {} segments corrupt, index {}",
+                        report.corrupt.len(),
+                        report.total_segments,
+                        if report.tree_ok { "ok" } else { "corrupt" }
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match parse_args().and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
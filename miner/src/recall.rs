@@ -0,0 +1,62 @@
+//! Where recalled segments come from.
+//!
+//! A tape that pays rent is proven against the recalled segment itself, so
+//! the miner needs the segment and its Merkle proof locally. Tapes without
+//! rent are proven against `EMPTY_SEGMENT` and need nothing. Sources that
+//! pack ahead of time (`store::PackedStore`) also hand out the packed
+//! record, so the miner does not have to pack inside the block.
+
+use std::collections::HashMap;
+
+use tape_sdk::consts::*;
+use tape_sdk::prover::ProofBuilder;
+use tape_sdk::Pubkey;
+
+/// A recalled segment and its full proof against the tape's root.
+pub type RecallSegment = ([u8; SEGMENT_SIZE], [[u8; 32]; SEGMENT_PROOF_LEN]);
+
+pub trait SegmentSource {
+    /// Segment `segment` of the tape numbered `tape`.
+    fn segment(&self, tape: u64, segment: u64) -> Option<RecallSegment>;
+
+    /// The same segment already packed against the miner's key, if this
+    /// source keeps one.
+    fn packed_segment(&self, _tape: u64, _segment: u64) -> Option<packx::Solution> {
+        None
+    }
+}
+
+/// Holds no segments; enough for mining tapes that pay no rent.
+pub struct NoSegments;
+
+impl SegmentSource for NoSegments {
+    fn segment(&self, _tape: u64, _segment: u64) -> Option<RecallSegment> {
+        None
+    }
+}
+
+/// Whole tapes kept in memory, with their trees.
+#[derive(Default)]
+pub struct MemoryTapes {
+    segments: HashMap<u64, Vec<[u8; SEGMENT_SIZE]>>,
+    proofs: ProofBuilder,
+}
+
+impl MemoryTapes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, number: u64, address: Pubkey, segments: Vec<[u8; SEGMENT_SIZE]>) {
+        self.proofs.insert_tape(number, &address, &segments);
+        self.segments.insert(number, segments);
+    }
+}
+
+impl SegmentSource for MemoryTapes {
+    fn segment(&self, tape: u64, segment: u64) -> Option<RecallSegment> {
+        let data = *self.segments.get(&tape)?.get(segment as usize)?;
+        let proof = self.proofs.segment_proof(tape, segment)?;
+        Some((data, proof.into_array()))
+    }
+}
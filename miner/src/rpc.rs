@@ -0,0 +1,79 @@
+//! The miner's view of the cluster.
+
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcProgramAccountsConfig;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use tape_sdk::accounts::tape_account_size;
+use tape_sdk::consts::MAX_SUBTREE_DEPTH;
+use tape_sdk::errors::tape_error;
+use tape_sdk::{Instruction, Pubkey, TAPE_PROGRAM_ID};
+
+use crate::MinerError;
+
+pub trait Rpc {
+    /// Data of the account at `address`, or `None` if it does not exist.
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, MinerError>;
+
+    /// Address of the tape numbered `number`, if there is one.
+    fn find_tape(&self, number: u64) -> Result<Option<Pubkey>, MinerError>;
+
+    /// Send `instructions` in one transaction paid and signed by `signer`,
+    /// returning once it has landed.
+    fn send(&mut self, signer: &Keypair, instructions: &[Instruction]) -> Result<(), MinerError>;
+}
+
+impl Rpc for RpcClient {
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, MinerError> {
+        let response = self
+            .get_account_with_commitment(address, self.commitment())
+            .map_err(|e| MinerError::Rpc(e.to_string()))?;
+        Ok(response.value.map(|account| account.data))
+    }
+
+    fn find_tape(&self, number: u64) -> Result<Option<Pubkey>, MinerError> {
+        // `Tape.number` leads the account; the size tells tapes from the
+        // other accounts that start with a number.
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                number.to_le_bytes().to_vec(),
+            ))]),
+            ..Default::default()
+        };
+
+        let accounts = self
+            .get_program_accounts_with_config(&TAPE_PROGRAM_ID, config)
+            .map_err(|e| MinerError::Rpc(e.to_string()))?;
+
+        Ok(accounts
+            .into_iter()
+            .find(|(_, account)| {
+                (0..=MAX_SUBTREE_DEPTH).any(|depth| account.data.len() == tape_account_size(depth))
+            })
+            .map(|(address, _)| address))
+    }
+
+    fn send(&mut self, signer: &Keypair, instructions: &[Instruction]) -> Result<(), MinerError> {
+        let blockhash = self
+            .get_latest_blockhash()
+            .map_err(|e| MinerError::Rpc(e.to_string()))?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+
+        self.send_and_confirm_transaction(&transaction)
+            .map(|_| ())
+            .map_err(|e| match e.get_transaction_error() {
+                Some(TransactionError::InstructionError(_, err)) => match tape_error(&err) {
+                    Some(err) => MinerError::Program(err),
+                    None => MinerError::Rpc(err.to_string()),
+                },
+                _ => MinerError::Rpc(e.to_string()),
+            })
+    }
+}
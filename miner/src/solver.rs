@@ -0,0 +1,187 @@
+//! Multithreaded CrankX search for `PoW`.
+//!
+//! Each thread walks its own stride of the nonce space. The search stops at
+//! the first solution meeting the difficulty, when the caller raises the
+//! cancel flag (say, because the block challenge changed), or at the
+//! deadline, where the best solution seen so far is returned instead.
+//! `Search::next_nonce` lets a caller resume where a stopped search left off.
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tape_api::consts::SEGMENT_SIZE;
+use tape_api::types::PoW;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SolverConfig {
+    /// Threads to search with (at least one is used).
+    pub threads: usize,
+    /// Give up at this point and return the best solution found, even if it
+    /// falls short of the difficulty.
+    pub deadline: Option<Instant>,
+    /// First nonce; thread `t` of `n` tries `start + t`, `start + t + n`, ...
+    pub start_nonce: u64,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            deadline: None,
+            start_nonce: 0,
+        }
+    }
+}
+
+/// A solution and its difficulty.
+pub struct Solved {
+    pub solution: crankx::Solution,
+    pub difficulty: u32,
+}
+
+impl Solved {
+    pub fn pow(&self) -> PoW {
+        PoW::from_solution(&self.solution)
+    }
+}
+
+/// How a search ended.
+pub struct Search {
+    /// The solution that met the difficulty, or the best one found before
+    /// the deadline. `None` if cancelled or nothing was found at all.
+    pub best: Option<Solved>,
+    /// Nonces tried across all threads.
+    pub hashes: u64,
+    pub elapsed: Duration,
+    /// Where to set `start_nonce` to carry on without skipping a nonce.
+    /// Threads stop at different points, so this is where the slowest one
+    /// stopped; the nonces faster threads tried past it are tried again.
+    pub next_nonce: u64,
+}
+
+impl Search {
+    pub fn hashes_per_second(&self) -> f64 {
+        self.hashes as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Search for a solution of `difficulty` or better over `challenge` and
+/// `segment`.
+pub fn solve(
+    challenge: &[u8; 32],
+    segment: &[u8; SEGMENT_SIZE],
+    difficulty: u64,
+    config: &SolverConfig,
+    cancel: &AtomicBool,
+) -> Search {
+    let threads = config.threads.max(1) as u64;
+    let started = Instant::now();
+
+    let done = AtomicBool::new(false);
+    let hashes = AtomicU64::new(0);
+    let stride = AtomicU64::new(u64::MAX);
+    let best: Mutex<Option<Solved>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for thread in 0..threads {
+            let (done, hashes, stride, best) = (&done, &hashes, &stride, &best);
+
+            scope.spawn(move || {
+                let mut tried = 0u64;
+
+                while !done.load(Ordering::Relaxed) && !cancel.load(Ordering::Relaxed) {
+                    if config.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        break;
+                    }
+
+                    let nonce = config
+                        .start_nonce
+                        .wrapping_add(thread)
+                        .wrapping_add(tried.wrapping_mul(threads));
+                    let result = crankx::solve(challenge, segment, &nonce.to_le_bytes());
+                    hashes.fetch_add(1, Ordering::Relaxed);
+                    tried += 1;
+
+                    let Ok(solution) = result else {
+                        continue;
+                    };
+                    let found = solution.difficulty();
+
+                    let mut best = best.lock().expect("solver thread panicked");
+                    if !best.as_ref().is_some_and(|b| found <= b.difficulty) {
+                        *best = Some(Solved {
+                            solution,
+                            difficulty: found,
+                        });
+                    }
+                    if found as u64 >= difficulty {
+                        done.store(true, Ordering::Relaxed);
+                    }
+                }
+
+                stride.fetch_min(tried, Ordering::Relaxed);
+            });
+        }
+    });
+
+    let best = best.into_inner().expect("solver thread panicked");
+    Search {
+        best: best.filter(|_| !cancel.load(Ordering::Relaxed)),
+        hashes: hashes.into_inner(),
+        elapsed: started.elapsed(),
+        next_nonce: config
+            .start_nonce
+            .wrapping_add(stride.into_inner().wrapping_mul(threads)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHALLENGE: [u8; 32] = [3; 32];
+    const SEGMENT: [u8; SEGMENT_SIZE] = [9; SEGMENT_SIZE];
+
+    #[test]
+    fn finds_a_valid_solution() {
+        for threads in [1, 4] {
+            let config = SolverConfig {
+                threads,
+                ..SolverConfig::default()
+            };
+            let search = solve(&CHALLENGE, &SEGMENT, 1, &config, &AtomicBool::new(false));
+            assert!(search.hashes > 0);
+            assert!(search.next_nonce <= search.hashes);
+
+            let solved = search.best.unwrap();
+            assert!(solved.difficulty >= 1);
+            assert!(solved.pow().as_solution().is_valid(&CHALLENGE, &SEGMENT).is_ok());
+        }
+    }
+
+    #[test]
+    fn cancel_stops_the_search() {
+        let config = SolverConfig::default();
+        let search = solve(&CHALLENGE, &SEGMENT, 1, &config, &AtomicBool::new(true));
+        assert!(search.best.is_none());
+        assert_eq!(search.hashes, 0);
+    }
+
+    #[test]
+    fn deadline_returns_the_best_so_far() {
+        let config = SolverConfig {
+            threads: 2,
+            deadline: Some(Instant::now() + Duration::from_millis(200)),
+            start_nonce: 0,
+        };
+
+        // Out of reach, so only the deadline ends the search.
+        let search = solve(&CHALLENGE, &SEGMENT, 200, &config, &AtomicBool::new(false));
+        assert!(search.hashes_per_second() > 0.0);
+
+        let solved = search.best.unwrap();
+        assert!(solved.difficulty < 200);
+        assert!(solved.pow().as_solution().is_valid(&CHALLENGE, &SEGMENT).is_ok());
+    }
+}
@@ -0,0 +1,450 @@
+//! Recalled segments, pre-packed on disk.
+//!
+//! Packing a segment against the miner's key takes far longer than a block,
+//! so a miner packs every segment of the tapes it stores ahead of time.
+//! Each tape gets two files under the store's directory:
+//!
+//! - `<number>.packed`: a `StoreHeader`, then one `PACKED_SEGMENT_SIZE`
+//!   record per segment, in segment order.
+//! - `<number>.tree`: the tape's `FullTree::levels`, leaves first, each
+//!   level half the length of the one below (rounded up). Missing right-hand
+//!   nodes are empty subtrees, so proofs are 18 reads.
+//!
+//! `scan` re-derives both files from the packed records and reports what no
+//! longer matches.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use bytemuck::{Pod, Zeroable};
+use tape_api::utils::compute_leaf;
+use tape_sdk::consts::*;
+use tape_sdk::prover::FullTree;
+use tape_sdk::Pubkey;
+
+use crate::recall::{RecallSegment, SegmentSource};
+use crate::MinerError;
+
+const STORE_MAGIC: [u8; 8] = *b"TAPEPACK";
+
+type SegmentLevels = FullTree<SEGMENT_TREE_HEIGHT>;
+
+/// Leading bytes of a `.packed` file.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct StoreHeader {
+    pub magic: [u8; 8],
+    /// Tape account the segments belong to.
+    pub tape: [u8; 32],
+    /// Miner account the segments are packed against.
+    pub miner: [u8; 32],
+    /// Segment tree root of the stored segments.
+    pub root: [u8; 32],
+    pub number: u64,
+    pub total_segments: u64,
+    /// Difficulty every record was packed to.
+    pub difficulty: u64,
+}
+
+/// What `scan` found for one tape.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScanReport {
+    pub total_segments: u64,
+    /// Records that are unreadable, too easy, or whose leaf no longer
+    /// matches the tree index.
+    pub corrupt: Vec<u64>,
+    /// Whether the tree index hashes up to the header's root.
+    pub tree_ok: bool,
+}
+
+impl ScanReport {
+    pub fn is_clean(&self) -> bool {
+        self.corrupt.is_empty() && self.tree_ok
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PackedStore {
+    dir: PathBuf,
+    miner: Pubkey,
+}
+
+impl PackedStore {
+    /// Open (creating it if needed) the store at `dir`, for the miner account
+    /// `miner`.
+    pub fn open(dir: impl Into<PathBuf>, miner: Pubkey) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, miner })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn packed_path(&self, number: u64) -> PathBuf {
+        self.dir.join(format!("{number}.packed"))
+    }
+
+    fn tree_path(&self, number: u64) -> PathBuf {
+        self.dir.join(format!("{number}.tree"))
+    }
+
+    /// Numbers of the tapes held, in ascending order.
+    pub fn tapes(&self) -> io::Result<Vec<u64>> {
+        let mut numbers = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "packed") {
+                if let Some(number) = path.file_stem().and_then(|s| s.to_str()?.parse().ok()) {
+                    numbers.push(number);
+                }
+            }
+        }
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+
+    pub fn contains(&self, number: u64) -> bool {
+        self.packed_path(number).exists() && self.tree_path(number).exists()
+    }
+
+    /// Pack `segments`, the whole of tape `number` at `address`, to
+    /// `difficulty` and store them with their tree index. Replaces whatever
+    /// was stored for `number` before.
+    pub fn spool(
+        &self,
+        number: u64,
+        address: &Pubkey,
+        segments: &[[u8; SEGMENT_SIZE]],
+        difficulty: u64,
+    ) -> Result<StoreHeader, MinerError> {
+        let packed = self.pack(number, segments, difficulty)?;
+        let leaves = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| compute_leaf(i as u64, segment).to_bytes());
+        let levels = SegmentLevels::from_leaves(&[address.as_ref()], leaves);
+
+        let header = StoreHeader {
+            magic: STORE_MAGIC,
+            tape: address.to_bytes(),
+            miner: self.miner.to_bytes(),
+            root: levels.root(),
+            number,
+            total_segments: segments.len() as u64,
+            difficulty,
+        };
+
+        // The tree goes first: a `.packed` file without its index is not
+        // picked up by `contains`.
+        let mut tree = BufWriter::new(File::create(self.tree_path(number))?);
+        for node in levels.levels().iter().flatten() {
+            tree.write_all(node)?;
+        }
+        tree.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+
+        let mut file = BufWriter::new(File::create(self.packed_path(number))?);
+        file.write_all(bytemuck::bytes_of(&header))?;
+        for solution in &packed {
+            file.write_all(&to_record(solution))?;
+        }
+        file.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+
+        Ok(header)
+    }
+
+    /// Pack on every core; the order of the result matches `segments`.
+    fn pack(
+        &self,
+        number: u64,
+        segments: &[[u8; SEGMENT_SIZE]],
+        difficulty: u64,
+    ) -> Result<Vec<packx::Solution>, MinerError> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = segments.len().div_ceil(threads).max(1);
+        let miner = self.miner.to_bytes();
+
+        let chunks: Vec<Vec<Option<packx::Solution>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = segments
+                .chunks(chunk)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|segment| packx::solve(&miner, segment, difficulty as u32))
+                            .collect()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("packing thread panicked"))
+                .collect()
+        });
+
+        chunks
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, solution)| {
+                solution.ok_or(MinerError::PackFailed {
+                    tape: number,
+                    segment: i as u64,
+                })
+            })
+            .collect()
+    }
+
+    /// Delete tape `number`; a no-op if it is not stored.
+    pub fn remove(&self, number: u64) -> io::Result<()> {
+        for path in [self.packed_path(number), self.tree_path(number)] {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn header(&self, number: u64) -> io::Result<StoreHeader> {
+        let mut file = File::open(self.packed_path(number))?;
+        self.read_header(&mut file)
+    }
+
+    fn read_header(&self, file: &mut File) -> io::Result<StoreHeader> {
+        let mut header = StoreHeader::zeroed();
+        file.read_exact(bytemuck::bytes_of_mut(&mut header))?;
+
+        if header.magic != STORE_MAGIC {
+            return Err(invalid("not a packed segment file"));
+        }
+        if header.miner != self.miner.to_bytes() {
+            return Err(invalid("packed against a different miner"));
+        }
+        Ok(header)
+    }
+
+    /// The packed record for segment `segment` of tape `number`.
+    pub fn packed(&self, number: u64, segment: u64) -> io::Result<packx::Solution> {
+        let mut file = File::open(self.packed_path(number))?;
+        let header = self.read_header(&mut file)?;
+        if segment >= header.total_segments {
+            return Err(invalid("segment past the end of the tape"));
+        }
+
+        let mut record = [0u8; PACKED_SEGMENT_SIZE];
+        file.seek(SeekFrom::Current((segment as usize * PACKED_SEGMENT_SIZE) as i64))?;
+        file.read_exact(&mut record)?;
+        Ok(from_record(&record))
+    }
+
+    /// Full proof for segment `segment` of tape `number`, read from the tree
+    /// index.
+    pub fn proof(&self, number: u64, segment: u64) -> io::Result<[[u8; 32]; SEGMENT_PROOF_LEN]> {
+        let header = self.header(number)?;
+        if segment >= header.total_segments {
+            return Err(invalid("segment past the end of the tape"));
+        }
+
+        let empty = SegmentLevels::new(&[header.tape.as_ref()]);
+        let lens = SegmentLevels::level_lens(header.total_segments as usize);
+        let mut tree = File::open(self.tree_path(number))?;
+        let mut proof = [[0u8; 32]; SEGMENT_PROOF_LEN];
+        let mut offset = 0;
+
+        for (level, len) in lens.into_iter().enumerate() {
+            let index = (segment as usize >> level) ^ 1;
+            if index < len {
+                tree.seek(SeekFrom::Start(((offset + index) * 32) as u64))?;
+                tree.read_exact(&mut proof[level])?;
+            } else {
+                proof[level] = empty.empty_subtree(level);
+            }
+            offset += len;
+        }
+
+        Ok(proof)
+    }
+
+    /// Check every record of tape `number` against its tree index, and the
+    /// index against the header's root.
+    pub fn scan(&self, number: u64) -> io::Result<ScanReport> {
+        let mut file = File::open(self.packed_path(number))?;
+        let header = self.read_header(&mut file)?;
+        let total = header.total_segments as usize;
+
+        let levels = read_levels(&self.tree_path(number), total)?;
+        let tree = SegmentLevels::from_levels(&[header.tape.as_ref()], levels)
+            .ok_or_else(|| invalid("malformed tree index"))?;
+        let mut report = ScanReport {
+            total_segments: header.total_segments,
+            ..ScanReport::default()
+        };
+
+        let mut records = BufReader::new(file);
+        let mut record = [0u8; PACKED_SEGMENT_SIZE];
+        for i in 0..total {
+            if records.read_exact(&mut record).is_err() {
+                report.corrupt.extend(i as u64..header.total_segments);
+                break;
+            }
+
+            let solution = from_record(&record);
+            let segment = solution.unpack(&header.miner);
+            let leaf = compute_leaf(i as u64, &segment).to_bytes();
+            if (solution.difficulty() as u64) < header.difficulty || tree.leaf(i) != Some(leaf) {
+                report.corrupt.push(i as u64);
+            }
+        }
+
+        // Rehash each level from the one below and compare.
+        report.tree_ok = tree.root() == header.root && tree.is_consistent();
+
+        Ok(report)
+    }
+}
+
+impl SegmentSource for PackedStore {
+    fn segment(&self, tape: u64, segment: u64) -> Option<RecallSegment> {
+        let data = self.packed(tape, segment).ok()?.unpack(&self.miner.to_bytes());
+        let proof = self.proof(tape, segment).ok()?;
+        Some((data, proof))
+    }
+
+    fn packed_segment(&self, tape: u64, segment: u64) -> Option<packx::Solution> {
+        self.packed(tape, segment).ok()
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn to_record(solution: &packx::Solution) -> [u8; PACKED_SEGMENT_SIZE] {
+    let mut record = [0u8; PACKED_SEGMENT_SIZE];
+    record[..8].copy_from_slice(&solution.bump);
+    record[8..24].copy_from_slice(&solution.seeds);
+    record[24..].copy_from_slice(&solution.nonces);
+    record
+}
+
+fn from_record(record: &[u8; PACKED_SEGMENT_SIZE]) -> packx::Solution {
+    packx::Solution::new(
+        record[8..24].try_into().unwrap(),
+        record[24..].try_into().unwrap(),
+        record[..8].try_into().unwrap(),
+    )
+}
+
+fn read_levels(path: &Path, total: usize) -> io::Result<Vec<Vec<[u8; 32]>>> {
+    let mut tree = BufReader::new(File::open(path)?);
+    SegmentLevels::level_lens(total)
+        .into_iter()
+        .map(|len| {
+            let mut nodes = vec![[0u8; 32]; len];
+            tree.read_exact(bytemuck::cast_slice_mut(&mut nodes))?;
+            Ok(nodes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recall::MemoryTapes;
+    use tape_sdk::types::SegmentTree;
+
+    fn segments(count: usize) -> Vec<[u8; SEGMENT_SIZE]> {
+        (0..count)
+            .map(|i| [(i % 251) as u8 + 1; SEGMENT_SIZE])
+            .collect()
+    }
+
+    /// A fresh store in the system temp directory.
+    fn store(name: &str) -> PackedStore {
+        let dir = std::env::temp_dir().join(format!("tape-store-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        PackedStore::open(dir, Pubkey::new_unique()).unwrap()
+    }
+
+    #[test]
+    fn recalls_match_memory_tapes() {
+        let store = store("recall");
+        let address = Pubkey::new_unique();
+        let data = segments(5);
+
+        let header = store.spool(3, &address, &data, 0).unwrap();
+        assert_eq!(header.total_segments, 5);
+        assert_eq!(store.tapes().unwrap(), vec![3]);
+
+        let mut memory = MemoryTapes::new();
+        memory.insert(3, address, data.clone());
+
+        for i in 0..5 {
+            assert_eq!(store.segment(3, i), memory.segment(3, i));
+        }
+        assert_eq!(store.segment(3, 5), None);
+        assert_eq!(store.segment(4, 0), None);
+
+        let mut tree = SegmentTree::new(&[address.as_ref()]);
+        for (i, segment) in data.iter().enumerate() {
+            tree.try_add_leaf(compute_leaf(i as u64, segment)).unwrap();
+        }
+        assert_eq!(header.root, tree.get_root().to_bytes());
+    }
+
+    #[test]
+    fn scan_finds_corrupt_records() {
+        let store = store("scan");
+        store.spool(1, &Pubkey::new_unique(), &segments(4), 0).unwrap();
+        assert!(store.scan(1).unwrap().is_clean());
+
+        // Flip a byte in the nonces of record 2.
+        let path = store.packed_path(1);
+        let mut bytes = fs::read(&path).unwrap();
+        let at = std::mem::size_of::<StoreHeader>() + 2 * PACKED_SEGMENT_SIZE + 40;
+        bytes[at] ^= 0xff;
+        fs::write(&path, &bytes).unwrap();
+
+        let report = store.scan(1).unwrap();
+        assert_eq!(report.corrupt, vec![2]);
+        assert!(report.tree_ok);
+
+        // A truncated file loses the records past the cut.
+        bytes.truncate(std::mem::size_of::<StoreHeader>() + 3 * PACKED_SEGMENT_SIZE);
+        fs::write(&path, &bytes).unwrap();
+        assert_eq!(store.scan(1).unwrap().corrupt, vec![2, 3]);
+    }
+
+    #[test]
+    fn scan_finds_a_corrupt_index() {
+        let store = store("index");
+        store.spool(1, &Pubkey::new_unique(), &segments(6), 0).unwrap();
+
+        // Damage a node above the leaves.
+        let path = store.tree_path(1);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[6 * 32] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+
+        let report = store.scan(1).unwrap();
+        assert!(report.corrupt.is_empty());
+        assert!(!report.tree_ok);
+    }
+
+    #[test]
+    fn other_miners_cannot_read_the_store() {
+        let store = store("miner");
+        store.spool(1, &Pubkey::new_unique(), &segments(1), 0).unwrap();
+
+        let other = PackedStore::open(store.dir(), Pubkey::new_unique()).unwrap();
+        assert!(other.header(1).is_err());
+        assert_eq!(other.segment(1, 0), None);
+
+        store.remove(1).unwrap();
+        assert!(!store.contains(1));
+        store.remove(1).unwrap();
+    }
+}
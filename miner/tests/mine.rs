@@ -0,0 +1,179 @@
+//! Runs the mining loop against the compiled program through mollusk.
+//!
+//! Needs the program's ELF; build it first with
+//! `cargo build-sbf --manifest-path program/Cargo.toml`.
+
+#[path = "../../program/tests/support/mod.rs"]
+mod support;
+
+use std::time::Duration;
+
+use bytemuck::Zeroable;
+use mollusk_svm::result::ProgramResult;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::signature::{Keypair, Signer};
+use support::*;
+use tape_miner::recall::NoSegments;
+use tape_miner::rpc::Rpc;
+use tape_miner::{MinerClient, MinerError, RunConfig};
+use tape_sdk::accounts::*;
+use tape_sdk::errors::tape_error;
+use tape_sdk::pda::*;
+use tape_sdk::types::to_name;
+use tape_sdk::{Instruction, Pubkey, TAPE_PROGRAM_ID};
+
+/// Stands in for a validator: instructions run in the test harness and the
+/// accounts they leave behind are kept.
+struct MolluskRpc(Harness);
+
+impl Rpc for MolluskRpc {
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, MinerError> {
+        Ok(self
+            .0
+            .stored()
+            .find(|(key, account)| *key == address && account.lamports > 0)
+            .map(|(_, account)| account.data.clone()))
+    }
+
+    fn find_tape(&self, number: u64) -> Result<Option<Pubkey>, MinerError> {
+        Ok(self
+            .0
+            .stored()
+            .find(|(_, account)| {
+                account.owner == TAPE_PROGRAM_ID
+                    && decode_tape_with_nodes(&account.data)
+                        .is_ok_and(|(tape, _)| tape.number == number)
+            })
+            .map(|(address, _)| *address))
+    }
+
+    fn send(&mut self, _signer: &Keypair, instructions: &[Instruction]) -> Result<(), MinerError> {
+        for ix in instructions {
+            match self.0.run(ix).program_result {
+                ProgramResult::Success => {}
+                ProgramResult::Failure(ProgramError::Custom(code)) => {
+                    let err = InstructionError::Custom(code);
+                    return Err(tape_error(&err).map_or(
+                        MinerError::Rpc(format!("custom error {code}")),
+                        MinerError::Program,
+                    ));
+                }
+                other => return Err(MinerError::Rpc(format!("{other:?}"))),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A cluster past genesis: one tape that pays no rent, and difficulties low
+/// enough that any solution passes.
+fn cluster(authority: &Pubkey) -> MolluskRpc {
+    let mut h = Harness::new();
+    h.fund(*authority);
+
+    genesis(
+        &mut h,
+        Genesis {
+            target_participation: 1_000,
+            ..Genesis::default()
+        },
+    );
+    h.set_state(
+        archive_pda().0,
+        &Archive {
+            tapes_stored: 1,
+            segments_stored: 1,
+        },
+    );
+
+    let mut tape = Tape::zeroed();
+    tape.number = 1;
+    tape.total_segments = 1;
+    tape.state = TapeState::Finalized as u64;
+    let (address, _bump) = tape_pda(Pubkey::new_unique(), &to_name("recalled"));
+    h.set_state(address, &tape);
+    open_challenge_set(&mut h);
+
+    MolluskRpc(h)
+}
+
+fn next_block(rpc: &mut MolluskRpc) {
+    let address = block_pda().0;
+    let mut block: Block = rpc.0.state(&address);
+    block.number += 1;
+    block.challenge[0] += 1;
+    rpc.0.set_state(address, &block);
+}
+
+#[test]
+fn mines_one_proof_per_block() {
+    let signer = Keypair::new();
+    let authority = signer.pubkey();
+    let rpc = cluster(&authority);
+    let mut client = MinerClient::new(rpc, signer, to_name("test"), NoSegments);
+
+    client.register().unwrap();
+    // Registering again is a no-op.
+    client.register().unwrap();
+
+    let report = client.mine_once().unwrap().expect("block not proven yet");
+    assert_eq!(report.block, 1);
+    assert_eq!(report.tape, 1);
+    assert_eq!(report.segment, None);
+
+    // Same block: nothing to do until it advances.
+    assert_eq!(client.mine_once().unwrap(), None);
+
+    let miner = client.miner().unwrap();
+    assert_eq!(miner.total_proofs, 1);
+    assert_eq!(miner.last_proof_block, 1);
+}
+
+#[test]
+fn run_stops_after_max_proofs() {
+    let signer = Keypair::new();
+    let authority = signer.pubkey();
+    let rpc = cluster(&authority);
+    let mut client = MinerClient::new(rpc, signer, to_name("runner"), NoSegments);
+    client.register().unwrap();
+
+    let config = RunConfig {
+        poll: Duration::ZERO,
+        claim_every: 0,
+        max_proofs: Some(1),
+    };
+
+    let mut reports = Vec::new();
+    client.run(&config, |report| reports.push(report.clone())).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(client.miner().unwrap().total_proofs, 1);
+}
+
+#[test]
+fn mining_follows_the_block() {
+    let signer = Keypair::new();
+    let authority = signer.pubkey();
+    let mut client = MinerClient::new(cluster(&authority), signer, to_name("follow"), NoSegments);
+    client.register().unwrap();
+
+    assert_eq!(client.mine_once().unwrap().unwrap().block, 1);
+    next_block(client.rpc_mut());
+    assert_eq!(client.mine_once().unwrap().unwrap().block, 2);
+
+    let miner = client.miner().unwrap();
+    assert_eq!(miner.total_proofs, 2);
+    assert_eq!(miner.last_proof_block, 2);
+}
+
+#[test]
+fn unregister_closes_the_miner() {
+    let signer = Keypair::new();
+    let authority = signer.pubkey();
+    let mut client = MinerClient::new(cluster(&authority), signer, to_name("leaver"), NoSegments);
+    client.register().unwrap();
+    client.unregister().unwrap();
+
+    assert!(client.rpc().account_data(&client.address()).unwrap().is_none());
+    assert!(matches!(client.miner(), Err(MinerError::MissingAccount(_))));
+}
@@ -1,122 +1,192 @@
-// use mollusk_svm::{program, Mollusk};
-// use mollusk_svm_bencher::MolluskComputeUnitBencher;
-// use solana_pinocchio_starter::{
-//     instruction::{InitializeMyStateV1IxData, UpdateMyStateV1IxData},
-//     instruction::{InitializeMyStateV2IxData, UpdateMyStateV2IxData},
-//     state::{to_bytes, DataLen, MyStateV1,MyStateV2},
-//     ID,
-// };
-// use solana_sdk::{
-//     account::Account,
-//     instruction::{AccountMeta, Instruction},
-//     native_token::LAMPORTS_PER_SOL,
-//     pubkey::Pubkey,
-//     sysvar::Sysvar,
-// };
-// use solana_sdk::{pubkey, rent::Rent};
-
-// pub const PROGRAM: Pubkey = Pubkey::new_from_array(ID);
-
-// pub const RENT: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
-
-// pub const PAYER: Pubkey = pubkey!("41LzznNicELmc5iCR9Jxke62a3v1VhzpBYodQF5AQwHX");
-
-// pub fn get_rent_data() -> Vec<u8> {
-//     let rent = Rent::default();
-//     unsafe {
-//         core::slice::from_raw_parts(&rent as *const Rent as *const u8, Rent::size_of()).to_vec()
-//     }
-// }
-
-// fn main() {
-//     let mollusk = Mollusk::new(&PROGRAM, "target/deploy/solana_pinocchio_starter");
-
-//     let (system_program, system_account) = program::keyed_account_for_system_program();
-
-//     // Create the PDA
-//     let (mystate_pda, bump) =
-//         Pubkey::find_program_address(&[MyStateV2::SEED.as_bytes(), &PAYER.to_bytes()], &PROGRAM);
-
-//     //Initialize the accounts
-//     let payer_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
-//     let mystate_account = Account::new(0, 0, &system_program);
-//     let min_balance = mollusk.sysvars.rent.minimum_balance(Rent::size_of());
-//     let mut rent_account = Account::new(min_balance, Rent::size_of(), &RENT);
-//     rent_account.data = get_rent_data();
-//     //Push the accounts in to the instruction_accounts vec!
-//     let ix_accounts = vec![
-//         AccountMeta::new(PAYER, true),
-//         AccountMeta::new(mystate_pda, false),
-//         AccountMeta::new_readonly(RENT, false),
-//         AccountMeta::new_readonly(system_program, false),
-//     ];
-
-//     // Create the instruction data
-//     let ix_data = InitializeMyStateV2IxData {
-//         owner: *PAYER.as_array(),
-//         data: [1; 32],
-//     };
-
-//     // Ix discriminator = 0
-//     let mut ser_ix_data = vec![0];
-
-//     // Serialize the instruction data
-//     ser_ix_data.extend_from_slice(unsafe { to_bytes(&ix_data) });
-
-//     // Create instruction
-//     let instruction0 = Instruction::new_with_bytes(PROGRAM, &ser_ix_data, ix_accounts);
-
-//     // Create tx_accounts vec
-//     let tx_accounts0 = &vec![
-//         (PAYER, payer_account.clone()),
-//         (mystate_pda, mystate_account.clone()),
-//         (RENT, rent_account.clone()),
-//         (system_program, system_account.clone()),
-//     ];
-
-//     let rent = mollusk.sysvars.rent.minimum_balance(MyStateV2::LEN);
-//     let mut mystate_account = Account::new(rent, MyStateV2::LEN, &ID.into());
-
-//     let my_state = MyStateV2 {
-//         is_initialized: 1,
-//         owner: *PAYER.as_array(),
-//         state: 1,
-//         data: [1; 32],
-//         update_count: 0,
-//         bump,
-//         _padding: 1
-
-//     };
-
-//     mystate_account.data = unsafe { to_bytes(&my_state).to_vec() };
-
-//     //Push the accounts in to the instruction_accounts vec!
-//     let ix_accounts = vec![
-//         AccountMeta::new(PAYER, true),
-//         AccountMeta::new(mystate_pda, false),
-//     ];
-
-//     // Create the instruction data
-//     let ix_data = UpdateMyStateV2IxData { data: [1; 32] };
-
-//     // Ix discriminator = 1
-//     let mut ser_ix_data = vec![1];
-
-//     // Serialize the instruction data
-//     ser_ix_data.extend_from_slice(unsafe { to_bytes(&ix_data) });
-
-//     // Create instruction
-//     let instruction1 = Instruction::new_with_bytes(PROGRAM, &ser_ix_data, ix_accounts);
-//     // Create tx_accounts vec
-//     let tx_accounts1 = &vec![
-//         (PAYER, payer_account.clone()),
-//         (mystate_pda, mystate_account.clone()),
-//     ];
-
-//     MolluskComputeUnitBencher::new(mollusk)
-//         .bench(("InitializeMyState", &instruction0, tx_accounts0))
-//         .bench(("UpdateMyState", &instruction1, tx_accounts1))
-//         .must_pass(true)
-//         .out_dir("benches/")
-//         .execute();
-// }
+//! Compute units for every instruction, at the sizes clients send them.
+//!
+//! The cases run in lifecycle order against one `Harness`: each snapshots
+//! the accounts its instruction touches, then applies it so the next case
+//! starts from the state it left behind. `execute` prepends a table to
+//! `benches/compute_units.md` with the change since the last run.
+//!
+//! `Initialize` is left out while it only validates its accounts, and
+//! `EmitEvent` only runs as a self-CPI inside the other instructions.
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path program/Cargo.toml
+//! cargo bench -p solana-pinocchio-starter --features bench-default
+//! ```
+
+#[path = "../tests/support/mod.rs"]
+mod support;
+
+use mollusk_svm_bencher::MolluskComputeUnitBencher;
+use solana_sdk::account::Account;
+use support::*;
+use tape_api::rent::min_finalization_rent;
+use tape_sdk::accounts::Miner;
+use tape_sdk::consts::*;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::prover::ProofBuilder;
+use tape_sdk::types::*;
+use tape_sdk::{Instruction, Pubkey};
+
+/// Tapes a spool holds before the large `SpoolPack` case.
+const FULL_SPOOL: u64 = 500;
+
+type Case = (&'static str, Instruction, Vec<(Pubkey, Account)>);
+
+/// Record `ix` against the current state, then apply it.
+fn case(h: &mut Harness, cases: &mut Vec<Case>, name: &'static str, ix: Instruction) {
+    let accounts = h.accounts(&ix);
+    h.run_ok(&ix);
+    cases.push((name, ix, accounts));
+}
+
+/// A copy of the finalized `tape` at a fresh address, renumbered to
+/// `number`. Packing only reads the number and state, so this stands in for
+/// writing and finalizing hundreds of real tapes.
+fn copy_tape(h: &mut Harness, tape: Pubkey, number: u64) -> Pubkey {
+    let address = Pubkey::new_unique();
+    let account = h.get(&tape);
+    h.set(address, account);
+
+    let mut copy = h.tape(&address);
+    copy.number = number;
+    h.set_tape(address, &copy);
+    address
+}
+
+fn main() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(10_000_000_000);
+    let mut cases = Vec::new();
+
+    // Tapes
+
+    let name = to_name("bench");
+    let (tape, _bump) = tape_pda(signer, &name);
+    let mut data = segments(15);
+    case(&mut h, &mut cases, "TapeCreate", build_create_tape_ix(signer, name, 0));
+
+    for (label, range) in [
+        ("TapeWrite (1 segment)", 0..1),
+        ("TapeWrite (4 segments)", 1..5),
+        ("TapeWrite (8 segments)", 5..13),
+    ] {
+        let ix = build_write_ix(signer, tape, bytemuck::cast_slice(&data[range]));
+        case(&mut h, &mut cases, label, ix);
+    }
+
+    // (lands past the end, then the gap is filled so the tape can finalize)
+    let ix = build_write_at_ix(signer, tape, 14, &data[14]);
+    case(&mut h, &mut cases, "TapeWriteAt (1 segment)", ix);
+    h.run_ok(&build_write_at_ix(signer, tape, 13, &data[13]));
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_tape(0, &tape, &data);
+
+    let new = [0xEE; SEGMENT_SIZE];
+    let proof = proofs.segment_proof(0, 3).unwrap();
+    let ix = build_update_ix(signer, tape, 3, data[3], new, proof.as_array());
+    case(&mut h, &mut cases, "TapeUpdate", ix);
+    proofs.update_segment(0, 3, &new);
+    data[3] = new;
+
+    let changed = [0u64, 5, 9, 14];
+    let updates: Vec<_> = changed
+        .iter()
+        .map(|&number| SegmentUpdate {
+            segment_number: number,
+            old_data: data[number as usize],
+            new_data: [0xB0 + number as u8; SEGMENT_SIZE],
+        })
+        .collect();
+    let proof = tape_sdk::proof::build_multiproof(&changed, |number| {
+        proofs.segment_proof(0, number).unwrap().into_array()
+    });
+    let ix = build_update_batch_ix(signer, tape, &updates, &proof);
+    case(&mut h, &mut cases, "TapeUpdateBatch (4 segments)", ix);
+    for update in &updates {
+        proofs.update_segment(0, update.segment_number, &update.new_data);
+        data[update.segment_number as usize] = update.new_data;
+    }
+
+    let ix = build_set_header_ix(signer, tape, [7; HEADER_SIZE]);
+    case(&mut h, &mut cases, "TapeSetHeader", ix);
+
+    let rent = min_finalization_rent(data.len() as u64);
+    let ix = build_subsidize_ix(signer, tape_ata(signer).0, tape, rent);
+    case(&mut h, &mut cases, "TapeSubsidize", ix);
+
+    case(&mut h, &mut cases, "TapeFinalize", build_finalize_ix(signer, tape));
+    open_challenge_set(&mut h);
+
+    // Mining
+
+    let name = to_name("bench");
+    let (miner, _bump) = miner_pda(signer, name);
+    case(&mut h, &mut cases, "MinerRegister", build_register_ix(signer, name));
+
+    let ix = mine_ix(&h, signer, miner, tape, &data);
+    case(&mut h, &mut cases, "MinerMine (full PoA)", ix);
+
+    // (the block moved on; without rent the proof skips the PoA)
+    let mut unpaid = h.tape(&tape);
+    unpaid.balance = 0;
+    h.set_tape(tape, &unpaid);
+    let ix = mine_ix(&h, signer, miner, tape, &data);
+    case(&mut h, &mut cases, "MinerMine (unpaid tape)", ix);
+
+    let rewards = h.state::<Miner>(&miner).unclaimed_rewards;
+    let ix = build_claim_ix(signer, tape_ata(signer).0, miner, rewards);
+    case(&mut h, &mut cases, "MinerClaim", ix);
+
+    // Spools
+
+    let (spool, _bump) = spool_pda(miner, 0);
+    case(&mut h, &mut cases, "SpoolCreate", build_create_spool_ix(signer, miner, 0));
+    proofs.insert_spool(&spool, &[]);
+
+    let number = h.tape(&tape).number;
+    let value = |i: u64| {
+        let mut value = [0; 32];
+        value[..8].copy_from_slice(&i.to_le_bytes());
+        value
+    };
+
+    h.run_ok(&build_pack_ix(signer, spool, tape, value(0)));
+    proofs.pack(&spool, number, &value(0));
+
+    let copy = copy_tape(&mut h, tape, number + 1);
+    let ix = build_pack_ix(signer, spool, copy, value(1));
+    case(&mut h, &mut cases, "SpoolPack (1 tape)", ix);
+    proofs.pack(&spool, number + 1, &value(1));
+
+    for i in 2..FULL_SPOOL {
+        let copy = copy_tape(&mut h, tape, number + i);
+        h.run_ok(&build_pack_ix(signer, spool, copy, value(i)));
+        proofs.pack(&spool, number + i, &value(i));
+    }
+
+    let copy = copy_tape(&mut h, tape, number + FULL_SPOOL);
+    let ix = build_pack_ix(signer, spool, copy, value(FULL_SPOOL));
+    case(&mut h, &mut cases, "SpoolPack (500 tapes)", ix);
+    proofs.pack(&spool, number + FULL_SPOOL, &value(FULL_SPOOL));
+
+    let proof = proofs.tape_proof(&spool, number).unwrap();
+    let ix = build_commit_ix(signer, miner, spool, number, proof, value(0));
+    case(&mut h, &mut cases, "SpoolCommit", ix);
+
+    let ix = build_unpack_ix(signer, spool, number, proof, value(0));
+    case(&mut h, &mut cases, "SpoolUnpack", ix);
+
+    case(&mut h, &mut cases, "SpoolDestroy", build_destroy_spool_ix(signer, spool));
+    case(&mut h, &mut cases, "MinerUnregister", build_unregister_ix(signer, miner));
+
+    let mut bencher = MolluskComputeUnitBencher::new(h.mollusk)
+        .must_pass(true)
+        .out_dir("benches/");
+    for (name, ix, accounts) in &cases {
+        bencher = bencher.bench((*name, ix, accounts.as_slice()));
+    }
+    bencher.execute();
+}
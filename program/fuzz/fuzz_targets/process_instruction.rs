@@ -0,0 +1,331 @@
+//! Arbitrary instructions through the compiled program.
+//!
+//! Each input picks a discriminator (usually a real one), arbitrary
+//! instruction data and a list of accounts drawn from a small pool of keys,
+//! so the program's PDAs, sysvars and signers turn up often and duplicates
+//! happen naturally. Account contents are sized like the program's own
+//! layouts (or not), filled with fuzzer bytes and owned by whichever program
+//! the input says.
+//!
+//! The program may reject anything, but only in ways we expect: one of the
+//! `ProgramError`s it returns, a custom code the program (or a program it
+//! calls) defines, or one of the runtime checks it relies on. A panic, abort
+//! or out-of-bounds access surfaces as `ProgramFailedToComplete`, and running
+//! out of compute units as `ComputationalBudgetExceeded`; both fail the run.
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path program/Cargo.toml
+//! cd program/fuzz && cargo +nightly fuzz run process_instruction
+//! ```
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mollusk_svm::program::{
+    create_program_account_loader_v3, keyed_account_for_system_program, loader_keys,
+};
+use mollusk_svm::result::ProgramResult;
+use mollusk_svm::Mollusk;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::slot_hashes::SlotHashes;
+use tape_api::instruction::TapeInstruction;
+use tape_sdk::accounts::*;
+use tape_sdk::consts::*;
+use tape_sdk::errors::TapeError;
+use tape_sdk::pda::*;
+use tape_sdk::types::to_name;
+use tape_sdk::{AccountMeta, Instruction, Pubkey, TAPE_PROGRAM_ID};
+
+/// Every discriminator `process_instruction` dispatches on.
+const DISCRIMINATORS: [TapeInstruction; 21] = [
+    TapeInstruction::Unknown,
+    TapeInstruction::Initialize,
+    TapeInstruction::Airdrop,
+    TapeInstruction::TapeCreate,
+    TapeInstruction::TapeWrite,
+    TapeInstruction::TapeUpdate,
+    TapeInstruction::TapeFinalize,
+    TapeInstruction::TapeSetHeader,
+    TapeInstruction::TapeSubsidize,
+    TapeInstruction::TapeUpdateBatch,
+    TapeInstruction::TapeWriteAt,
+    TapeInstruction::MinerRegister,
+    TapeInstruction::MinerUnregister,
+    TapeInstruction::MinerMine,
+    TapeInstruction::MinerClaim,
+    TapeInstruction::SpoolCreate,
+    TapeInstruction::SpoolDestroy,
+    TapeInstruction::SpoolPack,
+    TapeInstruction::SpoolUnpack,
+    TapeInstruction::SpoolCommit,
+    TapeInstruction::EmitEvent,
+];
+
+/// Highest custom code of the programs the tape program calls into: SPL
+/// Token's `TokenError` ends at 19 and the System program's at 8.
+const LAST_CPI_ERROR: u32 = 19;
+
+/// `ProgramError`s the program returns, itself or through the System and
+/// Token programs it calls. Any other one is a bug.
+const PROGRAM_ERRORS: [ProgramError; 13] = [
+    ProgramError::InvalidArgument,
+    ProgramError::InvalidInstructionData,
+    ProgramError::InvalidAccountData,
+    ProgramError::AccountDataTooSmall,
+    ProgramError::InsufficientFunds,
+    ProgramError::IncorrectProgramId,
+    ProgramError::MissingRequiredSignature,
+    ProgramError::AccountAlreadyInitialized,
+    ProgramError::UninitializedAccount,
+    ProgramError::NotEnoughAccountKeys,
+    ProgramError::AccountBorrowFailed,
+    ProgramError::Immutable,
+    ProgramError::InvalidAccountOwner,
+];
+
+/// Failures raised by the runtime rather than the program. The program
+/// relies on these instead of repeating them (it does not check
+/// `is_writable`, for one), so hitting them is not a bug.
+const RUNTIME_CHECKS: [InstructionError; 11] = [
+    InstructionError::ReadonlyDataModified,
+    InstructionError::ReadonlyLamportChange,
+    InstructionError::ExternalAccountDataModified,
+    InstructionError::ExternalAccountLamportSpend,
+    InstructionError::ExecutableDataModified,
+    InstructionError::ExecutableLamportChange,
+    InstructionError::UnbalancedInstruction,
+    InstructionError::ModifiedProgramId,
+    InstructionError::PrivilegeEscalation,
+    InstructionError::UnsupportedProgramId,
+    InstructionError::MissingAccount,
+];
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    /// Real discriminators by default, any byte otherwise.
+    raw_discriminator: bool,
+    discriminator: u8,
+    data: Vec<u8>,
+    accounts: Vec<Meta>,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Meta {
+    /// Index into the key pool.
+    key: u8,
+    signer: bool,
+    writable: bool,
+    /// Ignored for programs and sysvars, which always hold the real thing.
+    /// The first contents given for a key win.
+    contents: Contents,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Contents {
+    owner: Owner,
+    lamports: u32,
+    shape: Shape,
+    /// Repeated to fill the account data.
+    fill: Vec<u8>,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Owner {
+    Tape,
+    System,
+    Token,
+    Other(u8),
+}
+
+#[derive(Arbitrary, Debug)]
+enum Shape {
+    Empty,
+    Archive,
+    Epoch,
+    Block,
+    Tape(u8),
+    Writer,
+    Miner,
+    Spool,
+    Treasury,
+    TokenAccount,
+    Mint,
+    Raw(u16),
+}
+
+impl Shape {
+    fn len(&self) -> usize {
+        match self {
+            Shape::Empty => 0,
+            Shape::Archive => Archive::LEN,
+            Shape::Epoch => Epoch::LEN,
+            Shape::Block => Block::LEN,
+            // (one past the deepest valid cache, too)
+            Shape::Tape(depth) => tape_account_size(*depth as u64 % (MAX_SUBTREE_DEPTH + 2)),
+            Shape::Writer => writer_account_size(),
+            Shape::Miner => Miner::LEN,
+            Shape::Spool => Spool::LEN,
+            Shape::Treasury => Treasury::LEN,
+            Shape::TokenAccount => 165,
+            Shape::Mint => 82,
+            Shape::Raw(len) => *len as usize % 4096,
+        }
+    }
+}
+
+impl Contents {
+    fn account(&self) -> Account {
+        let len = self.shape.len();
+        let data = if self.fill.is_empty() {
+            vec![0; len]
+        } else {
+            self.fill.iter().copied().cycle().take(len).collect()
+        };
+        let owner = match self.owner {
+            Owner::Tape => TAPE_PROGRAM_ID,
+            Owner::System => SYSTEM_PROGRAM_ID,
+            Owner::Token => TOKEN_PROGRAM_ID,
+            Owner::Other(seed) => Pubkey::new_from_array([seed; 32]),
+        };
+
+        Account {
+            // (small enough that no sum of them overflows)
+            lamports: self.lamports as u64,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+struct Fixture {
+    mollusk: Mollusk,
+    keys: Vec<Pubkey>,
+    /// Accounts whose contents the input does not choose.
+    fixed: Vec<(Pubkey, Account)>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var(
+                "SBF_OUT_DIR",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+            );
+        }
+
+        let mut mollusk = Mollusk::new(&TAPE_PROGRAM_ID, "solana_pinocchio_starter");
+        mollusk.add_program(
+            &TOKEN_PROGRAM_ID,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/elfs/spl_token"),
+            &loader_keys::LOADER_V3,
+        );
+        mollusk.sysvars.clock.slot = 1;
+        mollusk.sysvars.slot_hashes = SlotHashes::new(&[(1, Hash::new_from_array([1; 32]))]);
+
+        let fixed = vec![
+            keyed_account_for_system_program(),
+            (TOKEN_PROGRAM_ID, create_program_account_loader_v3(&TOKEN_PROGRAM_ID)),
+            (TAPE_PROGRAM_ID, create_program_account_loader_v3(&TAPE_PROGRAM_ID)),
+            mollusk.sysvars.keyed_account_for_rent_sysvar(),
+            mollusk.sysvars.keyed_account_for_slot_hashes_sysvar(),
+        ];
+
+        let users: Vec<Pubkey> = (1..=3).map(|i| Pubkey::new_from_array([i; 32])).collect();
+        let name = to_name("fuzz");
+        let (tape, _bump) = tape_pda(users[0], &name);
+        let (miner, _bump) = miner_pda(users[0], name);
+
+        let mut keys: Vec<Pubkey> = fixed.iter().map(|(key, _)| *key).collect();
+        keys.extend(&users);
+        keys.extend([
+            archive_pda().0,
+            epoch_pda().0,
+            block_pda().0,
+            treasury_pda().0,
+            mint_pda().0,
+            treasury_ata().0,
+            event_authority_pda().0,
+            tape,
+            writer_pda(tape).0,
+            miner,
+            spool_pda(miner, 0).0,
+            tape_ata(users[0]).0,
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            MPL_TOKEN_METADATA_ID,
+        ]);
+
+        Self {
+            mollusk,
+            keys,
+            fixed,
+        }
+    }
+
+    fn run(&self, input: &Input) {
+        let discriminator = if input.raw_discriminator {
+            input.discriminator
+        } else {
+            DISCRIMINATORS[input.discriminator as usize % DISCRIMINATORS.len()] as u8
+        };
+        let mut data = vec![discriminator];
+        data.extend_from_slice(&input.data);
+
+        let mut metas = Vec::with_capacity(input.accounts.len());
+        let mut accounts: Vec<(Pubkey, Account)> = Vec::new();
+        for meta in &input.accounts {
+            let key = self.keys[meta.key as usize % self.keys.len()];
+            metas.push(AccountMeta {
+                pubkey: key,
+                is_signer: meta.signer,
+                is_writable: meta.writable,
+            });
+
+            if accounts.iter().any(|(k, _)| *k == key) {
+                continue;
+            }
+            let account = match self.fixed.iter().find(|(k, _)| *k == key) {
+                Some((_, account)) => account.clone(),
+                None => meta.contents.account(),
+            };
+            accounts.push((key, account));
+        }
+
+        let ix = Instruction {
+            program_id: TAPE_PROGRAM_ID,
+            accounts: metas,
+            data,
+        };
+        let result = self.mollusk.process_instruction(&ix, &accounts);
+        check(&result.program_result);
+    }
+}
+
+fn check(result: &ProgramResult) {
+    match result {
+        ProgramResult::Success => {}
+        ProgramResult::Failure(ProgramError::Custom(code)) => assert!(
+            TapeError::from_code(*code).is_some() || *code <= LAST_CPI_ERROR,
+            "unknown custom error {code:#x}"
+        ),
+        ProgramResult::Failure(err) => {
+            assert!(PROGRAM_ERRORS.contains(err), "unexpected error {err:?}")
+        }
+        ProgramResult::UnknownError(err) => {
+            assert!(RUNTIME_CHECKS.contains(err), "program failed: {err:?}")
+        }
+    }
+}
+
+thread_local! {
+    static FIXTURE: Fixture = Fixture::new();
+}
+
+fuzz_target!(|input: Input| {
+    FIXTURE.with(|fixture| fixture.run(&input));
+});
@@ -1,26 +0,0 @@
-#[macro_use]
-pub mod account;
-pub mod consts;
-pub mod error;
-// pub mod event;
-// pub mod instruction;
-pub mod loaders;
-// pub mod pda;
-pub mod rent;
-pub mod state;
-pub mod types;
-pub mod utils;
-
-pub mod prelude {
-    pub use super::account::*;
-    pub use super::consts::*;
-    pub use super::error::*;
-    // pub use super::event::*;
-    pub use super::loaders::*;
-    // pub use super::pda::*;
-    pub use super::rent::*;
-    pub use super::state::*;
-    pub use super::types::*;
-    pub use super::utils::*;
-}
-
@@ -1,37 +0,0 @@
-mod archive;
-mod epoch;
-mod block;
-mod tape;
-mod treasury;
-mod writer;
-mod miner;
-mod spool;
-
-pub use archive::*;
-pub use epoch::*;
-pub use block::*;
-pub use tape::*;
-pub use treasury::*;
-pub use writer::*;
-pub use miner::*;
-pub use spool::*;
-
-#[repr(u8)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum AccountType {
-    Unknown = 0,
-    Archive,
-    Spool,
-    Writer,
-    Tape,
-    Miner,
-    Epoch,
-    Block,
-    Treasury,
-}
-
-impl Into<u8> for AccountType {
-    fn into(self) -> u8 {
-        self as u8
-    }
-}
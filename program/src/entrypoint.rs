@@ -1,5 +1,6 @@
 #![allow(unexpected_cfgs)]
 
+use tape_api::error::error_name;
 use crate::instruction::*;
 use pinocchio::{
     account_info::AccountInfo, default_panic_handler, no_allocator, program_entrypoint,
@@ -25,6 +26,22 @@ fn process_instruction(
 
     let instruction = TapeInstruction::try_from(discriminator)?;
 
+    let result = dispatch(instruction, accounts, data);
+
+    // Log the error name so failures are readable without a code table.
+    if let Err(ProgramError::Custom(code)) = result {
+        pinocchio::log::sol_log(error_name(code));
+    }
+
+    result
+}
+
+#[inline(always)]
+fn dispatch(
+    instruction: TapeInstruction,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
     match instruction {
         // ProgramInstruction variants
         TapeInstruction::Unknown => return Err(ProgramError::InvalidInstructionData),
@@ -38,6 +55,8 @@ fn process_instruction(
         TapeInstruction::TapeFinalize => process_tape_finalize(accounts, data),
         TapeInstruction::TapeSetHeader => process_tape_set_header(accounts, data),
         TapeInstruction::TapeSubsidize => process_tape_subsidize_rent(accounts, data),
+        TapeInstruction::TapeUpdateBatch => process_tape_update_batch(accounts, data),
+        TapeInstruction::TapeWriteAt => process_tape_write_at(accounts, data),
 
         // MinerInstruction variants
         TapeInstruction::MinerRegister => process_register(accounts, data),
@@ -51,5 +70,8 @@ fn process_instruction(
         TapeInstruction::SpoolPack => process_spool_pack(accounts, data),
         TapeInstruction::SpoolUnpack => process_spool_unpack(accounts, data),
         TapeInstruction::SpoolCommit => process_spool_commit(accounts, data),
+
+        // Reserved
+        TapeInstruction::EmitEvent => process_emit_event(accounts, data),
     }
 }
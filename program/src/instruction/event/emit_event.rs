@@ -0,0 +1,20 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use tape_api::prelude::*;
+
+/// Target of the self-CPI event channel. The event payload lives in the
+/// instruction data and is read by indexers from inner instructions; the only
+/// job here is to reject anything not signed by our event authority.
+pub fn process_emit_event(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [event_authority_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    event_authority_info.is_event_authority()?;
+
+    if !event_authority_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
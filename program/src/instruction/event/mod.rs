@@ -0,0 +1,3 @@
+pub mod emit_event;
+
+pub use emit_event::*;
@@ -1,4 +1,5 @@
-use crate::state::*;
+use tape_api::prelude::*;
+use tape_api::state::utils::load_ix_data;
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
@@ -7,9 +8,7 @@ use pinocchio::{
 };
 
 pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    if !data.is_empty() {
-        return Err(ProgramError::InvalidInstructionData);
-    }
+    load_ix_data::<InitializeIxData>(data)?;
 
     let [signer_info, archive_info, epoch_info, block_info, metadata_info, mint_info, treasury_info, treasury_ata_info, tape_info, writer_info, tape_program_info, system_program_info, token_program_info, associated_token_program_info, metadata_program_info, rent_sysvar_info, slot_hashes_info] =
         accounts
@@ -20,9 +19,9 @@ pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
     check_account(&archive_info, ARCHIVE)?;
     check_account(&epoch_info, EPOCH)?;
     check_account(&block_info, BLOCK)?;
-    let (mint_address, mint_bump) = get_pda(GetPda::Mint);
-    let (treasury_address, treasury_bump) = get_pda(GetPda::Treasury);
-    let (metadata_address, _metadata_bump) = get_pda(GetPda::Metadata(mint_address));
+    let (mint_address, mint_bump) = mint_pda();
+    let (treasury_address, treasury_bump) = treasury_pda();
+    let (metadata_address, _metadata_bump) = metadata_pda(mint_address);
 
     assert_eq!(mint_bump, MINT_BUMP);
     assert_eq!(treasury_bump, TREASURY_BUMP);
@@ -56,7 +55,7 @@ fn check_account(account: &AccountInfo, seed: &[u8]) -> Result<(), ProgramError>
     if !account.is_writable() {
         return Err(ProgramError::Immutable);
     }
-    let (pda, _bump) = find_program_address(&[seed], &TAPE_ID);
+    let (pda, _bump) = find_program_address(&[seed], &crate::ID);
 
     if account.key() != &pda {
         return Err(ProgramError::InvalidAccountData);
@@ -64,25 +63,6 @@ fn check_account(account: &AccountInfo, seed: &[u8]) -> Result<(), ProgramError>
     Ok(())
 }
 
-fn get_pda(get_pda: GetPda) -> (Pubkey, u8) {
-    match get_pda {
-        GetPda::Mint => {
-            find_program_address(&[b"mint", &[152, 68, 212, 200, 25, 113, 221, 71]], &TAPE_ID)
-        }
-        GetPda::Treasury => find_program_address(&[b"treasury"], &TAPE_ID),
-        GetPda::Metadata(mint) => find_program_address(
-            &[b"metadata", MPL_TOKEN_METADATA_ID.as_ref(), mint.as_ref()],
-            &MPL_TOKEN_METADATA_ID,
-        ),
-    }
-}
-
-enum GetPda {
-    Metadata(Pubkey),
-    Mint,
-    Treasury,
-}
-
 fn check_account_with_address(account: &AccountInfo, address: &Pubkey) -> Result<(), ProgramError> {
     if !account.data_is_empty() {
         return Err(ProgramError::AccountAlreadyInitialized);
@@ -98,7 +78,7 @@ fn check_account_with_address(account: &AccountInfo, address: &Pubkey) -> Result
 }
 
 fn is_program_check(account: &AccountInfo) -> Result<(), ProgramError> {
-    if account.key().ne(&TAPE_ID) {
+    if account.key().ne(&crate::ID) {
         return Err(ProgramError::InvalidAccountData);
     }
 
@@ -1,5 +1,70 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use pinocchio_token::instructions::Transfer;
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info_mut};
 
 pub fn process_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        beneficiary_info,
+        miner_info,
+        treasury_info,
+        treasury_ata_info,
+        _token_program_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    treasury_info.is_treasury()?;
+    treasury_ata_info.is_treasury_ata()?;
+
+    let ix_data = load_ix_data::<ClaimIxData>(data)?;
+
+    let mut miner = try_from_account_info_mut::<Miner>(miner_info)?;
+    miner.assert_mut_err(
+        |m| m.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    check_condition(
+        ix_data.amount <= miner.unclaimed_rewards,
+        TapeError::ClaimTooLarge,
+    )?;
+
+    miner.unclaimed_rewards -= ix_data.amount;
+
+    let bump_binding = [TREASURY_BUMP];
+    let signer_seeds = [Seed::from(TREASURY), Seed::from(&bump_binding)];
+
+    Transfer {
+        from: treasury_ata_info,
+        to: beneficiary_info,
+        authority: treasury_info,
+        amount: ix_data.amount,
+    }
+    .invoke_signed(&[Signer::from(&signer_seeds[..])])?;
+
+    ClaimEvent {
+        amount: ix_data.amount,
+        miner: *miner_info.key(),
+        beneficiary: *beneficiary_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
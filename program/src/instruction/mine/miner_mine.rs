@@ -1,5 +1,191 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use bytemuck::Zeroable;
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{
+    try_borrow_program_data_mut, try_from_account_info, try_from_account_info_mut, DataLen,
+};
+
+/// Read a `MineIxData` whose `poa.path` may stop short of the root. Returns
+/// the data (missing hashes zeroed) and how many path hashes were sent.
+fn load_mine_ix_data(data: &[u8]) -> Result<(MineIxData, usize), ProgramError> {
+    const HEADER_LEN: usize = MineIxData::LEN - SEGMENT_PROOF_LEN * 32;
+
+    check_condition(
+        data.len() >= HEADER_LEN
+            && data.len() <= MineIxData::LEN
+            && (data.len() - HEADER_LEN) % 32 == 0,
+        TapeError::InvalidInstructionData,
+    )?;
+
+    let mut ix_data = MineIxData::zeroed();
+    bytemuck::bytes_of_mut(&mut ix_data)[..data.len()].copy_from_slice(data);
+
+    Ok((ix_data, (data.len() - HEADER_LEN) / 32))
+}
 
 pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let [
+        signer_info,
+        epoch_info,
+        block_info,
+        miner_info,
+        tape_info,
+        archive_info,
+        slot_hashes_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    archive_info.is_archive()?;
+    epoch_info.is_epoch()?;
+    block_info.is_block()?;
+
+    let (ix_data, path_len) = load_mine_ix_data(data)?;
+
+    let archive = try_from_account_info::<Archive>(archive_info)?;
+    let mut epoch = try_from_account_info_mut::<Epoch>(epoch_info)?;
+    let mut block = try_from_account_info_mut::<Block>(block_info)?;
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, nodes) = decode_tape_mut(&mut tape_data)?;
+    let mut miner = try_from_account_info_mut::<Miner>(miner_info)?;
+    miner.assert_mut_err(
+        |m| m.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    check_condition(
+        miner.last_proof_block < block.number || miner.total_proofs == 0,
+        TapeError::SolutionTooEarly,
+    )?;
+
+    let miner_challenge = compute_challenge(&block.challenge, &miner.challenge);
+    let recall_tape = compute_recall_tape(&miner_challenge, block.challenge_set);
+
+    check_condition(tape.number == recall_tape, TapeError::UnexpectedTape)?;
+
+    // Tapes without minimum rent are not worth storing, so miners prove
+    // against an empty segment instead.
+    let (segment_number, segment) = if tape.has_minimum_rent() {
+        let segment_number = compute_recall_segment(&miner_challenge, tape.total_segments);
+
+        let solution = ix_data.poa.as_solution();
+        check_condition(
+            solution.difficulty() as u64 >= epoch.packing_difficulty,
+            TapeError::SolutionTooEasy,
+        )?;
+
+        let segment = solution.unpack(miner_info.key());
+        let path = complete_proof(
+            nodes,
+            segment_number,
+            &ix_data.poa.path.as_array()[..path_len],
+        )?;
+        check_condition(
+            verify_segment(&tape.merkle_root, segment_number, &segment, &path),
+            TapeError::SolutionInvalid,
+        )?;
+
+        // A full path warms the cache so later proofs can be shorter.
+        if path_len == SEGMENT_PROOF_LEN {
+            let leaf = compute_leaf(segment_number, &segment);
+            cache_path(nodes, segment_number, &leaf.to_bytes(), &path, &tape.merkle_root);
+        }
+
+        (segment_number, segment)
+    } else {
+        (0, EMPTY_SEGMENT)
+    };
+
+    let solution = ix_data.pow.as_solution();
+    check_condition(
+        solution.is_valid(&miner_challenge, &segment).is_ok(),
+        TapeError::SolutionInvalid,
+    )?;
+    check_condition(
+        solution.difficulty() as u64 >= epoch.mining_difficulty,
+        TapeError::SolutionTooEasy,
+    )?;
+
+    // Consecutive proofs grow the multiplier, a missed block resets it.
+    miner.multiplier = if miner.last_proof_block + 1 == block.number {
+        (miner.multiplier + 1).min(MAX_CONSISTENCY_MULTIPLIER)
+    } else {
+        MIN_CONSISTENCY_MULTIPLIER
+    };
+
+    let reward = archive
+        .block_reward()
+        .saturating_mul(miner.multiplier)
+        / MAX_CONSISTENCY_MULTIPLIER
+        / epoch.target_participation.max(MIN_PARTICIPATION_TARGET);
+
+    let rent = tape.rent_owed(block.number).min(tape.balance);
+    tape.balance -= rent;
+    tape.last_rent_block = block.number;
+
+    miner.challenge = compute_next_challenge(&miner.challenge, slot_hashes_info)?;
+    miner.unclaimed_rewards = miner.unclaimed_rewards.saturating_add(reward);
+    miner.total_rewards = miner.total_rewards.saturating_add(reward);
+    miner.total_proofs += 1;
+    miner.last_proof_block = block.number;
+    miner.last_proof_at = current_time;
+
+    block.progress += 1;
+    block.last_proof_at = current_time;
+
+    if block.progress >= epoch.target_participation {
+        advance_block(&mut block, &mut epoch, &archive, slot_hashes_info, current_time)?;
+    }
+
+    MineEvent {
+        tape: tape.number,
+        segment: segment_number,
+        reward,
+        miner: *miner_info.key(),
+    }
+    .emit(&events)?;
+
+    Ok(())
+}
+
+fn advance_block(
+    block: &mut Block,
+    epoch: &mut Epoch,
+    archive: &Archive,
+    slot_hashes_info: &AccountInfo,
+    current_time: i64,
+) -> ProgramResult {
+    block.number += 1;
+    block.progress = 0;
+    block.challenge = compute_next_challenge(&block.challenge, slot_hashes_info)?;
+    block.challenge_set = archive.tapes_stored;
+    block.last_block_at = current_time;
+
+    epoch.progress += 1;
+
+    if epoch.progress >= EPOCH_BLOCKS {
+        epoch.number += 1;
+        epoch.progress = 0;
+        epoch.duplicates = 0;
+        epoch.last_epoch_at = current_time;
+    }
+
     Ok(())
 }
@@ -2,30 +2,19 @@ use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::{self, Pubkey},
     ProgramResult,
     sysvars::rent::Rent,
 };
 
 use pinocchio_system::instructions::CreateAccount;
 
-use crate::state::utils::try_from_account_info_mut;
+use tape_api::state::utils::try_from_account_info_mut;
 
-use crate::api::prelude::*;
+use tape_api::prelude::*;
 
-use crate::api::utils::compute_next_challenge;
+use tape_api::utils::compute_next_challenge;
 
-use crate::state::utils::{load_ix_data, DataLen};
-
-#[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq, shank::ShankType)]
-pub struct RegisterMinerIxData {
-    pub name: [u8; 32],   
-}
-
-impl DataLen for RegisterMinerIxData {
-    const LEN: usize = core::mem::size_of::<RegisterMinerIxData>();
-}
+use tape_api::state::utils::{load_ix_data, DataLen};
 
 pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let [
@@ -39,7 +28,7 @@ pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    if signer_info.is_signer() {
+    if !signer_info.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
@@ -49,20 +38,18 @@ pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
 
     let rent = Rent::from_account_info(rent_info)?;
 
-    let ix_data = unsafe { load_ix_data::<RegisterMinerIxData>(&data)? };
+    let ix_data = load_ix_data::<RegisterMinerIxData>(&data)?;
 
-    let seeds = &[b"miner_account", &ix_data.name[..]];
-    let (miner_pda, miner_bump) = pubkey::find_program_address(seeds, &crate::ID);
-    
-    if miner_pda.ne(miner_info.key()) {
-        return Err(ProgramError::InvalidAccountOwner);
-    }
+    let (miner_address, miner_bump) = miner_pda(*signer_info.key(), ix_data.name);
 
-    let rent = Rent::from_account_info(rent_info)?;
+    if miner_address.ne(miner_info.key()) {
+        return Err(TapeError::PdaMismatch.into());
+    }
 
     let bump_binding = [miner_bump];
     let signer_seeds = [
-        Seed::from(b"miner_account"),
+        Seed::from(MINER),
+        Seed::from(signer_info.key().as_ref()),
         Seed::from(&ix_data.name[..]),
         Seed::from(&bump_binding),
     ];
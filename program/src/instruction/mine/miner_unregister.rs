@@ -1,5 +1,35 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info};
+
 pub fn process_unregister(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        miner_info,
+        _system_program_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    load_ix_data::<UnregisterMinerIxData>(data)?;
+
+    try_from_account_info::<Miner>(miner_info)?
+        .assert_err(
+            |m| m.authority.eq(signer_info.key()),
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+    // Unregistering forfeits any unclaimed rewards, they stay in the
+    // treasury. Clients are expected to claim first.
+    *signer_info.try_borrow_mut_lamports()? += miner_info.lamports();
+    miner_info.close()?;
+
     Ok(())
 }
@@ -1,5 +1,55 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info_mut};
+
 pub fn process_spool_commit(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        miner_info,
+        spool_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let ix_data = load_ix_data::<CommitSpoolIxData>(data)?;
+
+    let mut miner = try_from_account_info_mut::<Miner>(miner_info)?;
+    miner.assert_mut_err(
+        |m| m.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let mut spool = try_from_account_info_mut::<Spool>(spool_info)?;
+    spool.assert_mut_err(
+        |s| s.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let leaf = compute_tape_leaf(ix_data.index, &ix_data.value);
+
+    check_condition(
+        spool.state.contains_leaf(&ix_data.proof, leaf),
+        TapeError::SpoolCommitFailed,
+    )?;
+
+    spool.contains = ix_data.value;
+    miner.commitment = ix_data.value;
+
+    CommitEvent {
+        tape: ix_data.index,
+        miner: *miner_info.key(),
+        spool: *spool_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
@@ -1,5 +1,80 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info, try_from_account_info_mut, DataLen};
 
 pub fn process_spool_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        miner_info,
+        spool_info,
+        _system_program_info,
+        rent_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !spool_info.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let ix_data = load_ix_data::<CreateSpoolIxData>(data)?;
+
+    try_from_account_info::<Miner>(miner_info)?
+        .assert_err(
+            |m| m.authority.eq(signer_info.key()),
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+    let (spool_address, spool_bump) = spool_pda(*miner_info.key(), ix_data.number);
+
+    if spool_address.ne(spool_info.key()) {
+        return Err(TapeError::PdaMismatch.into());
+    }
+
+    let rent = Rent::from_account_info(rent_info)?;
+
+    let number_binding = ix_data.number.to_le_bytes();
+    let bump_binding = [spool_bump];
+    let signer_seeds = [
+        Seed::from(SPOOL),
+        Seed::from(miner_info.key().as_ref()),
+        Seed::from(&number_binding),
+        Seed::from(&bump_binding),
+    ];
+
+    CreateAccount {
+        from: signer_info,
+        to: spool_info,
+        space: Spool::LEN as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance(Spool::LEN),
+    }
+    .invoke_signed(&[Signer::from(&signer_seeds[..])])?;
+
+    let mut spool = try_from_account_info_mut::<Spool>(spool_info)?;
+    spool.number = ix_data.number;
+    spool.authority = *signer_info.key();
+    spool.state = TapeTree::new(&[spool_address.as_ref()]);
+    spool.seed = spool_address;
+    spool.contains = [0; 32];
+    spool.total_tapes = 0;
+    spool.last_proof_block = 0;
+    spool.last_proof_at = 0;
+
     Ok(())
 }
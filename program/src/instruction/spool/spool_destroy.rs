@@ -1,5 +1,33 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info};
+
 pub fn process_spool_destroy(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        spool_info,
+        _system_program_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    load_ix_data::<DestroySpoolIxData>(data)?;
+
+    try_from_account_info::<Spool>(spool_info)?
+        .assert_err(
+            |s| s.authority.eq(signer_info.key()),
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+    *signer_info.try_borrow_mut_lamports()? += spool_info.lamports();
+    spool_info.close()?;
+
     Ok(())
 }
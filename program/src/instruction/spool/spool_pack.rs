@@ -1,5 +1,61 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_borrow_program_data, try_from_account_info_mut};
+
 pub fn process_spool_pack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        spool_info,
+        tape_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let ix_data = load_ix_data::<PackSpoolIxData>(data)?;
+
+    let mut spool = try_from_account_info_mut::<Spool>(spool_info)?;
+    spool.assert_mut_err(
+        |s| s.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let tape_data = try_borrow_program_data(tape_info)?;
+    let (tape, _nodes) = decode_tape(&tape_data)?;
+
+    check_condition(
+        tape.state == TapeState::Finalized as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    check_condition(
+        spool.total_tapes < MAX_TAPES_PER_SPOOL as u64,
+        TapeError::SpoolTooManyTapes,
+    )?;
+
+    let leaf = compute_tape_leaf(tape.number, &ix_data.value);
+
+    check_condition(
+        spool.state.try_add_leaf(leaf).is_ok(),
+        TapeError::SpoolPackFailed,
+    )?;
+
+    spool.total_tapes += 1;
+
+    PackEvent {
+        tape: tape.number,
+        total_tapes: spool.total_tapes,
+        spool: *spool_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
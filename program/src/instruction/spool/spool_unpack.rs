@@ -1,5 +1,47 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_from_account_info_mut};
+
 pub fn process_spool_unpack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        spool_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let ix_data = load_ix_data::<UnpackSpoolIxData>(data)?;
+
+    let mut spool = try_from_account_info_mut::<Spool>(spool_info)?;
+    spool.assert_mut_err(
+        |s| s.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let leaf = compute_tape_leaf(ix_data.index, &ix_data.value);
+
+    check_condition(
+        spool.state.try_remove_leaf(&ix_data.proof, leaf).is_ok(),
+        TapeError::SpoolUnpackFailed,
+    )?;
+
+    spool.total_tapes = spool.total_tapes.saturating_sub(1);
+
+    UnpackEvent {
+        tape: ix_data.index,
+        total_tapes: spool.total_tapes,
+        spool: *spool_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
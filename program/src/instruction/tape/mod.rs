@@ -3,11 +3,15 @@ pub mod tape_finalize;
 pub mod tape_set_header;
 pub mod tape_subsidize;
 pub mod tape_update;
+pub mod tape_update_batch;
 pub mod tape_write;
+pub mod tape_write_at;
 
 pub use tape_create::*;
 pub use tape_finalize::*;
 pub use tape_set_header::*;
 pub use tape_subsidize::*;
 pub use tape_update::*;
+pub use tape_update_batch::*;
 pub use tape_write::*;
+pub use tape_write_at::*;
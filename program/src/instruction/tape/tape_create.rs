@@ -1,5 +1,110 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+
+use pinocchio_system::instructions::CreateAccount;
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_borrow_program_data_mut};
 
 pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let current_slot = Clock::get()?.slot;
+
+    let [
+        signer_info,
+        tape_info,
+        writer_info,
+        _system_program_info,
+        rent_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !tape_info.data_is_empty() || !writer_info.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let ix_data = load_ix_data::<CreateTapeIxData>(data)?;
+
+    check_condition(
+        ix_data.subtree_depth <= MAX_SUBTREE_DEPTH,
+        TapeError::InvalidSubtreeDepth,
+    )?;
+
+    let (tape_address, tape_bump) = tape_pda(*signer_info.key(), &ix_data.name);
+    let (writer_address, writer_bump) = writer_pda(tape_address);
+
+    if tape_address.ne(tape_info.key()) || writer_address.ne(writer_info.key()) {
+        return Err(TapeError::PdaMismatch.into());
+    }
+
+    let rent = Rent::from_account_info(rent_info)?;
+    let tape_size = tape_account_size(ix_data.subtree_depth);
+
+    let tape_bump_binding = [tape_bump];
+    let tape_seeds = [
+        Seed::from(TAPE),
+        Seed::from(signer_info.key().as_ref()),
+        Seed::from(&ix_data.name[..]),
+        Seed::from(&tape_bump_binding),
+    ];
+
+    CreateAccount {
+        from: signer_info,
+        to: tape_info,
+        space: tape_size as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance(tape_size),
+    }
+    .invoke_signed(&[Signer::from(&tape_seeds[..])])?;
+
+    let writer_bump_binding = [writer_bump];
+    let writer_seeds = [
+        Seed::from(WRITER),
+        Seed::from(tape_address.as_ref()),
+        Seed::from(&writer_bump_binding),
+    ];
+
+    CreateAccount {
+        from: signer_info,
+        to: writer_info,
+        space: writer_account_size() as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance(writer_account_size()),
+    }
+    .invoke_signed(&[Signer::from(&writer_seeds[..])])?;
+
+    // (fresh accounts are zeroed, which leaves every out-of-order slot empty)
+    let mut writer_data = try_borrow_program_data_mut(writer_info)?;
+    let (writer, _slots) = decode_writer_mut(&mut writer_data)?;
+    writer.tape = tape_address;
+    writer.state = SegmentTree::new(&[tape_address.as_ref()]);
+
+    // (fresh accounts are zeroed, which is an empty subtree cache)
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, _nodes) = decode_tape_mut(&mut tape_data)?;
+    tape.number = 0; // (tapes get a number when finalized)
+    tape.state = TapeState::Created as u64;
+    tape.authority = *signer_info.key();
+    tape.name = ix_data.name;
+    tape.merkle_seed = tape_address;
+    tape.merkle_root = writer.state.get_root().to_bytes();
+    tape.header = [0; HEADER_SIZE];
+    tape.first_slot = current_slot;
+    tape.tail_slot = current_slot;
+    tape.balance = 0;
+    tape.last_rent_block = 0;
+    tape.total_segments = 0;
+
     Ok(())
 }
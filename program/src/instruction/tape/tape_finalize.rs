@@ -1,5 +1,82 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_borrow_program_data_mut, try_from_account_info_mut};
+
 pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        tape_info,
+        writer_info,
+        archive_info,
+        _system_program_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    load_ix_data::<FinalizeTapeIxData>(data)?;
+
+    archive_info.is_archive()?;
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, nodes) = decode_tape_mut(&mut tape_data)?;
+    tape.assert_mut_err(
+        |t| t.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let mut writer_data = try_borrow_program_data_mut(writer_info)?;
+    let (writer, slots) = decode_writer_mut(&mut writer_data)?;
+    writer.assert_mut_err(
+        |w| w.tape.eq(tape_info.key()),
+        TapeError::PdaMismatch.into(),
+    )?;
+
+    let mut archive = try_from_account_info_mut::<Archive>(archive_info)?;
+
+    check_condition(
+        tape.state == TapeState::Writing as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    // Out-of-order writes that are now contiguous become part of the tape,
+    // however many `drain_slots` leaves for the next call; anything still
+    // pending sits behind a missing segment.
+    while drain_slots(&mut writer.state, slots, &mut tape.total_segments, nodes)? > 0 {}
+    check_condition(
+        slots.iter().all(|slot| *slot == [0; 32]),
+        TapeError::TapeHasHoles,
+    )?;
+
+    tape.merkle_root = writer.state.get_root().to_bytes();
+
+    check_condition(tape.can_finalize(), TapeError::InsufficientRent)?;
+
+    archive.tapes_stored += 1;
+    archive.segments_stored += tape.total_segments;
+
+    tape.number = archive.tapes_stored;
+    tape.state = TapeState::Finalized as u64;
+
+    // The writer is no longer needed once the tape is finalized; refund its
+    // rent to the signer.
+    drop(writer_data);
+    *signer_info.try_borrow_mut_lamports()? += writer_info.lamports();
+    writer_info.close()?;
+
+    FinalizeEvent {
+        tape: tape.number,
+        address: *tape_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
@@ -1,5 +1,37 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_borrow_program_data_mut};
+
 pub fn process_tape_set_header(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        tape_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let ix_data = load_ix_data::<SetTapeHeaderIxData>(data)?;
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, _nodes) = decode_tape_mut(&mut tape_data)?;
+    tape.assert_mut_err(
+        |t| t.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    check_condition(
+        tape.state != TapeState::Finalized as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    tape.header = ix_data.header;
+
     Ok(())
 }
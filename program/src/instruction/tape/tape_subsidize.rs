@@ -1,5 +1,43 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
 
+use pinocchio_token::instructions::Transfer;
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data, try_borrow_program_data_mut};
+
 pub fn process_tape_subsidize_rent(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [
+        signer_info,
+        ata_info,
+        tape_info,
+        treasury_ata_info,
+        _token_program_info,
+        _remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    treasury_ata_info.is_treasury_ata()?;
+
+    let ix_data = load_ix_data::<SubsidizeTapeIxData>(data)?;
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, _nodes) = decode_tape_mut(&mut tape_data)?;
+
+    Transfer {
+        from: ata_info,
+        to: treasury_ata_info,
+        authority: signer_info,
+        amount: ix_data.amount,
+    }
+    .invoke()?;
+
+    tape.balance = tape.balance.saturating_add(ix_data.amount);
+
     Ok(())
 }
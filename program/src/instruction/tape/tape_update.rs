@@ -1,5 +1,91 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data_with_tail, try_borrow_program_data_mut};
 
 pub fn process_tape_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let current_slot = Clock::get()?.slot;
+
+    let [
+        signer_info,
+        tape_info,
+        writer_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let (ix_data, proof) = load_ix_data_with_tail::<UpdateTapeIxData>(data)?;
+    let proof: &[[u8; 32]] = bytemuck::try_cast_slice(proof)
+        .map_err(|_| TapeError::InvalidInstructionData)?;
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, nodes) = decode_tape_mut(&mut tape_data)?;
+    tape.assert_mut_err(
+        |t| t.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let mut writer_data = try_borrow_program_data_mut(writer_info)?;
+    let (writer, _slots) = decode_writer_mut(&mut writer_data)?;
+    writer.assert_mut_err(
+        |w| w.tape.eq(tape_info.key()),
+        TapeError::PdaMismatch.into(),
+    )?;
+
+    check_condition(
+        tape.state == TapeState::Writing as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    check_condition(
+        ix_data.segment_number < tape.total_segments,
+        TapeError::WriteFailed,
+    )?;
+
+    let proof = complete_proof(nodes, ix_data.segment_number, proof)?;
+
+    update_segment(
+        &mut writer.state,
+        ix_data.segment_number,
+        &ix_data.old_data,
+        &ix_data.new_data,
+        &proof,
+    )?;
+
+    let prev_slot = tape.tail_slot;
+
+    tape.merkle_root = writer.state.get_root().to_bytes();
+
+    // Keep the cached path in step with the new leaf.
+    let new_leaf = compute_leaf(ix_data.segment_number, &ix_data.new_data);
+    cache_path(
+        nodes,
+        ix_data.segment_number,
+        &new_leaf.to_bytes(),
+        &proof,
+        &tape.merkle_root,
+    );
+    tape.tail_slot = current_slot;
+
+    UpdateEvent {
+        segment_number: ix_data.segment_number,
+        prev_slot,
+        address: *tape_info.key(),
+    }
+    .emit(&events)?;
+
     Ok(())
 }
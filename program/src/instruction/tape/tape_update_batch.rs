@@ -0,0 +1,101 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data_with_tail, try_borrow_program_data_mut, DataLen};
+
+pub fn process_tape_update_batch(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let current_slot = Clock::get()?.slot;
+
+    let [
+        signer_info,
+        tape_info,
+        writer_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let (ix_data, data) = load_ix_data_with_tail::<UpdateTapeBatchIxData>(data)?;
+    let count = ix_data.count as usize;
+
+    check_condition(
+        count > 0 && count <= MAX_BATCH_UPDATES && data.len() >= count * SegmentUpdate::LEN,
+        TapeError::InvalidInstructionData,
+    )?;
+
+    let (updates, proof) = data.split_at(count * SegmentUpdate::LEN);
+    let proof: &[[u8; 32]] = bytemuck::try_cast_slice(proof)
+        .map_err(|_| TapeError::InvalidInstructionData)?;
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, cache) = decode_tape_mut(&mut tape_data)?;
+    tape.assert_mut_err(
+        |t| t.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let mut writer_data = try_borrow_program_data_mut(writer_info)?;
+    let (writer, _slots) = decode_writer_mut(&mut writer_data)?;
+    writer.assert_mut_err(
+        |w| w.tape.eq(tape_info.key()),
+        TapeError::PdaMismatch.into(),
+    )?;
+
+    check_condition(
+        tape.state == TapeState::Writing as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    // (instruction data is unaligned, so updates are copied out)
+    let mut nodes = [BatchNode::default(); MAX_BATCH_UPDATES];
+    for (node, update) in nodes.iter_mut().zip(updates.chunks_exact(SegmentUpdate::LEN)) {
+        let update: SegmentUpdate = bytemuck::pod_read_unaligned(update);
+
+        check_condition(
+            update.segment_number < tape.total_segments,
+            TapeError::WriteFailed,
+        )?;
+
+        *node = BatchNode {
+            index: update.segment_number,
+            old: compute_leaf(update.segment_number, &update.old_data).to_bytes(),
+            new: compute_leaf(update.segment_number, &update.new_data).to_bytes(),
+        };
+    }
+
+    update_segments(&mut writer.state, &mut nodes[..count], proof, |level, node| {
+        // Changed nodes near the root replace their cached copies.
+        let level = (SEGMENT_TREE_HEIGHT - level) as u64;
+        cache_node(cache, level, node.index, &node.new);
+    })?;
+
+    let prev_slot = tape.tail_slot;
+
+    tape.merkle_root = writer.state.get_root().to_bytes();
+    tape.tail_slot = current_slot;
+
+    for update in updates.chunks_exact(SegmentUpdate::LEN) {
+        let update: SegmentUpdate = bytemuck::pod_read_unaligned(update);
+
+        UpdateEvent {
+            segment_number: update.segment_number,
+            prev_slot,
+            address: *tape_info.key(),
+        }
+        .emit(&events)?;
+    }
+
+    Ok(())
+}
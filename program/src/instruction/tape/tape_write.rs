@@ -1,5 +1,100 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use tape_api::prelude::*;
+
+use tape_api::state::utils::{load_ix_data_with_tail, try_borrow_program_data_mut};
 
 pub fn process_tape_write(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let current_slot = Clock::get()?.slot;
+
+    let [
+        signer_info,
+        tape_info,
+        writer_info,
+        remaining @ ..,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let events = EventChannel::from_accounts(remaining)?;
+
+    let (_ix_data, data) = load_ix_data_with_tail::<WriteTapeIxData>(data)?;
+
+    if data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let mut tape_data = try_borrow_program_data_mut(tape_info)?;
+    let (tape, nodes) = decode_tape_mut(&mut tape_data)?;
+    tape.assert_mut_err(
+        |t| t.authority.eq(signer_info.key()),
+        ProgramError::MissingRequiredSignature,
+    )?;
+
+    let mut writer_data = try_borrow_program_data_mut(writer_info)?;
+    let (writer, slots) = decode_writer_mut(&mut writer_data)?;
+    writer.assert_mut_err(
+        |w| w.tape.eq(tape_info.key()),
+        TapeError::PdaMismatch.into(),
+    )?;
+
+    check_condition(
+        tape.state == TapeState::Created as u64 || tape.state == TapeState::Writing as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    let segments = data.chunks(SEGMENT_SIZE);
+    let segment_count = segments.len() as u64;
+
+    check_condition(
+        tape.total_segments + segment_count <= MAX_SEGMENTS_PER_TAPE as u64,
+        TapeError::TapeTooLong,
+    )?;
+
+    let first_segment = tape.total_segments;
+
+    for (segment_number, segment) in segments.enumerate() {
+        let canonical_segment = padded_array::<SEGMENT_SIZE>(segment);
+        let segment_id = first_segment + segment_number as u64;
+
+        // (a pending out-of-order write already claimed this segment)
+        check_condition(
+            slots[slot_index(segment_id)] == [0; 32],
+            TapeError::SegmentAlreadyWritten,
+        )?;
+
+        write_segment(&mut writer.state, segment_id, &canonical_segment)?;
+
+        // Appending changes every ancestor of the new leaf.
+        clear_path(nodes, segment_id);
+    }
+
+    let prev_slot = tape.tail_slot;
+
+    tape.total_segments += segment_count;
+    drain_slots(&mut writer.state, slots, &mut tape.total_segments, nodes)?;
+
+    tape.merkle_root = writer.state.get_root().to_bytes();
+    tape.state = TapeState::Writing as u64;
+    tape.tail_slot = current_slot;
+
+    WriteEvent {
+        num_added: segment_count,
+        num_total: tape.total_segments,
+        prev_slot,
+        address: *tape_info.key(),
+        segment_number: first_segment,
+    }
+    .emit(&events)?;
+
     Ok(())
 }
@@ -6,9 +6,6 @@ mod entrypoint;
 #[cfg(feature = "std")]
 extern crate std;
 
-pub mod error;
 pub mod instruction;
-pub mod state;
-pub mod api;
 
-pinocchio_pubkey::declare_id!("ENrRns55VechXJiq4bMbdx7idzQh7tvaEJoYeWxRNe7Y");
+pub use tape_api::{check_id, id, ID};
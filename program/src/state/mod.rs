@@ -1,7 +0,0 @@
-pub mod constant;
-pub mod my_state;
-pub mod utils;
-
-pub use constant::*;
-pub use my_state::*;
-pub use utils::*;
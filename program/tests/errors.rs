@@ -0,0 +1,351 @@
+//! One failing instruction per `TapeError` the program can return.
+
+mod support;
+
+use bytemuck::Zeroable;
+use support::*;
+use tape_sdk::accounts::*;
+use tape_sdk::consts::*;
+use tape_sdk::errors::TapeError;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::types::*;
+use tape_sdk::Pubkey;
+
+/// Every variant, each with a test below.
+const COVERED: [TapeError; 22] = [
+    TapeError::InvalidInstructionData,
+    TapeError::PdaMismatch,
+    TapeError::InvalidOwner,
+    TapeError::UnexpectedState,
+    TapeError::WriteFailed,
+    TapeError::TapeTooLong,
+    TapeError::InsufficientRent,
+    TapeError::InvalidSubtreeDepth,
+    TapeError::SubtreeNotCached,
+    TapeError::InvalidProofLength,
+    TapeError::TapeHasHoles,
+    TapeError::SegmentAlreadyWritten,
+    TapeError::SegmentOutOfWindow,
+    TapeError::SolutionInvalid,
+    TapeError::UnexpectedTape,
+    TapeError::SolutionTooEasy,
+    TapeError::SolutionTooEarly,
+    TapeError::ClaimTooLarge,
+    TapeError::SpoolPackFailed,
+    TapeError::SpoolUnpackFailed,
+    TapeError::SpoolTooManyTapes,
+    TapeError::SpoolCommitFailed,
+];
+
+#[test]
+fn every_variant_is_covered() {
+    for err in TapeError::ALL {
+        assert!(COVERED.contains(&err), "{err:?} has no test");
+    }
+    assert_eq!(COVERED.len(), TapeError::ALL.len());
+}
+
+fn setup() -> (Harness, Pubkey) {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+    (h, signer)
+}
+
+/// Register a miner called "rig" for `signer`.
+fn register(h: &mut Harness, signer: Pubkey) -> Pubkey {
+    let name = to_name("rig");
+    h.run_ok(&build_register_ix(signer, name));
+    miner_pda(signer, name).0
+}
+
+/// Register a miner and give it an empty spool.
+fn empty_spool(h: &mut Harness, signer: Pubkey) -> (Pubkey, Pubkey) {
+    let miner = register(h, signer);
+    h.run_ok(&build_create_spool_ix(signer, miner, 0));
+    (miner, spool_pda(miner, 0).0)
+}
+
+#[test]
+fn invalid_instruction_data() {
+    let (mut h, signer) = setup();
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "tape", &data);
+    let miner = register(&mut h, signer);
+
+    // (a path hash cut in half)
+    let mut ix = mine_ix(&h, signer, miner, tape, &data);
+    ix.data.truncate(ix.data.len() - 16);
+    h.run_err(&ix, TapeError::InvalidInstructionData);
+}
+
+#[test]
+fn pda_mismatch() {
+    let (mut h, signer) = setup();
+
+    let mut create = build_create_tape_ix(signer, to_name("tape"), 0);
+    create.accounts[1].pubkey = Pubkey::new_unique();
+    h.run_err(&create, TapeError::PdaMismatch);
+
+    // (another tape's writer)
+    let tape = written_tape(&mut h, signer, "tape", &segments(1));
+    let other = written_tape(&mut h, signer, "other", &segments(1));
+    let mut write = build_write_ix(signer, tape, &[1; SEGMENT_SIZE]);
+    write.accounts[2].pubkey = writer_pda(other).0;
+    h.run_err(&write, TapeError::PdaMismatch);
+}
+
+#[test]
+fn invalid_owner() {
+    let (mut h, signer) = setup();
+    let tape = written_tape(&mut h, signer, "tape", &segments(1));
+
+    let mut account = h.get(&tape);
+    account.owner = SYSTEM_PROGRAM_ID;
+    h.set(tape, account);
+
+    let ix = build_write_ix(signer, tape, &[1; SEGMENT_SIZE]);
+    h.run_err(&ix, TapeError::InvalidOwner);
+}
+
+#[test]
+fn unexpected_state() {
+    let (mut h, signer) = setup();
+    let name = to_name("tape");
+    let (tape, _bump) = tape_pda(signer, &name);
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+
+    // (nothing written yet, so there is nothing to update)
+    let proof = [[0; 32]; SEGMENT_PROOF_LEN];
+    let ix = build_update_ix(signer, tape, 0, [0; SEGMENT_SIZE], [1; SEGMENT_SIZE], &proof);
+    h.run_err(&ix, TapeError::UnexpectedState);
+}
+
+#[test]
+fn write_failed() {
+    let (mut h, signer) = setup();
+    let data = segments(2);
+    let tape = written_tape(&mut h, signer, "tape", &data);
+
+    let proof = [[0; 32]; SEGMENT_PROOF_LEN];
+    let past_end = build_update_ix(signer, tape, 2, [0; SEGMENT_SIZE], [1; SEGMENT_SIZE], &proof);
+    h.run_err(&past_end, TapeError::WriteFailed);
+
+    let bad_proof = build_update_ix(signer, tape, 1, data[1], [1; SEGMENT_SIZE], &proof);
+    h.run_err(&bad_proof, TapeError::WriteFailed);
+}
+
+#[test]
+fn tape_too_long() {
+    let (mut h, signer) = setup();
+    let tape = written_tape(&mut h, signer, "tape", &segments(1));
+
+    let ix = build_write_at_ix(signer, tape, MAX_SEGMENTS_PER_TAPE as u64, &[1; SEGMENT_SIZE]);
+    h.run_err(&ix, TapeError::TapeTooLong);
+}
+
+#[test]
+fn insufficient_rent() {
+    let (mut h, signer) = setup();
+    let tape = written_tape(&mut h, signer, "tape", &segments(2));
+
+    h.run_ok(&build_subsidize_ix(signer, tape_ata(signer).0, tape, 1));
+    h.run_err(&build_finalize_ix(signer, tape), TapeError::InsufficientRent);
+}
+
+#[test]
+fn invalid_subtree_depth() {
+    let (mut h, signer) = setup();
+    let ix = build_create_tape_ix(signer, to_name("tape"), MAX_SUBTREE_DEPTH + 1);
+    h.run_err(&ix, TapeError::InvalidSubtreeDepth);
+}
+
+#[test]
+fn subtree_not_cached() {
+    let (mut h, signer) = setup();
+    let name = to_name("tape");
+    let (tape, _bump) = tape_pda(signer, &name);
+    let data = segments(4);
+    h.run_ok(&build_create_tape_ix(signer, name, 2));
+    h.run_ok(&build_write_ix(signer, tape, bytemuck::cast_slice(&data)));
+
+    // (writes clear the cache, so the top two levels must still be sent)
+    let proof = [[0; 32]; SEGMENT_PROOF_LEN - 2];
+    let ix = build_update_ix(signer, tape, 0, data[0], [1; SEGMENT_SIZE], &proof);
+    h.run_err(&ix, TapeError::SubtreeNotCached);
+}
+
+#[test]
+fn invalid_proof_length() {
+    let (mut h, signer) = setup();
+    let data = segments(2);
+    let tape = written_tape(&mut h, signer, "tape", &data);
+
+    // (depth 0 caches nothing, so the proof must be full length)
+    let proof = [[0; 32]; SEGMENT_PROOF_LEN - 1];
+    let ix = build_update_ix(signer, tape, 0, data[0], [1; SEGMENT_SIZE], &proof);
+    h.run_err(&ix, TapeError::InvalidProofLength);
+}
+
+#[test]
+fn tape_has_holes() {
+    let (mut h, signer) = setup();
+    let name = to_name("tape");
+    let (tape, _bump) = tape_pda(signer, &name);
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+    h.run_ok(&build_write_at_ix(signer, tape, 1, &[1; SEGMENT_SIZE]));
+
+    h.run_err(&build_finalize_ix(signer, tape), TapeError::TapeHasHoles);
+}
+
+#[test]
+fn segment_already_written() {
+    let (mut h, signer) = setup();
+    let name = to_name("tape");
+    let (tape, _bump) = tape_pda(signer, &name);
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+    h.run_ok(&build_write_at_ix(signer, tape, 2, &[1; SEGMENT_SIZE]));
+
+    let again = build_write_at_ix(signer, tape, 2, &[2; SEGMENT_SIZE]);
+    h.run_err(&again, TapeError::SegmentAlreadyWritten);
+}
+
+#[test]
+fn segment_out_of_window() {
+    let (mut h, signer) = setup();
+    let tape = written_tape(&mut h, signer, "tape", &segments(3));
+
+    let ahead = build_write_at_ix(signer, tape, 3 + WRITE_WINDOW as u64, &[1; SEGMENT_SIZE]);
+    h.run_err(&ahead, TapeError::SegmentOutOfWindow);
+
+    let behind = build_write_at_ix(signer, tape, 1, &[1; SEGMENT_SIZE]);
+    h.run_err(&behind, TapeError::SegmentOutOfWindow);
+}
+
+#[test]
+fn solution_invalid() {
+    let (mut h, signer) = setup();
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "tape", &data);
+    let miner = register(&mut h, signer);
+
+    let ix = build_mine_ix(signer, miner, tape, PoW::zeroed(), PoA::zeroed());
+    h.run_err(&ix, TapeError::SolutionInvalid);
+}
+
+#[test]
+fn unexpected_tape() {
+    let (mut h, signer) = setup();
+    finalized_tape(&mut h, signer, "first", &segments(2));
+    let data = segments(2);
+    let second = finalized_tape(&mut h, signer, "second", &data);
+    let miner = register(&mut h, signer);
+
+    // (with only tape 1 in the challenge set, every miner recalls it)
+    let mut block: Block = h.state(&block_pda().0);
+    block.challenge_set = 1;
+    h.set_state(block_pda().0, &block);
+
+    let ix = build_mine_ix(signer, miner, second, PoW::zeroed(), PoA::zeroed());
+    h.run_err(&ix, TapeError::UnexpectedTape);
+}
+
+#[test]
+fn solution_too_easy() {
+    let (mut h, signer) = setup();
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "tape", &data);
+    let miner = register(&mut h, signer);
+    let ix = mine_ix(&h, signer, miner, tape, &data);
+
+    // (raised after solving; nothing would meet it in a test)
+    let mut epoch: Epoch = h.state(&epoch_pda().0);
+    epoch.mining_difficulty = 64;
+    h.set_state(epoch_pda().0, &epoch);
+
+    h.run_err(&ix, TapeError::SolutionTooEasy);
+}
+
+#[test]
+fn solution_too_early() {
+    let mut h = Harness::new();
+    genesis(
+        &mut h,
+        Genesis {
+            target_participation: 2,
+            ..Genesis::default()
+        },
+    );
+    let signer = h.user(1_000_000_000);
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "tape", &data);
+    let miner = register(&mut h, signer);
+
+    h.run_ok(&mine_ix(&h, signer, miner, tape, &data));
+    assert_eq!(h.state::<Block>(&block_pda().0).number, 1);
+
+    let again = mine_ix(&h, signer, miner, tape, &data);
+    h.run_err(&again, TapeError::SolutionTooEarly);
+}
+
+#[test]
+fn claim_too_large() {
+    let (mut h, signer) = setup();
+    let miner = register(&mut h, signer);
+
+    let ix = build_claim_ix(signer, tape_ata(signer).0, miner, 1);
+    h.run_err(&ix, TapeError::ClaimTooLarge);
+}
+
+#[test]
+fn spool_pack_failed() {
+    let (mut h, signer) = setup();
+    let tape = finalized_tape(&mut h, signer, "tape", &segments(2));
+    let (_miner, spool) = empty_spool(&mut h, signer);
+
+    // (a full tree; the tape cap normally stops packing well before this)
+    let mut state: Spool = h.state(&spool);
+    state.state.next_index = 1 << TAPE_TREE_HEIGHT;
+    h.set_state(spool, &state);
+
+    h.run_err(&build_pack_ix(signer, spool, tape, [1; 32]), TapeError::SpoolPackFailed);
+}
+
+#[test]
+fn spool_unpack_failed() {
+    let (mut h, signer) = setup();
+    let tape = finalized_tape(&mut h, signer, "tape", &segments(2));
+    let (_miner, spool) = empty_spool(&mut h, signer);
+    h.run_ok(&build_pack_ix(signer, spool, tape, [1; 32]));
+
+    // (a value the spool never packed)
+    let proof = [[0; 32]; TAPE_PROOF_LEN];
+    let ix = build_unpack_ix(signer, spool, 1, proof, [2; 32]);
+    h.run_err(&ix, TapeError::SpoolUnpackFailed);
+}
+
+#[test]
+fn spool_too_many_tapes() {
+    let (mut h, signer) = setup();
+    let tape = finalized_tape(&mut h, signer, "tape", &segments(2));
+    let (_miner, spool) = empty_spool(&mut h, signer);
+
+    let mut state: Spool = h.state(&spool);
+    state.total_tapes = MAX_TAPES_PER_SPOOL as u64;
+    h.set_state(spool, &state);
+
+    h.run_err(&build_pack_ix(signer, spool, tape, [1; 32]), TapeError::SpoolTooManyTapes);
+}
+
+#[test]
+fn spool_commit_failed() {
+    let (mut h, signer) = setup();
+    let tape = finalized_tape(&mut h, signer, "tape", &segments(2));
+    let (miner, spool) = empty_spool(&mut h, signer);
+    h.run_ok(&build_pack_ix(signer, spool, tape, [1; 32]));
+
+    let proof = [[0; 32]; TAPE_PROOF_LEN];
+    let ix = build_commit_ix(signer, miner, spool, 1, proof, [2; 32]);
+    h.run_err(&ix, TapeError::SpoolCommitFailed);
+}
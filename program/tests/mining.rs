@@ -0,0 +1,151 @@
+//! Miners from registration to unregistration.
+
+mod support;
+
+use mollusk_svm::result::ProgramResult;
+use solana_sdk::program_error::ProgramError;
+use support::*;
+use tape_api::rent::rent_per_block;
+use tape_sdk::accounts::*;
+use tape_sdk::consts::*;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::types::*;
+
+#[test]
+fn miner_lifecycle() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let data = segments(6);
+    let tape = finalized_tape(&mut h, signer, "mined", &data);
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+
+    let registered: Miner = h.state(&miner);
+    assert_eq!(registered.authority, signer.to_bytes());
+    assert_eq!(registered.total_proofs, 0);
+    assert_ne!(registered.challenge, [0; 32]);
+
+    let balance_before = h.tape(&tape).balance;
+    h.run_ok(&mine_ix(&h, signer, miner, tape, &data));
+
+    let mined: Miner = h.state(&miner);
+    assert_eq!(mined.total_proofs, 1);
+    assert_eq!(mined.last_proof_block, 1);
+    assert!(mined.unclaimed_rewards > 0);
+    assert_eq!(mined.total_rewards, mined.unclaimed_rewards);
+    assert_ne!(mined.challenge, registered.challenge);
+
+    // (target participation of 1 moves the block on after every proof)
+    let block: Block = h.state(&block_pda().0);
+    assert_eq!(block.number, 2);
+    assert_eq!(block.progress, 0);
+    assert_eq!(h.tape(&tape).balance, balance_before - rent_per_block(6));
+
+    let reward = mined.unclaimed_rewards;
+    let beneficiary = tape_ata(signer).0;
+    let tokens_before = h.token_balance(&beneficiary);
+    let treasury_before = h.token_balance(&treasury_ata().0);
+    h.run_ok(&build_claim_ix(signer, beneficiary, miner, reward));
+
+    assert_eq!(h.state::<Miner>(&miner).unclaimed_rewards, 0);
+    assert_eq!(h.token_balance(&beneficiary), tokens_before + reward);
+    assert_eq!(h.token_balance(&treasury_ata().0), treasury_before - reward);
+
+    let lamports_before = h.get(&signer).lamports;
+    let miner_lamports = h.get(&miner).lamports;
+    h.run_ok(&build_unregister_ix(signer, miner));
+
+    assert!(!h.exists(&miner));
+    assert_eq!(h.get(&signer).lamports, lamports_before + miner_lamports);
+}
+
+#[test]
+fn registering_needs_the_authority_to_sign() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(0);
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    let mut ix = build_register_ix(signer, name);
+    ix.accounts[0].is_signer = false;
+
+    let result = h.run(&ix);
+    assert_eq!(
+        result.program_result,
+        ProgramResult::Failure(ProgramError::MissingRequiredSignature)
+    );
+    assert!(!h.exists(&miner));
+}
+
+#[test]
+fn unregistering_forfeits_unclaimed_rewards() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(0);
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+
+    let mut state: Miner = h.state(&miner);
+    state.unclaimed_rewards = 500;
+    h.set_state(miner, &state);
+
+    let treasury_before = h.token_balance(&treasury_ata().0);
+    h.run_ok(&build_unregister_ix(signer, miner));
+
+    assert!(!h.exists(&miner));
+    assert_eq!(h.token_balance(&treasury_ata().0), treasury_before);
+}
+
+#[test]
+fn consecutive_blocks_grow_the_multiplier() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let data = segments(3);
+    let tape = finalized_tape(&mut h, signer, "steady", &data);
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+
+    let mut multipliers = Vec::new();
+    for block in 1..=3 {
+        h.warp(block + 1, 1_700_000_000 + 60 * block as i64);
+        h.run_ok(&mine_ix(&h, signer, miner, tape, &data));
+        multipliers.push(h.state::<Miner>(&miner).multiplier);
+    }
+
+    assert!(multipliers.windows(2).all(|pair| pair[1] == pair[0] + 1));
+    assert_eq!(h.state::<Miner>(&miner).total_proofs, 3);
+}
+
+#[test]
+fn unpaid_tapes_are_proven_against_the_empty_segment() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "lapsed", &data);
+
+    // (rent has run out since finalization)
+    let mut state = h.tape(&tape);
+    state.balance = 0;
+    h.set_tape(tape, &state);
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+    h.run_ok(&mine_ix(&h, signer, miner, tape, &data));
+
+    assert_eq!(h.state::<Miner>(&miner).total_proofs, 1);
+}
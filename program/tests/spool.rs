@@ -0,0 +1,92 @@
+//! Spools from creation to destruction.
+
+mod support;
+
+use support::*;
+use tape_sdk::accounts::*;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::prover::ProofBuilder;
+use tape_sdk::types::*;
+
+#[test]
+fn spool_lifecycle() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let first = finalized_tape(&mut h, signer, "first", &segments(2));
+    let second = finalized_tape(&mut h, signer, "second", &segments(3));
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+
+    let (spool, _bump) = spool_pda(miner, 0);
+    h.run_ok(&build_create_spool_ix(signer, miner, 0));
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_spool(&spool, &[]);
+
+    let created: Spool = h.state(&spool);
+    assert_eq!(created.authority, signer.to_bytes());
+    assert_eq!(created.total_tapes, 0);
+    assert_eq!(proofs.spool_root(&spool), Some(created.state.get_root().to_bytes()));
+
+    let packed = [(first, 1, [0x11; 32]), (second, 2, [0x22; 32])];
+    for (tape, number, value) in packed {
+        h.run_ok(&build_pack_ix(signer, spool, tape, value));
+        proofs.pack(&spool, number, &value);
+    }
+
+    let full: Spool = h.state(&spool);
+    assert_eq!(full.total_tapes, 2);
+    assert_eq!(proofs.spool_root(&spool), Some(full.state.get_root().to_bytes()));
+
+    let (_, number, value) = packed[1];
+    let proof = proofs.tape_proof(&spool, number).unwrap();
+    h.run_ok(&build_commit_ix(signer, miner, spool, number, proof, value));
+    assert_eq!(h.state::<Miner>(&miner).commitment, value);
+    assert_eq!(h.state::<Spool>(&spool).contains, value);
+
+    let (_, number, value) = packed[0];
+    let proof = proofs.tape_proof(&spool, number).unwrap();
+    h.run_ok(&build_unpack_ix(signer, spool, number, proof, value));
+    proofs.unpack(&spool, number);
+
+    let unpacked: Spool = h.state(&spool);
+    assert_eq!(unpacked.total_tapes, 1);
+    assert_eq!(proofs.spool_root(&spool), Some(unpacked.state.get_root().to_bytes()));
+
+    // (the remaining tape still proves against the new root)
+    let (_, number, value) = packed[1];
+    let proof = proofs.tape_proof(&spool, number).unwrap();
+    h.run_ok(&build_commit_ix(signer, miner, spool, number, proof, value));
+
+    let lamports_before = h.get(&signer).lamports;
+    let spool_lamports = h.get(&spool).lamports;
+    h.run_ok(&build_destroy_spool_ix(signer, spool));
+
+    assert!(!h.exists(&spool));
+    assert_eq!(h.get(&signer).lamports, lamports_before + spool_lamports);
+}
+
+#[test]
+fn spools_only_take_finalized_tapes() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(0);
+
+    let tape = written_tape(&mut h, signer, "draft", &segments(2));
+
+    let name = to_name("rig");
+    let (miner, _bump) = miner_pda(signer, name);
+    h.run_ok(&build_register_ix(signer, name));
+    h.run_ok(&build_create_spool_ix(signer, miner, 0));
+
+    let (spool, _bump) = spool_pda(miner, 0);
+    h.run_err(
+        &build_pack_ix(signer, spool, tape, [1; 32]),
+        tape_sdk::errors::TapeError::UnexpectedState,
+    );
+}
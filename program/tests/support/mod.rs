@@ -0,0 +1,385 @@
+//! Fixtures shared by the integration tests.
+//!
+//! `Harness` runs instructions through mollusk against the compiled program
+//! and keeps the accounts they leave behind, so a test can walk a whole
+//! lifecycle one instruction at a time. The token helpers build SPL accounts
+//! by hand.
+//!
+//! `genesis` writes the global accounts with `set_state` instead of running
+//! `Initialize`: that instruction only validates its accounts and creates
+//! none yet, so the suite does not cover it. Drive it from `genesis` once it
+//! creates the archive, epoch, block, mint and treasury.
+//!
+//! The sdk and miner tests include this module by path, so paths here are
+//! relative to the workspace rather than to `program`.
+//!
+//! Needs the program's ELF; build it first with
+//! `cargo build-sbf --manifest-path program/Cargo.toml`.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use bytemuck::{Pod, Zeroable};
+use mollusk_svm::program::{
+    create_program_account_loader_v3, keyed_account_for_system_program, loader_keys,
+};
+use mollusk_svm::result::{InstructionResult, ProgramResult};
+use mollusk_svm::Mollusk;
+use solana_sdk::account::Account;
+use solana_sdk::hash::Hash;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::slot_hashes::SlotHashes;
+use tape_api::consts::EMPTY_SEGMENT;
+use tape_api::rent::{min_finalization_rent, rent_per_block};
+use tape_api::utils::{compute_challenge, compute_recall_segment};
+use tape_sdk::accounts::*;
+use tape_sdk::consts::*;
+use tape_sdk::errors::TapeError;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::types::*;
+use tape_sdk::{Instruction, Pubkey, TAPE_PROGRAM_ID};
+
+/// SPL token account and mint sizes.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+pub const MINT_LEN: usize = 82;
+
+/// A mollusk instance plus the accounts left behind by earlier instructions.
+pub struct Harness {
+    pub mollusk: Mollusk,
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var(
+                "SBF_OUT_DIR",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"),
+            );
+        }
+
+        let mut mollusk = Mollusk::new(&TAPE_PROGRAM_ID, "solana_pinocchio_starter");
+        mollusk.add_program(
+            &TOKEN_PROGRAM_ID,
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../program/tests/elfs/spl_token"),
+            &loader_keys::LOADER_V3,
+        );
+
+        let mut h = Self {
+            mollusk,
+            accounts: HashMap::new(),
+        };
+        h.set_slot_hashes(Hash::new_from_array([1; 32]));
+        h.warp(1, 1_700_000_000);
+        h
+    }
+
+    /// Point the Clock sysvar at `slot` and `unix_timestamp`.
+    pub fn warp(&mut self, slot: u64, unix_timestamp: i64) {
+        self.mollusk.sysvars.clock.slot = slot;
+        self.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+    }
+
+    /// Make `hash` the most recent entry of SlotHashes, which is what new
+    /// challenges are derived from.
+    pub fn set_slot_hashes(&mut self, hash: Hash) {
+        let slot = self.mollusk.sysvars.clock.slot;
+        self.mollusk.sysvars.slot_hashes = SlotHashes::new(&[(slot, hash)]);
+    }
+
+    pub fn set(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    /// Store `value` as a program-owned account at `key`.
+    pub fn set_state<T: Pod>(&mut self, key: Pubkey, value: &T) {
+        self.set_data(key, bytemuck::bytes_of(value).to_vec());
+    }
+
+    pub fn set_data(&mut self, key: Pubkey, data: Vec<u8>) {
+        let lamports = self.mollusk.sysvars.rent.minimum_balance(data.len());
+        self.set(
+            key,
+            Account {
+                lamports,
+                data,
+                owner: TAPE_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    pub fn get(&self, key: &Pubkey) -> Account {
+        if let Some(account) = self.accounts.get(key) {
+            return account.clone();
+        }
+        if *key == SYSTEM_PROGRAM_ID {
+            return keyed_account_for_system_program().1;
+        }
+        if *key == TOKEN_PROGRAM_ID || *key == TAPE_PROGRAM_ID {
+            return create_program_account_loader_v3(key);
+        }
+        if *key == RENT_SYSVAR_ID {
+            return self.mollusk.sysvars.keyed_account_for_rent_sysvar().1;
+        }
+        if *key == SLOT_HASHES_SYSVAR_ID {
+            return self.mollusk.sysvars.keyed_account_for_slot_hashes_sysvar().1;
+        }
+        Account::default()
+    }
+
+    /// Decode the program account at `key`.
+    pub fn state<T: TapeAccount>(&self, key: &Pubkey) -> T {
+        T::decode(&self.get(key).data).unwrap()
+    }
+
+    pub fn tape(&self, key: &Pubkey) -> Tape {
+        decode_tape_with_nodes(&self.get(key).data).unwrap().0
+    }
+
+    /// Overwrite the fixed fields of the tape at `key`, keeping its cached
+    /// subtree nodes.
+    pub fn set_tape(&mut self, key: Pubkey, tape: &Tape) {
+        let mut account = self.get(&key);
+        account.data[..Tape::LEN].copy_from_slice(bytemuck::bytes_of(tape));
+        self.set(key, account);
+    }
+
+    /// Every account an earlier instruction or `set` left behind.
+    pub fn stored(&self) -> impl Iterator<Item = (&Pubkey, &Account)> {
+        self.accounts.iter()
+    }
+
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.get(key).lamports > 0
+    }
+
+    /// The current state of every account `ix` touches, in its order.
+    pub fn accounts(&self, ix: &Instruction) -> Vec<(Pubkey, Account)> {
+        ix.accounts
+            .iter()
+            .map(|meta| (meta.pubkey, self.get(&meta.pubkey)))
+            .collect()
+    }
+
+    /// Run `ix`, keeping the resulting accounts if it succeeds.
+    pub fn run(&mut self, ix: &Instruction) -> InstructionResult {
+        let accounts = self.accounts(ix);
+        let result = self.mollusk.process_instruction(ix, &accounts);
+        if result.program_result.is_ok() {
+            for (key, account) in &result.resulting_accounts {
+                self.set(*key, account.clone());
+            }
+        }
+        result
+    }
+
+    pub fn run_ok(&mut self, ix: &Instruction) {
+        let result = self.run(ix);
+        assert!(
+            result.program_result.is_ok(),
+            "ix 0x{:02x} failed: {:?}",
+            ix.data[0],
+            result.program_result
+        );
+    }
+
+    /// Run `ix` and check it fails with `err`, leaving every account as it
+    /// was.
+    pub fn run_err(&mut self, ix: &Instruction, err: TapeError) {
+        let result = self.run(ix);
+        assert_eq!(result.program_result, custom(err), "ix 0x{:02x}", ix.data[0]);
+    }
+
+    /// A system account holding 10 SOL.
+    pub fn fund(&mut self, key: Pubkey) {
+        self.set(key, Account::new(10 * LAMPORTS_PER_SOL, 0, &SYSTEM_PROGRAM_ID));
+    }
+
+    /// A signer with SOL and a TAPE token account holding `tokens`.
+    pub fn user(&mut self, tokens: u64) -> Pubkey {
+        let signer = Pubkey::new_unique();
+        self.fund(signer);
+        let ata = tape_ata(signer).0;
+        let account = token_account(&self.mollusk, &mint_pda().0, &signer, tokens);
+        self.set(ata, account);
+        signer
+    }
+
+    pub fn token_balance(&self, key: &Pubkey) -> u64 {
+        let data = self.get(key).data;
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    }
+}
+
+pub fn custom(err: TapeError) -> ProgramResult {
+    ProgramResult::Failure(ProgramError::Custom(err.code()))
+}
+
+/// An initialized SPL token account.
+pub fn token_account(mollusk: &Mollusk, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+    data[..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = 1; // AccountState::Initialized
+
+    Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(TOKEN_ACCOUNT_LEN),
+        data,
+        owner: TOKEN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// An initialized SPL mint with `authority` as its mint authority.
+pub fn mint_account(mollusk: &Mollusk, authority: &Pubkey, supply: u64) -> Account {
+    let mut data = vec![0u8; MINT_LEN];
+    data[..4].copy_from_slice(&1u32.to_le_bytes()); // COption::Some
+    data[4..36].copy_from_slice(authority.as_ref());
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = TOKEN_DECIMALS;
+    data[45] = 1; // is_initialized
+
+    Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(MINT_LEN),
+        data,
+        owner: TOKEN_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+const TOKEN_DECIMALS: u8 = 10;
+
+/// Difficulty settings for `genesis`.
+#[derive(Clone, Copy)]
+pub struct Genesis {
+    pub mining_difficulty: u64,
+    pub packing_difficulty: u64,
+    /// Proofs per block; 1 advances the block on every proof.
+    pub target_participation: u64,
+    /// Tokens the treasury holds for rewards.
+    pub treasury_tokens: u64,
+}
+
+impl Default for Genesis {
+    fn default() -> Self {
+        Self {
+            mining_difficulty: 0,
+            packing_difficulty: 0,
+            target_participation: 1,
+            treasury_tokens: 1_000_000_000,
+        }
+    }
+}
+
+/// Seed the accounts `Initialize` is meant to create: archive, epoch, first
+/// block, treasury, mint and treasury token account (see the module docs).
+pub fn genesis(h: &mut Harness, config: Genesis) {
+    h.set_state(archive_pda().0, &Archive::zeroed());
+
+    let mut epoch = Epoch::zeroed();
+    epoch.mining_difficulty = config.mining_difficulty;
+    epoch.packing_difficulty = config.packing_difficulty;
+    epoch.target_participation = config.target_participation;
+    h.set_state(epoch_pda().0, &epoch);
+
+    let mut block = Block::zeroed();
+    block.number = 1;
+    block.challenge = [7; 32];
+    h.set_state(block_pda().0, &block);
+
+    let treasury = treasury_pda().0;
+    h.set_state(treasury, &Treasury::zeroed());
+
+    let mint = mint_account(&h.mollusk, &treasury, config.treasury_tokens);
+    h.set(mint_pda().0, mint);
+    let ata = token_account(&h.mollusk, &mint_pda().0, &treasury, config.treasury_tokens);
+    h.set(treasury_ata().0, ata);
+}
+
+/// The segments a test tape holds.
+pub fn segments(count: usize) -> Vec<[u8; SEGMENT_SIZE]> {
+    (0..count)
+        .map(|i| [(i % 251) as u8 + 1; SEGMENT_SIZE])
+        .collect()
+}
+
+/// Create a tape called `name` and write `data` to it in one instruction.
+pub fn written_tape(h: &mut Harness, signer: Pubkey, name: &str, data: &[[u8; SEGMENT_SIZE]]) -> Pubkey {
+    let name = to_name(name);
+    let (tape, _bump) = tape_pda(signer, &name);
+
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+    h.run_ok(&build_write_ix(signer, tape, bytemuck::cast_slice(data)));
+    tape
+}
+
+/// Write, pay for and finalize a tape, and make it part of the challenge
+/// set of the current block. `signer` needs the tokens to pay for it.
+pub fn finalized_tape(h: &mut Harness, signer: Pubkey, name: &str, data: &[[u8; SEGMENT_SIZE]]) -> Pubkey {
+    let tape = written_tape(h, signer, name, data);
+
+    let rent = min_finalization_rent(data.len() as u64);
+    h.run_ok(&build_subsidize_ix(signer, tape_ata(signer).0, tape, rent));
+    h.run_ok(&build_finalize_ix(signer, tape));
+    open_challenge_set(h);
+
+    tape
+}
+
+/// Let the current block recall every finalized tape, as it would once the
+/// block after finalization starts.
+pub fn open_challenge_set(h: &mut Harness) {
+    let archive: Archive = h.state(&archive_pda().0);
+    let mut block: Block = h.state(&block_pda().0);
+    block.challenge_set = archive.tapes_stored;
+    h.set_state(block_pda().0, &block);
+}
+
+/// Solve the current block for `miner`, against `tape` holding `data`.
+/// Tapes without minimum rent are proven against `EMPTY_SEGMENT`.
+pub fn mine_ix(
+    h: &Harness,
+    signer: Pubkey,
+    miner: Pubkey,
+    tape: Pubkey,
+    data: &[[u8; SEGMENT_SIZE]],
+) -> Instruction {
+    let epoch: Epoch = h.state(&epoch_pda().0);
+    let block: Block = h.state(&block_pda().0);
+    let state: Miner = h.state(&miner);
+    let decoded = h.tape(&tape);
+
+    let challenge = compute_challenge(&block.challenge, &state.challenge);
+
+    let (segment, poa) = if decoded.balance >= rent_per_block(decoded.total_segments) {
+        let number = compute_recall_segment(&challenge, decoded.total_segments);
+        let segment = data[number as usize];
+
+        let mut proofs = tape_sdk::prover::ProofBuilder::new();
+        proofs.insert_tape(decoded.number, &tape, data);
+        let path = proofs.segment_proof(decoded.number, number).unwrap();
+
+        let packed = packx::solve(&miner.to_bytes(), &segment, epoch.packing_difficulty as u32)
+            .expect("packing failed");
+        (segment, PoA::from_solution(&packed, path))
+    } else {
+        (EMPTY_SEGMENT, PoA::zeroed())
+    };
+
+    // (test difficulties are low, so one thread finds a solution quickly)
+    let solution = (0u64..)
+        .filter_map(|nonce| crankx::solve(&challenge, &segment, &nonce.to_le_bytes()).ok())
+        .find(|solution| solution.difficulty() as u64 >= epoch.mining_difficulty)
+        .unwrap();
+    let pow = PoW::from_solution(&solution);
+
+    build_mine_ix(signer, miner, tape, pow, poa)
+}
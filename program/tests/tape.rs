@@ -0,0 +1,194 @@
+//! Tapes from creation to finalization.
+
+mod support;
+
+use support::*;
+use tape_api::consts::MAX_DRAIN_PER_IX;
+use tape_api::rent::min_finalization_rent;
+use tape_sdk::accounts::*;
+use tape_sdk::consts::*;
+use tape_sdk::errors::TapeError;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::prover::ProofBuilder;
+use tape_sdk::types::*;
+
+#[test]
+fn tape_lifecycle() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let name = to_name("lifecycle");
+    let (tape, _bump) = tape_pda(signer, &name);
+    let (writer, _bump) = writer_pda(tape);
+
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+    let created = h.tape(&tape);
+    assert_eq!(TapeState::from(created.state), TapeState::Created);
+    assert_eq!(created.authority, signer.to_bytes());
+
+    let mut data = segments(6);
+    h.run_ok(&build_write_ix(signer, tape, bytemuck::cast_slice(&data[..4])));
+    h.warp(2, 1_700_000_060);
+    h.run_ok(&build_write_ix(signer, tape, bytemuck::cast_slice(&data[4..])));
+
+    let written = h.tape(&tape);
+    assert_eq!(TapeState::from(written.state), TapeState::Writing);
+    assert_eq!(written.total_segments, 6);
+    assert_eq!(written.first_slot, 1);
+    assert_eq!(written.tail_slot, 2);
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_tape(0, &tape, &data);
+    assert_eq!(proofs.tape_root(0), Some(written.merkle_root));
+
+    let new = [0xEE; SEGMENT_SIZE];
+    let proof = proofs.segment_proof(0, 3).unwrap();
+    h.run_ok(&build_update_ix(signer, tape, 3, data[3], new, proof.as_array()));
+    proofs.update_segment(0, 3, &new);
+    data[3] = new;
+    assert_eq!(proofs.tape_root(0), Some(h.tape(&tape).merkle_root));
+
+    h.run_ok(&build_set_header_ix(signer, tape, [7; HEADER_SIZE]));
+    assert_eq!(h.tape(&tape).header, [7; HEADER_SIZE]);
+
+    let rent = min_finalization_rent(6);
+    let treasury_before = h.token_balance(&treasury_ata().0);
+    h.run_ok(&build_subsidize_ix(signer, tape_ata(signer).0, tape, rent));
+    assert_eq!(h.tape(&tape).balance, rent);
+    assert_eq!(h.token_balance(&treasury_ata().0), treasury_before + rent);
+    assert_eq!(h.token_balance(&tape_ata(signer).0), 1_000_000_000 - rent);
+
+    let lamports_before = h.get(&signer).lamports;
+    let writer_lamports = h.get(&writer).lamports;
+    h.run_ok(&build_finalize_ix(signer, tape));
+
+    let finalized = h.tape(&tape);
+    assert_eq!(TapeState::from(finalized.state), TapeState::Finalized);
+    assert_eq!(finalized.number, 1);
+    assert_eq!(finalized.merkle_root, proofs.tape_root(0).unwrap());
+    assert!(!h.exists(&writer));
+    assert_eq!(h.get(&signer).lamports, lamports_before + writer_lamports);
+
+    let archive: Archive = h.state(&archive_pda().0);
+    assert_eq!(archive.tapes_stored, 1);
+    assert_eq!(archive.segments_stored, 6);
+}
+
+#[test]
+fn out_of_order_writes_finalize_once_contiguous() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let name = to_name("scattered");
+    let (tape, _bump) = tape_pda(signer, &name);
+    let data = segments(4);
+
+    h.run_ok(&build_create_tape_ix(signer, name, 2));
+    h.run_ok(&build_write_at_ix(signer, tape, 3, &data[3]));
+    h.run_ok(&build_write_at_ix(signer, tape, 1, &data[1]));
+    assert_eq!(h.tape(&tape).total_segments, 0);
+
+    h.run_ok(&build_write_at_ix(signer, tape, 0, &data[0]));
+    assert_eq!(h.tape(&tape).total_segments, 2);
+    h.run_ok(&build_write_at_ix(signer, tape, 2, &data[2]));
+    assert_eq!(h.tape(&tape).total_segments, 4);
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_tape(0, &tape, &data);
+    assert_eq!(proofs.tape_root(0), Some(h.tape(&tape).merkle_root));
+
+    h.run_ok(&build_subsidize_ix(signer, tape_ata(signer).0, tape, min_finalization_rent(4)));
+    h.run_ok(&build_finalize_ix(signer, tape));
+    assert_eq!(TapeState::from(h.tape(&tape).state), TapeState::Finalized);
+}
+
+#[test]
+fn finalize_drains_every_pending_segment() {
+    let data = segments(3 * MAX_DRAIN_PER_IX + 8);
+    let rent = min_finalization_rent(data.len() as u64);
+
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(rent);
+
+    let name = to_name("backlog");
+    let (tape, _bump) = tape_pda(signer, &name);
+
+    h.run_ok(&build_create_tape_ix(signer, name, 0));
+    for (i, chunk) in data[1..].chunks(8).enumerate() {
+        let first = 1 + 8 * i as u64;
+        h.run_ok(&build_write_at_ix(signer, tape, first, bytemuck::cast_slice(chunk)));
+    }
+    h.run_ok(&build_write_at_ix(signer, tape, 0, &data[0]));
+
+    // (one write drains at most MAX_DRAIN_PER_IX, so most are still pending)
+    let pending = data.len() as u64 - h.tape(&tape).total_segments;
+    assert!(pending > MAX_DRAIN_PER_IX as u64);
+
+    h.run_ok(&build_subsidize_ix(signer, tape_ata(signer).0, tape, rent));
+    h.run_ok(&build_finalize_ix(signer, tape));
+
+    let finalized = h.tape(&tape);
+    assert_eq!(TapeState::from(finalized.state), TapeState::Finalized);
+    assert_eq!(finalized.total_segments, data.len() as u64);
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_tape(0, &tape, &data);
+    assert_eq!(proofs.tape_root(0), Some(finalized.merkle_root));
+}
+
+#[test]
+fn batch_updates_match_single_updates() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(0);
+
+    let data = segments(8);
+    let tape = written_tape(&mut h, signer, "batch", &data);
+
+    let mut proofs = ProofBuilder::new();
+    proofs.insert_tape(0, &tape, &data);
+
+    let changed = [1u64, 2, 6];
+    let updates: Vec<_> = changed
+        .iter()
+        .map(|&number| SegmentUpdate {
+            segment_number: number,
+            old_data: data[number as usize],
+            new_data: [0xA0 + number as u8; SEGMENT_SIZE],
+        })
+        .collect();
+    let proof = tape_sdk::proof::build_multiproof(&changed, |number| {
+        proofs.segment_proof(0, number).unwrap().into_array()
+    });
+
+    h.run_ok(&build_update_batch_ix(signer, tape, &updates, &proof));
+
+    for update in &updates {
+        proofs.update_segment(0, update.segment_number, &update.new_data);
+    }
+    assert_eq!(proofs.tape_root(0), Some(h.tape(&tape).merkle_root));
+}
+
+#[test]
+fn finalized_tapes_are_read_only() {
+    let mut h = Harness::new();
+    genesis(&mut h, Genesis::default());
+    let signer = h.user(1_000_000_000);
+
+    let data = segments(2);
+    let tape = finalized_tape(&mut h, signer, "sealed", &data);
+
+    // (finalize closed the writer, so writes fail before reaching the tape)
+    let write = h.run(&build_write_ix(signer, tape, &[1; SEGMENT_SIZE]));
+    assert!(write.program_result.is_err());
+
+    h.run_err(
+        &build_set_header_ix(signer, tape, [1; HEADER_SIZE]),
+        TapeError::UnexpectedState,
+    );
+    assert_eq!(h.tape(&tape).header, [0; HEADER_SIZE]);
+}
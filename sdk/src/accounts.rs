@@ -0,0 +1,143 @@
+//! Decoders for the program's zero-copy account layouts.
+//!
+//! The layouts are `tape_api::state`'s, re-exported here. RPC account data
+//! carries no alignment guarantee, so every decoder copies the bytes out
+//! with `try_pod_read_unaligned` instead of casting in place.
+
+use bytemuck::Pod;
+
+pub use tape_api::state::utils::DataLen;
+pub use tape_api::state::{
+    slot_index, subtree_node_count, tape_account_size, writer_account_size, Archive, Block,
+    Epoch, Miner, SegmentSlot, Spool, SubtreeNode, Tape, TapeState, Treasury, Writer,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccountDecodeError {
+    /// The account data length does not match the expected layout.
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl core::fmt::Display for AccountDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidLength { expected, actual } => {
+                write!(f, "invalid account length: expected {expected} bytes, got {actual}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AccountDecodeError {}
+
+/// Implemented by every account layout owned by the tape program.
+pub trait TapeAccount: Pod + DataLen {
+    fn decode(data: &[u8]) -> Result<Self, AccountDecodeError> {
+        bytemuck::try_pod_read_unaligned(data).map_err(|_| AccountDecodeError::InvalidLength {
+            expected: Self::LEN,
+            actual: data.len(),
+        })
+    }
+}
+
+impl TapeAccount for Archive {}
+impl TapeAccount for Epoch {}
+impl TapeAccount for Block {}
+impl TapeAccount for Tape {}
+impl TapeAccount for Writer {}
+impl TapeAccount for Miner {}
+impl TapeAccount for Spool {}
+impl TapeAccount for Treasury {}
+
+/// Decode a tape account, including the cached subtree nodes that follow
+/// the fixed fields.
+pub fn decode_tape_with_nodes(
+    data: &[u8],
+) -> Result<(Tape, Vec<SubtreeNode>), AccountDecodeError> {
+    if data.len() < Tape::LEN || (data.len() - Tape::LEN) % 32 != 0 {
+        return Err(AccountDecodeError::InvalidLength {
+            expected: Tape::LEN,
+            actual: data.len(),
+        });
+    }
+    let (head, tail) = data.split_at(Tape::LEN);
+    let tape = Tape::decode(head)?;
+    let nodes = tail
+        .chunks_exact(32)
+        .map(|c| c.try_into().expect("chunk is 32 bytes"))
+        .collect();
+    Ok((tape, nodes))
+}
+
+/// Decode a writer account, including the out-of-order segment slots
+/// (leaves waiting for the segments before them, zero when empty) that
+/// follow the fixed fields.
+pub fn decode_writer_with_slots(
+    data: &[u8],
+) -> Result<(Writer, Vec<SegmentSlot>), AccountDecodeError> {
+    if data.len() != writer_account_size() {
+        return Err(AccountDecodeError::InvalidLength {
+            expected: writer_account_size(),
+            actual: data.len(),
+        });
+    }
+    let (head, tail) = data.split_at(Writer::LEN);
+    let writer = Writer::decode(head)?;
+    let slots = tail
+        .chunks_exact(32)
+        .map(|c| c.try_into().expect("chunk is 32 bytes"))
+        .collect();
+    Ok((writer, slots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::*;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn decode_rejects_wrong_length() {
+        let data = vec![0u8; Archive::LEN + 1];
+        assert_eq!(
+            Archive::decode(&data),
+            Err(AccountDecodeError::InvalidLength {
+                expected: Archive::LEN,
+                actual: Archive::LEN + 1,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_unaligned() {
+        let archive = Archive {
+            tapes_stored: 7,
+            segments_stored: 42,
+        };
+        // Offset by one byte so the slice is not 8-byte aligned.
+        let mut data = vec![0u8; Archive::LEN + 1];
+        data[1..].copy_from_slice(bytemuck::bytes_of(&archive));
+        assert_eq!(Archive::decode(&data[1..]).unwrap(), archive);
+    }
+
+    #[test]
+    fn decode_writer_with_slots() {
+        let data = vec![0u8; writer_account_size()];
+        let (_, slots) = decode_writer_with_slots(&data).unwrap();
+        assert_eq!(slots.len(), WRITE_WINDOW);
+        assert!(decode_writer_with_slots(&data[..Writer::LEN]).is_err());
+    }
+
+    #[test]
+    fn decode_tape_with_nodes() {
+        let mut data = vec![0u8; tape_account_size(1)];
+        data[Tape::LEN + 32..].fill(5);
+
+        let (tape, nodes) = decode_tape_with_nodes(&data).unwrap();
+        assert_eq!(tape, Tape::zeroed());
+        assert_eq!(nodes, vec![[0; 32], [5; 32]]);
+
+        assert!(decode_tape_with_nodes(&data[..Tape::LEN + 31]).is_err());
+        assert!(Tape::decode(&data).is_err());
+    }
+}
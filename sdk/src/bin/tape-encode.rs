@@ -0,0 +1,139 @@
+//! Encode a file for a new tape and print what writing it will take.
+//!
+//! ```text
+//! tape-encode <FILE> --authority <PUBKEY> [--name <NAME>] [--subtree-depth <N>] [--compress]
+//! ```
+//!
+//! Nothing is sent; the report shows the tape address, the Merkle root the
+//! tape will end up with, the rent to pay before finalizing and the
+//! transactions to send, so they can be checked first.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use tape_sdk::consts::*;
+use tape_sdk::encoder::{encode, Compression};
+use tape_sdk::pda::{tape_ata, tape_pda};
+use tape_sdk::plan::{plan_tape, transaction_size};
+use tape_sdk::Pubkey;
+
+const USAGE: &str = "usage: tape-encode <FILE> --authority <PUBKEY> [--name <NAME>] \
+                     [--subtree-depth <N>] [--compress]";
+
+struct Args {
+    file: PathBuf,
+    authority: Pubkey,
+    name: Option<String>,
+    subtree_depth: u64,
+    compression: Compression,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut file = None;
+    let mut authority = None;
+    let mut name = None;
+    let mut subtree_depth = 0;
+    let mut compression = Compression::None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--authority" => {
+                let value = value("--authority")?;
+                authority =
+                    Some(Pubkey::from_str(&value).map_err(|e| format!("--authority: {e}"))?);
+            }
+            "--name" => name = Some(value("--name")?),
+            "--subtree-depth" => {
+                subtree_depth = value("--subtree-depth")?
+                    .parse()
+                    .map_err(|e| format!("--subtree-depth: {e}"))?;
+            }
+            "--compress" => compression = Compression::Deflate,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("unknown flag {arg}")),
+            _ if file.is_none() => file = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+
+    if subtree_depth > MAX_SUBTREE_DEPTH {
+        return Err(format!("--subtree-depth must be at most {MAX_SUBTREE_DEPTH}"));
+    }
+
+    Ok(Args {
+        file: file.ok_or("missing <FILE>")?,
+        authority: authority.ok_or("missing --authority")?,
+        name,
+        subtree_depth,
+        compression,
+    })
+}
+
+/// `name`, or the file name, cut to `NAME_LEN` bytes.
+fn tape_name(args: &Args) -> [u8; NAME_LEN] {
+    let name = match &args.name {
+        Some(name) => name.clone(),
+        None => args
+            .file
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+
+    let mut out = [0u8; NAME_LEN];
+    let len = name.len().min(NAME_LEN);
+    out[..len].copy_from_slice(&name.as_bytes()[..len]);
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let data = std::fs::read(&args.file)
+        .map_err(|e| format!("reading {}: {e}", args.file.display()))?;
+
+    let name = tape_name(&args);
+    let (tape, _bump) = tape_pda(args.authority, &name);
+
+    let encoded = encode(&tape, &data, args.compression).map_err(|e| e.to_string())?;
+    let ata = tape_ata(args.authority).0;
+    let plan = plan_tape(args.authority, ata, name, args.subtree_depth, &encoded);
+
+    println!("tape          {}", plan.tape);
+    println!("name          {}", String::from_utf8_lossy(&name).trim_end_matches('\0'));
+    println!(
+        "payload       {} bytes ({:?}, from {} bytes)",
+        encoded.header.data_len, args.compression, encoded.header.original_len
+    );
+    println!("segments      {}", plan.total_segments);
+    println!("root          {}", hex(&plan.root));
+    println!("rent          {} (min_finalization_rent, paid from {ata})", plan.rent);
+    println!("transactions  {}", plan.transactions.len());
+
+    for (i, tx) in plan.transactions.iter().enumerate() {
+        let kinds: Vec<String> = tx.iter().map(|ix| format!("0x{:02x}", ix.data[0])).collect();
+        println!(
+            "  {i:>5}  {:>4} bytes  {}",
+            transaction_size(&args.authority, tx),
+            kinds.join(" ")
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = parse_args().and_then(run);
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
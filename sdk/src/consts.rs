@@ -0,0 +1,16 @@
+use solana_pubkey::{pubkey, Pubkey};
+
+pub use tape_api::consts::{
+    ARCHIVE, BLOCK, EPOCH, EVENT_AUTHORITY, HEADER_SIZE, MAX_BATCH_UPDATES, MAX_SEGMENTS_PER_TAPE,
+    MAX_SUBTREE_DEPTH, MAX_SUBTREE_NODES, METADATA, MINER, MINT, MINT_SEED, NAME_LEN,
+    PACKED_SEGMENT_SIZE, SEGMENT_PROOF_LEN, SEGMENT_SIZE, SEGMENT_TREE_HEIGHT, SPOOL, TAPE,
+    TAPE_PROOF_LEN, TAPE_TREE_HEIGHT, TREASURY, WRITER, WRITE_WINDOW,
+};
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const MPL_TOKEN_METADATA_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+pub const RENT_SYSVAR_ID: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
+pub const SLOT_HASHES_SYSVAR_ID: Pubkey = pubkey!("SysvarS1otHashes111111111111111111111111111");
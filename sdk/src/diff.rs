@@ -0,0 +1,254 @@
+//! Updating a tape in place when the file on it changes.
+//!
+//! `plan_diff` compares the old and new payloads segment by segment and
+//! emits a `TapeUpdate` for each segment that differs, each with a full proof
+//! against the tree as it will be after the updates before it. Segments past
+//! the old end are appended with `TapeWrite`. A shorter file zeroes the
+//! segments it no longer covers, since a tape cannot shrink; the header's
+//! `data_len` is what tells readers where the data ends.
+//!
+//! Updates need the tape to still be writable, so this is for tapes that have
+//! not been finalized.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use tape_api::utils::compute_leaf;
+
+use crate::consts::*;
+use crate::encoder::EncodeError;
+use crate::instructions::{build_update_ix, build_write_ix};
+use crate::plan::segments_per_write;
+use crate::prover::FullTree;
+
+/// The instructions that turn a tape holding one payload into one holding
+/// another.
+#[derive(Clone, Debug)]
+pub struct TapeDiff {
+    /// Segments rewritten in place, in the order their updates are sent.
+    pub changed: Vec<u64>,
+    /// Segments added after the old end of the tape.
+    pub appended: u64,
+    /// The tape's Merkle root once every instruction has landed.
+    pub root: [u8; 32],
+    /// Updates first, then writes; send them in this order.
+    pub instructions: Vec<Instruction>,
+}
+
+/// Plan the updates that turn the payload `old`, already on `tape`, into
+/// `new`.
+pub fn plan_diff(
+    signer: Pubkey,
+    tape: Pubkey,
+    old: &[u8],
+    new: &[u8],
+) -> Result<TapeDiff, EncodeError> {
+    let old = split(old);
+    let mut new = split(new);
+
+    if new.len() > MAX_SEGMENTS_PER_TAPE {
+        return Err(EncodeError::TooLong {
+            segments: new.len(),
+        });
+    }
+    if new.len() < old.len() {
+        new.resize(old.len(), [0; SEGMENT_SIZE]);
+    }
+
+    let leaves = old
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| compute_leaf(i as u64, segment).to_bytes());
+    let mut tree = FullTree::<SEGMENT_TREE_HEIGHT>::from_leaves(&[tape.as_ref()], leaves);
+    let mut changed = Vec::new();
+    let mut instructions = Vec::new();
+
+    for (number, (before, after)) in old.iter().zip(&new).enumerate() {
+        if before == after {
+            continue;
+        }
+
+        let number = number as u64;
+        let proof = tree.proof(number as usize);
+        instructions.push(build_update_ix(signer, tape, number, *before, *after, &proof));
+        tree.set(number as usize, compute_leaf(number, after).to_bytes());
+        changed.push(number);
+    }
+
+    let appended = &new[old.len()..];
+    let per_write = segments_per_write(signer, tape);
+    for chunk in appended.chunks(per_write) {
+        instructions.push(build_write_ix(signer, tape, bytemuck::cast_slice(chunk)));
+    }
+    for (i, segment) in appended.iter().enumerate() {
+        let number = (old.len() + i) as u64;
+        tree.push(compute_leaf(number, segment).to_bytes());
+    }
+
+    Ok(TapeDiff {
+        changed,
+        appended: appended.len() as u64,
+        root: tree.root(),
+        instructions,
+    })
+}
+
+/// `data` cut into zero-padded segments, as `TapeWrite` stores it.
+fn split(data: &[u8]) -> Vec<[u8; SEGMENT_SIZE]> {
+    data.chunks(SEGMENT_SIZE)
+        .map(|chunk| {
+            let mut segment = [0u8; SEGMENT_SIZE];
+            segment[..chunk.len()].copy_from_slice(chunk);
+            segment
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{encode, Compression};
+    use crate::instructions::{TapeInstruction, UpdateTapeIxData};
+    use crate::types::SegmentTree;
+    use tape_api::utils::{update_segment, write_segment};
+
+    fn sample(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8
+            })
+            .collect()
+    }
+
+    /// Apply the diff the way the program would, starting from `old`.
+    fn apply(tape: &Pubkey, old: &[u8], diff: &TapeDiff) -> SegmentTree {
+        let mut tree = SegmentTree::new(&[tape.as_ref()]);
+        let mut total = 0;
+        for segment in split(old) {
+            write_segment(&mut tree, total, &segment).unwrap();
+            total += 1;
+        }
+
+        for ix in &diff.instructions {
+            match TapeInstruction::try_from(&ix.data[0]).unwrap() {
+                TapeInstruction::TapeUpdate => {
+                    let start = 1 + core::mem::size_of::<UpdateTapeIxData>();
+                    let args: UpdateTapeIxData = bytemuck::pod_read_unaligned(&ix.data[1..start]);
+                    let proof: [[u8; 32]; SEGMENT_PROOF_LEN] =
+                        bytemuck::pod_read_unaligned(&ix.data[start..]);
+                    update_segment(
+                        &mut tree,
+                        args.segment_number,
+                        &args.old_data,
+                        &args.new_data,
+                        &proof,
+                    )
+                    .unwrap();
+                }
+                TapeInstruction::TapeWrite => {
+                    for segment in split(&ix.data[1..]) {
+                        write_segment(&mut tree, total, &segment).unwrap();
+                        total += 1;
+                    }
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+
+        tree
+    }
+
+    #[test]
+    fn full_tree_matches_the_segment_tree() {
+        let tape = Pubkey::new_unique();
+        let data = sample(13 * SEGMENT_SIZE - 5, 1);
+        let segments = split(&data);
+        let layers = FullTree::<SEGMENT_TREE_HEIGHT>::from_leaves(
+            &[tape.as_ref()],
+            segments
+                .iter()
+                .enumerate()
+                .map(|(i, s)| compute_leaf(i as u64, s).to_bytes()),
+        );
+
+        let encoded = encode(&tape, &data, Compression::None).unwrap();
+        assert_eq!(layers.root(), encoded.root);
+
+        let tree = SegmentTree::new(&[tape.as_ref()]);
+        let leaves: Vec<_> = segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| compute_leaf(i as u64, s))
+            .collect();
+        for number in [0, 6, 12] {
+            let expected: Vec<[u8; 32]> = tree
+                .get_merkle_proof(&leaves, number)
+                .iter()
+                .map(|hash| hash.to_bytes())
+                .collect();
+            assert_eq!(layers.proof(number).to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn only_changed_segments_are_updated() {
+        let tape = Pubkey::new_unique();
+        let old = sample(40 * SEGMENT_SIZE, 2);
+        let mut new = old.clone();
+        new[3 * SEGMENT_SIZE + 7] ^= 1;
+        new[4 * SEGMENT_SIZE] ^= 1;
+        new[31 * SEGMENT_SIZE + 100] ^= 1;
+
+        let diff = plan_diff(Pubkey::new_unique(), tape, &old, &new).unwrap();
+        assert_eq!(diff.changed, vec![3, 4, 31]);
+        assert_eq!(diff.appended, 0);
+        assert_eq!(diff.instructions.len(), 3);
+
+        let tree = apply(&tape, &old, &diff);
+        assert_eq!(tree.get_root().to_bytes(), diff.root);
+        assert_eq!(diff.root, encode(&tape, &new, Compression::None).unwrap().root);
+    }
+
+    #[test]
+    fn growth_is_appended() {
+        let tape = Pubkey::new_unique();
+        let old = sample(10 * SEGMENT_SIZE + 20, 3);
+        let mut new = old.clone();
+        new[0] ^= 1;
+        new.extend(sample(30 * SEGMENT_SIZE, 4));
+
+        let diff = plan_diff(Pubkey::new_unique(), tape, &old, &new).unwrap();
+        // The old last segment was partly padding and now holds more data.
+        assert_eq!(diff.changed, vec![0, 10]);
+        assert_eq!(diff.appended, 30);
+
+        let tree = apply(&tape, &old, &diff);
+        assert_eq!(tree.get_root().to_bytes(), diff.root);
+        assert_eq!(diff.root, encode(&tape, &new, Compression::None).unwrap().root);
+    }
+
+    #[test]
+    fn shrinking_zeroes_the_tail() {
+        let tape = Pubkey::new_unique();
+        let old = sample(8 * SEGMENT_SIZE, 5);
+        let new = old[..5 * SEGMENT_SIZE].to_vec();
+
+        let diff = plan_diff(Pubkey::new_unique(), tape, &old, &new).unwrap();
+        assert_eq!(diff.changed, vec![5, 6, 7]);
+
+        let mut padded = new.clone();
+        padded.resize(old.len(), 0);
+        let tree = apply(&tape, &old, &diff);
+        assert_eq!(tree.get_root().to_bytes(), diff.root);
+        assert_eq!(diff.root, encode(&tape, &padded, Compression::None).unwrap().root);
+    }
+
+    #[test]
+    fn unchanged_file_needs_nothing() {
+        let old = sample(5000, 6);
+        let diff = plan_diff(Pubkey::new_unique(), Pubkey::new_unique(), &old, &old).unwrap();
+        assert!(diff.instructions.is_empty());
+        assert!(diff.changed.is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+//! Turning a file into tape segments.
+//!
+//! `encode` optionally compresses the input, splits it into `SEGMENT_SIZE`
+//! segments (the last one zero-padded, as `TapeWrite` does) and folds them
+//! into the same `SegmentTree` the writer account builds on chain, so the
+//! root is known before anything is sent. `TapeHeader` records what a reader
+//! needs to get the original bytes back; it goes in the tape's header via
+//! `TapeSetHeader`.
+
+use std::io::{Read, Write};
+
+use bytemuck::{Pod, Zeroable};
+use solana_pubkey::Pubkey;
+use tape_api::rent::min_finalization_rent;
+use tape_api::utils::compute_leaf;
+
+use crate::consts::*;
+use crate::types::SegmentTree;
+
+/// Version of the `TapeHeader` layout written by this crate.
+pub const TAPE_HEADER_VERSION: u64 = 1;
+
+#[repr(u64)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Compression {
+    #[default]
+    None = 0,
+    /// Raw DEFLATE (RFC 1951).
+    Deflate = 1,
+}
+
+impl Compression {
+    pub fn from_u64(value: u64) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+
+    pub fn compress(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::None => data.to_vec(),
+            Self::Deflate => {
+                let mut encoder = flate2::write::DeflateEncoder::new(
+                    Vec::new(),
+                    flate2::Compression::best(),
+                );
+                encoder.write_all(data).expect("writing to a Vec cannot fail");
+                encoder.finish().expect("writing to a Vec cannot fail")
+            }
+        }
+    }
+
+    pub fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Deflate => {
+                let mut out = Vec::new();
+                flate2::read::DeflateDecoder::new(data).read_to_end(&mut out)?;
+                Ok(out)
+            }
+        }
+    }
+}
+
+/// Layout of the tape's `HEADER_SIZE`-byte header for tapes written by
+/// `encode`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct TapeHeader {
+    pub version: u64,
+    /// `Compression` of the payload.
+    pub compression: u64,
+    /// Payload bytes on the tape, before the last segment's padding.
+    pub data_len: u64,
+    /// Length of the original file.
+    pub original_len: u64,
+    pub reserved: [u8; HEADER_SIZE - 32],
+}
+
+const _: () = assert!(core::mem::size_of::<TapeHeader>() == HEADER_SIZE);
+
+impl TapeHeader {
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        bytemuck::cast(*self)
+    }
+
+    pub fn from_bytes(bytes: &[u8; HEADER_SIZE]) -> Self {
+        bytemuck::pod_read_unaligned(bytes)
+    }
+
+    pub fn compression(&self) -> Option<Compression> {
+        Compression::from_u64(self.compression)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EncodeError {
+    /// The (compressed) payload needs more segments than a tape can hold.
+    TooLong { segments: usize },
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooLong { segments } => write!(
+                f,
+                "payload needs {segments} segments, a tape holds at most {MAX_SEGMENTS_PER_TAPE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// A file laid out as tape segments, with the root the tape will end up with.
+#[derive(Clone, Debug)]
+pub struct EncodedTape {
+    pub header: TapeHeader,
+    pub segments: Vec<[u8; SEGMENT_SIZE]>,
+    pub root: [u8; 32],
+}
+
+impl EncodedTape {
+    pub fn total_segments(&self) -> u64 {
+        self.segments.len() as u64
+    }
+
+    /// The segments as one byte string, ready to be split across `TapeWrite`s.
+    pub fn data(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.segments)
+    }
+
+    /// TAPE the tape must hold before it can be finalized.
+    pub fn finalization_rent(&self) -> u64 {
+        min_finalization_rent(self.total_segments())
+    }
+}
+
+/// Encode `data` for the tape at address `tape`; the address seeds the
+/// tape's segment tree.
+pub fn encode(
+    tape: &Pubkey,
+    data: &[u8],
+    compression: Compression,
+) -> Result<EncodedTape, EncodeError> {
+    let payload = compression.compress(data);
+
+    let segment_count = payload.len().div_ceil(SEGMENT_SIZE);
+    if segment_count > MAX_SEGMENTS_PER_TAPE {
+        return Err(EncodeError::TooLong {
+            segments: segment_count,
+        });
+    }
+
+    let mut segments: Vec<[u8; SEGMENT_SIZE]> = payload
+        .chunks(SEGMENT_SIZE)
+        .map(|chunk| {
+            let mut segment = [0u8; SEGMENT_SIZE];
+            segment[..chunk.len()].copy_from_slice(chunk);
+            segment
+        })
+        .collect();
+
+    // A tape without segments cannot be finalized, so an empty payload
+    // still takes one zero segment; `data_len` says none of it is data.
+    if segments.is_empty() {
+        segments.push([0; SEGMENT_SIZE]);
+    }
+
+    let mut tree = SegmentTree::new(&[tape.as_ref()]);
+    for (i, segment) in segments.iter().enumerate() {
+        tree.try_add_leaf(compute_leaf(i as u64, segment))
+            .expect("segment count is checked against the tree capacity");
+    }
+
+    Ok(EncodedTape {
+        header: TapeHeader {
+            version: TAPE_HEADER_VERSION,
+            compression: compression as u64,
+            data_len: payload.len() as u64,
+            original_len: data.len() as u64,
+            reserved: [0; HEADER_SIZE - 32],
+        },
+        segments,
+        root: tree.get_root().to_bytes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tape_api::utils::write_segment;
+
+    #[test]
+    fn root_matches_program_writes() {
+        let tape = Pubkey::new_unique();
+        let data: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        let encoded = encode(&tape, &data, Compression::None).unwrap();
+
+        assert_eq!(encoded.total_segments(), 8);
+        assert_eq!(encoded.segments[7][1000 - 7 * SEGMENT_SIZE..], [0; 8 * SEGMENT_SIZE - 1000]);
+
+        // What the writer account does with the same bytes.
+        let mut tree = SegmentTree::new(&[tape.as_ref()]);
+        for (i, segment) in encoded.data().chunks(SEGMENT_SIZE).enumerate() {
+            write_segment(&mut tree, i as u64, segment.try_into().unwrap()).unwrap();
+        }
+        assert_eq!(encoded.root, tree.get_root().to_bytes());
+    }
+
+    #[test]
+    fn root_depends_on_tape_address() {
+        let a = encode(&Pubkey::new_unique(), b"tape", Compression::None).unwrap();
+        let b = encode(&Pubkey::new_unique(), b"tape", Compression::None).unwrap();
+        assert_ne!(a.root, b.root);
+    }
+
+    #[test]
+    fn compression_round_trips() {
+        let data = vec![42u8; 10 * SEGMENT_SIZE];
+        let encoded = encode(&Pubkey::new_unique(), &data, Compression::Deflate).unwrap();
+
+        assert!(encoded.total_segments() < 10);
+        assert_eq!(encoded.header.original_len, data.len() as u64);
+
+        let header = TapeHeader::from_bytes(&encoded.header.to_bytes());
+        let payload = &encoded.data()[..header.data_len as usize];
+        assert_eq!(header.compression().unwrap().decompress(payload).unwrap(), data);
+    }
+
+    #[test]
+    fn empty_file_takes_one_zero_segment() {
+        let encoded = encode(&Pubkey::new_unique(), &[], Compression::None).unwrap();
+        assert_eq!(encoded.segments, vec![[0; SEGMENT_SIZE]]);
+        assert_eq!(encoded.header.data_len, 0);
+        assert_eq!(encoded.finalization_rent(), min_finalization_rent(1));
+    }
+}
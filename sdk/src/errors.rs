@@ -0,0 +1,29 @@
+use solana_instruction::error::InstructionError;
+
+/// Custom error codes returned by the tape program (`ProgramError::Custom`).
+pub use tape_api::error::TapeError;
+
+/// Extract a tape error from a failed instruction, if it is one.
+pub fn tape_error(err: &InstructionError) -> Option<TapeError> {
+    match err {
+        InstructionError::Custom(code) => TapeError::from_code(*code),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_custom_codes() {
+        let err = InstructionError::Custom(0x01);
+        assert_eq!(tape_error(&err), Some(TapeError::InvalidInstructionData));
+        assert_eq!(
+            tape_error(&InstructionError::Custom(0x19)),
+            Some(TapeError::SegmentOutOfWindow)
+        );
+        assert_eq!(tape_error(&InstructionError::Custom(0x04)), None);
+        assert_eq!(tape_error(&InstructionError::InvalidSeeds), None);
+    }
+}
@@ -0,0 +1,512 @@
+//! Instruction builders.
+//!
+//! Account order and signer/writable flags follow the processors in
+//! `program/src/instruction`; instruction data is the one-byte
+//! discriminator followed by the `tape_api::instruction` ix-data struct.
+
+use bytemuck::Pod;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::consts::*;
+use crate::pda::*;
+use crate::types::*;
+use crate::TAPE_PROGRAM_ID;
+
+pub use tape_api::instruction::*;
+
+fn ix_data<T: Pod>(discriminator: TapeInstruction, args: &T) -> Vec<u8> {
+    let bytes = bytemuck::bytes_of(args);
+    let mut data = Vec::with_capacity(1 + bytes.len());
+    data.push(discriminator as u8);
+    data.extend_from_slice(bytes);
+    data
+}
+
+fn instruction(accounts: Vec<AccountMeta>, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: TAPE_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// Route the instruction's events through the self-CPI channel instead of
+/// the program logs by appending the `[event_authority, program]` accounts.
+pub fn with_event_cpi(mut ix: Instruction) -> Instruction {
+    let (event_authority, _bump) = event_authority_pda();
+    ix.accounts.push(AccountMeta::new_readonly(event_authority, false));
+    ix.accounts.push(AccountMeta::new_readonly(TAPE_PROGRAM_ID, false));
+    ix
+}
+
+pub fn build_initialize_ix(signer: Pubkey) -> Instruction {
+    let (tape, _bump) = tape_pda(signer, &to_name("genesis"));
+    let (writer, _bump) = writer_pda(tape);
+    let (mint, _bump) = mint_pda();
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(archive_pda().0, false),
+            AccountMeta::new(epoch_pda().0, false),
+            AccountMeta::new(block_pda().0, false),
+            AccountMeta::new(metadata_pda(mint).0, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new(treasury_ata().0, false),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+            AccountMeta::new_readonly(TAPE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(MPL_TOKEN_METADATA_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(SLOT_HASHES_SYSVAR_ID, false),
+        ],
+        vec![TapeInstruction::Initialize as u8],
+    )
+}
+
+pub fn build_airdrop_ix(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(mint_pda().0, false),
+            AccountMeta::new(treasury_pda().0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        ix_data(TapeInstruction::Airdrop, &AirdropIxData { amount }),
+    )
+}
+
+/// `subtree_depth` is how many levels of the segment tree the tape account
+/// caches (0..=`MAX_SUBTREE_DEPTH`); deeper caches cost more rent but allow
+/// shorter proofs.
+pub fn build_create_tape_ix(
+    signer: Pubkey,
+    name: [u8; NAME_LEN],
+    subtree_depth: u64,
+) -> Instruction {
+    let (tape, _bump) = tape_pda(signer, &name);
+    let (writer, _bump) = writer_pda(tape);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        ],
+        ix_data(TapeInstruction::TapeCreate, &CreateTapeIxData { name, subtree_depth }),
+    )
+}
+
+/// `data` is split into `SEGMENT_SIZE` segments by the program; the last one
+/// is zero-padded.
+pub fn build_write_ix(signer: Pubkey, tape: Pubkey, data: &[u8]) -> Instruction {
+    let (writer, _bump) = writer_pda(tape);
+
+    let mut ix_data = Vec::with_capacity(1 + data.len());
+    ix_data.push(TapeInstruction::TapeWrite as u8);
+    ix_data.extend_from_slice(data);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+        ],
+        ix_data,
+    )
+}
+
+/// Write `data` starting at `segment_number`. Unlike `build_write_ix`, these may
+/// land in any order, as long as every segment is within `WRITE_WINDOW` of
+/// the current end of the tape. Finalize fails while any gap remains.
+pub fn build_write_at_ix(
+    signer: Pubkey,
+    tape: Pubkey,
+    segment_number: u64,
+    data: &[u8],
+) -> Instruction {
+    let (writer, _bump) = writer_pda(tape);
+
+    let mut ix_data = ix_data(TapeInstruction::TapeWriteAt, &WriteTapeAtIxData { segment_number });
+    ix_data.extend_from_slice(data);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+        ],
+        ix_data,
+    )
+}
+
+/// `proof` is leaf side first. It may be shorter than `SEGMENT_PROOF_LEN`
+/// when the tape caches the rest (see `proof::short_proof`).
+pub fn build_update_ix(
+    signer: Pubkey,
+    tape: Pubkey,
+    segment_number: u64,
+    old_data: [u8; SEGMENT_SIZE],
+    new_data: [u8; SEGMENT_SIZE],
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let (writer, _bump) = writer_pda(tape);
+
+    let mut data = ix_data(
+        TapeInstruction::TapeUpdate,
+        &UpdateTapeIxData {
+            segment_number,
+            old_data,
+            new_data,
+        },
+    );
+    data.extend_from_slice(bytemuck::cast_slice(proof));
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+        ],
+        data,
+    )
+}
+
+/// Update up to `MAX_BATCH_UPDATES` segments with one multiproof.
+/// `updates` must be sorted by segment number; build `proof` with
+/// `proof::build_multiproof` over the same segment numbers.
+pub fn build_update_batch_ix(
+    signer: Pubkey,
+    tape: Pubkey,
+    updates: &[SegmentUpdate],
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let (writer, _bump) = writer_pda(tape);
+
+    let mut data = ix_data(
+        TapeInstruction::TapeUpdateBatch,
+        &UpdateTapeBatchIxData {
+            count: updates.len() as u64,
+        },
+    );
+    data.extend_from_slice(bytemuck::cast_slice(updates));
+    data.extend_from_slice(bytemuck::cast_slice(proof));
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+        ],
+        data,
+    )
+}
+
+pub fn build_finalize_ix(signer: Pubkey, tape: Pubkey) -> Instruction {
+    let (writer, _bump) = writer_pda(tape);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+            AccountMeta::new(archive_pda().0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        vec![TapeInstruction::TapeFinalize as u8],
+    )
+}
+
+pub fn build_set_header_ix(signer: Pubkey, tape: Pubkey, header: [u8; HEADER_SIZE]) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(tape, false),
+        ],
+        ix_data(TapeInstruction::TapeSetHeader, &SetTapeHeaderIxData { header }),
+    )
+}
+
+/// Pay `amount` TAPE from the signer's token account `ata` into the treasury
+/// on behalf of `tape`.
+pub fn build_subsidize_ix(signer: Pubkey, ata: Pubkey, tape: Pubkey, amount: u64) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(ata, false),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(treasury_ata().0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        ix_data(TapeInstruction::TapeSubsidize, &SubsidizeTapeIxData { amount }),
+    )
+}
+
+pub fn build_register_ix(signer: Pubkey, name: [u8; NAME_LEN]) -> Instruction {
+    let (miner, _bump) = miner_pda(signer, name);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(SLOT_HASHES_SYSVAR_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        ix_data(TapeInstruction::MinerRegister, &RegisterMinerIxData { name }),
+    )
+}
+
+pub fn build_unregister_ix(signer: Pubkey, miner: Pubkey) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        vec![TapeInstruction::MinerUnregister as u8],
+    )
+}
+
+pub fn build_mine_ix(
+    signer: Pubkey,
+    miner: Pubkey,
+    tape: Pubkey,
+    pow: PoW,
+    poa: PoA,
+) -> Instruction {
+    build_mine_short_ix(signer, miner, tape, pow, poa, SEGMENT_PROOF_LEN)
+}
+
+/// Like `build_mine_ix`, but only sends the first `path_len` hashes of
+/// `poa.path`; the program fills in the rest from the tape's cached subtree.
+/// Panics if `path_len` is larger than `SEGMENT_PROOF_LEN`.
+pub fn build_mine_short_ix(
+    signer: Pubkey,
+    miner: Pubkey,
+    tape: Pubkey,
+    pow: PoW,
+    poa: PoA,
+    path_len: usize,
+) -> Instruction {
+    assert!(
+        path_len <= SEGMENT_PROOF_LEN,
+        "path too long ({path_len} > {SEGMENT_PROOF_LEN})"
+    );
+
+    let mut data = ix_data(TapeInstruction::MinerMine, &MineIxData { pow, poa });
+    data.truncate(data.len() - (SEGMENT_PROOF_LEN - path_len) * 32);
+
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(epoch_pda().0, false),
+            AccountMeta::new(block_pda().0, false),
+            AccountMeta::new(miner, false),
+            AccountMeta::new(tape, false),
+            AccountMeta::new_readonly(archive_pda().0, false),
+            AccountMeta::new_readonly(SLOT_HASHES_SYSVAR_ID, false),
+        ],
+        data,
+    )
+}
+
+pub fn build_claim_ix(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    miner: Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(miner, false),
+            AccountMeta::new_readonly(treasury_pda().0, false),
+            AccountMeta::new(treasury_ata().0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        ix_data(TapeInstruction::MinerClaim, &ClaimIxData { amount }),
+    )
+}
+
+pub fn build_create_spool_ix(signer: Pubkey, miner: Pubkey, number: u64) -> Instruction {
+    let (spool, _bump) = spool_pda(miner, number);
+
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(spool, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        ],
+        ix_data(TapeInstruction::SpoolCreate, &CreateSpoolIxData { number }),
+    )
+}
+
+pub fn build_destroy_spool_ix(signer: Pubkey, spool: Pubkey) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(spool, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        vec![TapeInstruction::SpoolDestroy as u8],
+    )
+}
+
+pub fn build_pack_ix(signer: Pubkey, spool: Pubkey, tape: Pubkey, value: [u8; 32]) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(spool, false),
+            AccountMeta::new_readonly(tape, false),
+        ],
+        ix_data(TapeInstruction::SpoolPack, &PackSpoolIxData { value }),
+    )
+}
+
+pub fn build_unpack_ix(
+    signer: Pubkey,
+    spool: Pubkey,
+    index: u64,
+    proof: [[u8; 32]; TAPE_PROOF_LEN],
+    value: [u8; 32],
+) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(spool, false),
+        ],
+        ix_data(
+            TapeInstruction::SpoolUnpack,
+            &UnpackSpoolIxData {
+                index,
+                proof,
+                value,
+            },
+        ),
+    )
+}
+
+pub fn build_commit_ix(
+    signer: Pubkey,
+    miner: Pubkey,
+    spool: Pubkey,
+    index: u64,
+    proof: [[u8; 32]; TAPE_PROOF_LEN],
+    value: [u8; 32],
+) -> Instruction {
+    instruction(
+        vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(miner, false),
+            AccountMeta::new(spool, false),
+        ],
+        ix_data(
+            TapeInstruction::SpoolCommit,
+            &CommitSpoolIxData {
+                index,
+                proof,
+                value,
+            },
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn ix_data_sizes_match_program() {
+        assert_eq!(core::mem::size_of::<UpdateTapeIxData>(), 8 + 2 * SEGMENT_SIZE);
+        assert_eq!(core::mem::size_of::<MineIxData>(), 24 + 8 + 16 + 128 + 32 * SEGMENT_PROOF_LEN);
+        assert_eq!(core::mem::size_of::<UnpackSpoolIxData>(), 8 + 32 * TAPE_PROOF_LEN + 32);
+    }
+
+    #[test]
+    fn write_carries_raw_segments() {
+        let signer = Pubkey::new_unique();
+        let tape = Pubkey::new_unique();
+        let ix = build_write_ix(signer, tape, &[7u8; SEGMENT_SIZE + 1]);
+
+        assert_eq!(ix.data[0], TapeInstruction::TapeWrite as u8);
+        assert_eq!(ix.data.len(), 1 + SEGMENT_SIZE + 1);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[1].pubkey, tape);
+    }
+
+    #[test]
+    fn short_proofs_shrink_ix_data() {
+        let signer = Pubkey::new_unique();
+        let tape = Pubkey::new_unique();
+        let proof = [[3u8; 32]; SEGMENT_PROOF_LEN];
+
+        let full = build_update_ix(signer, tape, 0, [0; SEGMENT_SIZE], [1; SEGMENT_SIZE], &proof);
+        let short =
+            build_update_ix(signer, tape, 0, [0; SEGMENT_SIZE], [1; SEGMENT_SIZE], &proof[..10]);
+        assert_eq!(full.data.len() - short.data.len(), 8 * 32);
+
+        let poa = PoA::zeroed();
+        let pow = PoW::zeroed();
+        let full = build_mine_ix(signer, Pubkey::new_unique(), tape, pow, poa);
+        let short = build_mine_short_ix(signer, Pubkey::new_unique(), tape, pow, poa, 7);
+        assert_eq!(full.data.len(), 1 + core::mem::size_of::<MineIxData>());
+        assert_eq!(full.data.len() - short.data.len(), 11 * 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "path too long")]
+    fn mine_path_longer_than_proof_panics() {
+        let key = Pubkey::new_unique();
+        build_mine_short_ix(key, key, key, PoW::zeroed(), PoA::zeroed(), SEGMENT_PROOF_LEN + 1);
+    }
+
+    #[test]
+    fn batch_update_layout() {
+        let update = SegmentUpdate {
+            segment_number: 3,
+            old_data: [1; SEGMENT_SIZE],
+            new_data: [2; SEGMENT_SIZE],
+        };
+        let ix = build_update_batch_ix(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            &[update, update],
+            &[[9; 32]; 5],
+        );
+
+        assert_eq!(ix.data[0], TapeInstruction::TapeUpdateBatch as u8);
+        assert_eq!(ix.data[1..9], 2u64.to_le_bytes());
+        assert_eq!(ix.data.len(), 1 + 8 + 2 * (8 + 2 * SEGMENT_SIZE) + 5 * 32);
+    }
+
+    #[test]
+    fn write_at_carries_segment_number() {
+        let (signer, tape) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = build_write_at_ix(signer, tape, 300, &[7u8; SEGMENT_SIZE]);
+
+        assert_eq!(ix.data[0], TapeInstruction::TapeWriteAt as u8);
+        assert_eq!(ix.data[1..9], 300u64.to_le_bytes());
+        assert_eq!(ix.data.len(), 1 + 8 + SEGMENT_SIZE);
+    }
+
+    #[test]
+    fn event_cpi_appends_accounts() {
+        let ix = with_event_cpi(build_write_ix(Pubkey::new_unique(), Pubkey::new_unique(), &[1]));
+        assert_eq!(ix.accounts.len(), 5);
+        assert_eq!(ix.accounts[3].pubkey, event_authority_pda().0);
+        assert_eq!(ix.accounts[4].pubkey, TAPE_PROGRAM_ID);
+    }
+}
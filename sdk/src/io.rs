@@ -0,0 +1,409 @@
+//! `std::io` adapters for tapes.
+//!
+//! `TapeWriter` takes a byte stream and turns it into `TapeWrite`
+//! instructions as full segments become available, handing each transaction
+//! to a `TransactionSink`. `TapeReader` serves reads and seeks over a tape's
+//! payload from a `SegmentStore`, one segment at a time. Both work on the
+//! payload as stored; compression, if any, is the caller's business.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use tape_api::utils::compute_leaf;
+
+use crate::accounts::Tape;
+use crate::consts::*;
+use crate::encoder::{Compression, TapeHeader, TAPE_HEADER_VERSION};
+use crate::instructions::build_write_ix;
+use crate::plan::segments_per_write;
+use crate::reader::{read_segments, LedgerSource, ReadError};
+use crate::types::SegmentTree;
+
+/// Where `TapeWriter` sends its transactions.
+pub trait TransactionSink {
+    /// Send one transaction. It must have landed (or be sure to land, in
+    /// order) by the time this returns, since the next write depends on it.
+    fn send(&mut self, instructions: Vec<Instruction>) -> io::Result<()>;
+}
+
+/// Collects the transactions instead of sending them.
+impl TransactionSink for Vec<Vec<Instruction>> {
+    fn send(&mut self, instructions: Vec<Instruction>) -> io::Result<()> {
+        self.push(instructions);
+        Ok(())
+    }
+}
+
+/// Streams bytes onto a tape that has been created but not yet written.
+///
+/// Writes go out only in whole segments, so the last partial segment is held
+/// back until `finish`. Dropping the writer without calling `finish` loses
+/// it.
+pub struct TapeWriter<S: TransactionSink> {
+    sink: S,
+    signer: Pubkey,
+    tape: Pubkey,
+    /// Bytes not yet sent, always less than a full write.
+    buffer: Vec<u8>,
+    write_len: usize,
+    total_segments: u64,
+    data_len: u64,
+    tree: SegmentTree,
+}
+
+/// What `TapeWriter::finish` left on the tape.
+pub struct FinishedTape<S> {
+    pub sink: S,
+    pub total_segments: u64,
+    /// Bytes written, before the last segment's padding.
+    pub data_len: u64,
+    /// The tape's Merkle root once every write has landed.
+    pub root: [u8; 32],
+}
+
+impl<S> FinishedTape<S> {
+    /// Header describing the written bytes, for `build_set_header_ix`.
+    pub fn header(&self) -> TapeHeader {
+        TapeHeader {
+            version: TAPE_HEADER_VERSION,
+            compression: Compression::None as u64,
+            data_len: self.data_len,
+            original_len: self.data_len,
+            reserved: [0; HEADER_SIZE - 32],
+        }
+    }
+}
+
+impl<S: TransactionSink> TapeWriter<S> {
+    pub fn new(sink: S, signer: Pubkey, tape: Pubkey) -> Self {
+        Self {
+            sink,
+            signer,
+            tape,
+            buffer: Vec::new(),
+            write_len: segments_per_write(signer, tape) * SEGMENT_SIZE,
+            total_segments: 0,
+            data_len: 0,
+            tree: SegmentTree::new(&[tape.as_ref()]),
+        }
+    }
+
+    pub fn total_segments(&self) -> u64 {
+        self.total_segments
+    }
+
+    /// Send everything buffered, padding the last segment, and return the
+    /// sink along with what was written.
+    pub fn finish(mut self) -> io::Result<FinishedTape<S>> {
+        let rest = core::mem::take(&mut self.buffer);
+        if !rest.is_empty() {
+            self.send(&rest)?;
+        }
+
+        Ok(FinishedTape {
+            sink: self.sink,
+            total_segments: self.total_segments,
+            data_len: self.data_len,
+            root: self.tree.get_root().to_bytes(),
+        })
+    }
+
+    fn send(&mut self, data: &[u8]) -> io::Result<()> {
+        let count = data.len().div_ceil(SEGMENT_SIZE) as u64;
+        if self.total_segments + count > MAX_SEGMENTS_PER_TAPE as u64 {
+            return Err(io::Error::other("tape is full"));
+        }
+
+        self.sink.send(vec![build_write_ix(self.signer, self.tape, data)])?;
+
+        for chunk in data.chunks(SEGMENT_SIZE) {
+            let mut segment = [0u8; SEGMENT_SIZE];
+            segment[..chunk.len()].copy_from_slice(chunk);
+            self.tree
+                .try_add_leaf(compute_leaf(self.total_segments, &segment))
+                .map_err(|_| io::Error::other("tape is full"))?;
+            self.total_segments += 1;
+        }
+        self.data_len += data.len() as u64;
+
+        Ok(())
+    }
+}
+
+impl<S: TransactionSink> Write for TapeWriter<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let take = buf.len().min(self.write_len - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..take]);
+
+        if self.buffer.len() == self.write_len {
+            let full = core::mem::take(&mut self.buffer);
+            self.send(&full)?;
+        }
+        Ok(take)
+    }
+
+    /// Send the buffered whole segments; a trailing partial segment stays
+    /// buffered, as padding it now would put zeros in the middle of the data.
+    fn flush(&mut self) -> io::Result<()> {
+        let whole = self.buffer.len() / SEGMENT_SIZE * SEGMENT_SIZE;
+        if whole > 0 {
+            let rest = self.buffer.split_off(whole);
+            let full = core::mem::replace(&mut self.buffer, rest);
+            self.send(&full)?;
+        }
+        Ok(())
+    }
+}
+
+/// Random access to a tape's segments.
+pub trait SegmentStore {
+    fn segment(&mut self, number: u64) -> io::Result<[u8; SEGMENT_SIZE]>;
+}
+
+impl SegmentStore for Vec<[u8; SEGMENT_SIZE]> {
+    fn segment(&mut self, number: u64) -> io::Result<[u8; SEGMENT_SIZE]> {
+        self.get(number as usize)
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such segment"))
+    }
+}
+
+/// Reads the first `len` bytes stored on a tape.
+pub struct TapeReader<S: SegmentStore> {
+    store: S,
+    len: u64,
+    pos: u64,
+    current: Option<(u64, [u8; SEGMENT_SIZE])>,
+}
+
+impl<S: SegmentStore> TapeReader<S> {
+    pub fn new(store: S, len: u64) -> Self {
+        Self {
+            store,
+            len,
+            pos: 0,
+            current: None,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+}
+
+impl TapeReader<Vec<[u8; SEGMENT_SIZE]>> {
+    /// Rebuild the tape at `address` from the ledger (see
+    /// `reader::read_segments`). For tapes with an encoder header the reader
+    /// covers the stored payload, still compressed if the header says so;
+    /// otherwise it covers every segment.
+    pub fn from_ledger<L: LedgerSource>(
+        ledger: &L,
+        address: &Pubkey,
+        tape: &Tape,
+    ) -> Result<Self, ReadError<L::Error>> {
+        let segments = read_segments(ledger, address, tape)?;
+        let stored = (segments.len() * SEGMENT_SIZE) as u64;
+
+        let header = TapeHeader::from_bytes(&tape.header);
+        let len = if header.version == TAPE_HEADER_VERSION && header.data_len <= stored {
+            header.data_len
+        } else {
+            stored
+        };
+
+        Ok(Self::new(segments, len))
+    }
+}
+
+impl<S: SegmentStore> Read for TapeReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.len || buf.is_empty() {
+            return Ok(0);
+        }
+
+        let number = self.pos / SEGMENT_SIZE as u64;
+        let segment = match self.current {
+            Some((n, segment)) if n == number => segment,
+            _ => {
+                let segment = self.store.segment(number)?;
+                self.current = Some((number, segment));
+                segment
+            }
+        };
+
+        let offset = (self.pos % SEGMENT_SIZE as u64) as usize;
+        let n = buf
+            .len()
+            .min(SEGMENT_SIZE - offset)
+            .min((self.len - self.pos) as usize);
+        buf[..n].copy_from_slice(&segment[offset..offset + n]);
+        self.pos += n as u64;
+
+        Ok(n)
+    }
+}
+
+impl<S: SegmentStore> Seek for TapeReader<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+
+        self.pos = target.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "seek before the start of the tape")
+        })?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::encode;
+    use crate::instructions::TapeInstruction;
+    use crate::reader::{LedgerTransaction, MemoryLedger};
+    use bytemuck::Zeroable;
+    use tape_api::event::{EventType, WriteEvent};
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    fn written(data: &[u8], chunk: usize) -> FinishedTape<Vec<Vec<Instruction>>> {
+        let mut writer = TapeWriter::new(Vec::new(), Pubkey::new_unique(), Pubkey::new_unique());
+        for piece in data.chunks(chunk) {
+            writer.write_all(piece).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn writer_matches_encoder() {
+        let signer = Pubkey::new_unique();
+        let tape = Pubkey::new_unique();
+        let data = sample(5000);
+
+        let mut writer = TapeWriter::new(Vec::new(), signer, tape);
+        for piece in data.chunks(77) {
+            writer.write_all(piece).unwrap();
+        }
+        let finished = writer.finish().unwrap();
+        let encoded = encode(&tape, &data, Compression::None).unwrap();
+
+        assert_eq!(finished.root, encoded.root);
+        assert_eq!(finished.total_segments, encoded.total_segments());
+        assert_eq!(finished.header(), encoded.header);
+
+        let sent: Vec<u8> = finished
+            .sink
+            .iter()
+            .flatten()
+            .inspect(|ix| assert_eq!(ix.data[0], TapeInstruction::TapeWrite as u8))
+            .flat_map(|ix| ix.data[1..].iter().copied())
+            .collect();
+        assert_eq!(sent, data);
+    }
+
+    #[test]
+    fn flush_sends_only_whole_segments() {
+        let mut writer = TapeWriter::new(Vec::new(), Pubkey::new_unique(), Pubkey::new_unique());
+        writer.write_all(&[1; SEGMENT_SIZE + 10]).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.total_segments(), 1);
+
+        let finished = writer.finish().unwrap();
+        assert_eq!(finished.total_segments, 2);
+        assert_eq!(finished.sink.len(), 2);
+        assert_eq!(finished.sink[1][0].data.len(), 1 + 10);
+    }
+
+    #[test]
+    fn reader_seeks_across_segments() {
+        let data = sample(3 * SEGMENT_SIZE + 5);
+        let segments = encode(&Pubkey::new_unique(), &data, Compression::None)
+            .unwrap()
+            .segments;
+        let mut reader = TapeReader::new(segments, data.len() as u64);
+
+        let mut all = Vec::new();
+        reader.read_to_end(&mut all).unwrap();
+        assert_eq!(all, data);
+
+        let mut buf = [0u8; 20];
+        reader.seek(SeekFrom::Start(SEGMENT_SIZE as u64 - 10)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data[SEGMENT_SIZE - 10..SEGMENT_SIZE + 10]);
+
+        assert_eq!(reader.seek(SeekFrom::End(-3)).unwrap(), data.len() as u64 - 3);
+        let mut tail = Vec::new();
+        reader.read_to_end(&mut tail).unwrap();
+        assert_eq!(tail, data[data.len() - 3..]);
+
+        assert!(reader.seek(SeekFrom::Current(-(data.len() as i64) - 1)).is_err());
+    }
+
+    #[test]
+    fn writer_output_reads_back_from_the_ledger() {
+        let signer = Pubkey::new_unique();
+        let address = Pubkey::new_unique();
+        let data = sample(4000);
+
+        let mut writer = TapeWriter::new(Vec::new(), signer, address);
+        writer.write_all(&data).unwrap();
+        let finished = writer.finish().unwrap();
+
+        let mut ledger = MemoryLedger::new();
+        let mut first = 0;
+        for (slot, instructions) in finished.sink.iter().enumerate() {
+            let added = (instructions[0].data.len() - 1).div_ceil(SEGMENT_SIZE) as u64;
+            let event = WriteEvent {
+                num_added: added,
+                num_total: first + added,
+                prev_slot: slot as u64,
+                address: address.to_bytes(),
+                segment_number: first,
+            };
+            let mut bytes = vec![EventType::WriteEvent as u8];
+            bytes.extend_from_slice(bytemuck::bytes_of(&event));
+
+            ledger.push(
+                slot as u64 + 1,
+                LedgerTransaction {
+                    instructions: instructions.clone(),
+                    events: vec![bytes],
+                },
+            );
+            first += added;
+        }
+
+        let mut tape = Tape::zeroed();
+        tape.merkle_root = finished.root;
+        tape.header = finished.header().to_bytes();
+        tape.tail_slot = finished.sink.len() as u64;
+        tape.total_segments = finished.total_segments;
+
+        let mut reader = TapeReader::from_ledger(&ledger, &address, &tape).unwrap();
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn small_writes_are_batched() {
+        let data = sample(10 * SEGMENT_SIZE);
+        let finished = written(&data, 1);
+        assert!(finished.sink.len() < 10);
+        assert_eq!(finished.total_segments, 10);
+    }
+}
@@ -0,0 +1,33 @@
+//! Host-side SDK for the tape program.
+//!
+//! `instructions` has a `build_*_ix` function per instruction, returning a
+//! `solana_instruction::Instruction` with the account order and
+//! signer/writable flags its processor expects.
+//!
+//! Unlike `client/ts`, this crate is written by hand: the program stores its
+//! accounts as zero-copy `#[repr(C)]` structs (including `brine_tree` Merkle
+//! trees) and takes raw `#[repr(C)]` instruction data, neither of which the
+//! borsh-based codama renderer can describe. Account layouts, instruction
+//! data, constants and error codes are re-exported from `tape-api`, the same
+//! definitions the program is built on.
+
+pub mod accounts;
+pub mod consts;
+pub mod diff;
+pub mod encoder;
+pub mod errors;
+pub mod instructions;
+pub mod io;
+pub mod pda;
+pub mod plan;
+pub mod proof;
+pub mod prover;
+pub mod reader;
+pub mod resume;
+pub mod types;
+
+pub use solana_instruction::{AccountMeta, Instruction};
+pub use solana_pubkey::{pubkey, Pubkey};
+
+/// `tape` program ID.
+pub const TAPE_PROGRAM_ID: Pubkey = Pubkey::new_from_array(tape_api::ID);
@@ -0,0 +1,68 @@
+use solana_pubkey::Pubkey;
+
+use crate::consts::*;
+use crate::TAPE_PROGRAM_ID;
+
+pub fn archive_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ARCHIVE], &TAPE_PROGRAM_ID)
+}
+
+pub fn epoch_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EPOCH], &TAPE_PROGRAM_ID)
+}
+
+pub fn block_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BLOCK], &TAPE_PROGRAM_ID)
+}
+
+pub fn treasury_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY], &TAPE_PROGRAM_ID)
+}
+
+pub fn mint_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT, MINT_SEED], &TAPE_PROGRAM_ID)
+}
+
+pub fn event_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY], &TAPE_PROGRAM_ID)
+}
+
+pub fn metadata_pda(mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[METADATA, MPL_TOKEN_METADATA_ID.as_ref(), mint.as_ref()],
+        &MPL_TOKEN_METADATA_ID,
+    )
+}
+
+/// Associated token account of `owner` for the TAPE mint.
+pub fn tape_ata(owner: Pubkey) -> (Pubkey, u8) {
+    let (mint, _bump) = mint_pda();
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
+
+pub fn treasury_ata() -> (Pubkey, u8) {
+    let (treasury, _bump) = treasury_pda();
+    tape_ata(treasury)
+}
+
+pub fn tape_pda(authority: Pubkey, name: &[u8; NAME_LEN]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TAPE, authority.as_ref(), name.as_ref()], &TAPE_PROGRAM_ID)
+}
+
+pub fn writer_pda(tape: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WRITER, tape.as_ref()], &TAPE_PROGRAM_ID)
+}
+
+pub fn miner_pda(authority: Pubkey, name: [u8; NAME_LEN]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINER, authority.as_ref(), name.as_ref()], &TAPE_PROGRAM_ID)
+}
+
+pub fn spool_pda(miner: Pubkey, number: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SPOOL, miner.as_ref(), number.to_le_bytes().as_ref()],
+        &TAPE_PROGRAM_ID,
+    )
+}
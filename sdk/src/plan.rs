@@ -0,0 +1,255 @@
+//! Planning the transactions that put an `EncodedTape` on chain.
+//!
+//! The plan is TapeCreate, TapeSetHeader, as many TapeWrites as the data
+//! needs, TapeSubsidize for the finalization rent and TapeFinalize, packed
+//! greedily into as few legacy transactions as fit in a packet. Writes are
+//! sized to fill a transaction on their own.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use crate::consts::*;
+use crate::encoder::EncodedTape;
+use crate::instructions::*;
+use crate::pda::*;
+
+/// Largest serialized transaction the network accepts.
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Everything needed to write one file to a new tape.
+#[derive(Clone, Debug)]
+pub struct TapePlan {
+    pub tape: Pubkey,
+    /// Merkle root the tape will have once every write has landed.
+    pub root: [u8; 32],
+    pub total_segments: u64,
+    /// TAPE paid into the tape before finalizing (`min_finalization_rent`).
+    pub rent: u64,
+    /// Transactions in the order they must land.
+    pub transactions: Vec<Vec<Instruction>>,
+}
+
+/// Plan writing `encoded` to a new tape `name` owned by `signer`, paying the
+/// rent from the signer's token account `ata`.
+pub fn plan_tape(
+    signer: Pubkey,
+    ata: Pubkey,
+    name: [u8; NAME_LEN],
+    subtree_depth: u64,
+    encoded: &EncodedTape,
+) -> TapePlan {
+    let (tape, _bump) = tape_pda(signer, &name);
+    let rent = encoded.finalization_rent();
+
+    let mut ixs = vec![
+        build_create_tape_ix(signer, name, subtree_depth),
+        build_set_header_ix(signer, tape, encoded.header.to_bytes()),
+    ];
+
+    let per_write = segments_per_write(signer, tape);
+    for chunk in encoded.data().chunks(per_write * SEGMENT_SIZE) {
+        ixs.push(build_write_ix(signer, tape, chunk));
+    }
+
+    if rent > 0 {
+        ixs.push(build_subsidize_ix(signer, ata, tape, rent));
+    }
+    ixs.push(build_finalize_ix(signer, tape));
+
+    TapePlan {
+        tape,
+        root: encoded.root,
+        total_segments: encoded.total_segments(),
+        rent,
+        transactions: pack_transactions(&signer, ixs),
+    }
+}
+
+/// Most segments a single `TapeWrite` from `signer` can carry and still fit
+/// in a transaction by itself.
+pub fn segments_per_write(signer: Pubkey, tape: Pubkey) -> usize {
+    let empty = build_write_ix(signer, tape, &[]);
+    let mut count = (PACKET_DATA_SIZE - transaction_size(&signer, &[empty])) / SEGMENT_SIZE;
+
+    // (the data length prefix can grow by a byte)
+    while count > 1 {
+        let ix = build_write_ix(signer, tape, &vec![0; count * SEGMENT_SIZE]);
+        if transaction_size(&signer, &[ix]) <= PACKET_DATA_SIZE {
+            break;
+        }
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+/// Group `ixs`, in order, into as few transactions as fit in a packet.
+pub fn pack_transactions(payer: &Pubkey, ixs: Vec<Instruction>) -> Vec<Vec<Instruction>> {
+    let mut transactions: Vec<Vec<Instruction>> = Vec::new();
+    let mut current: Vec<Instruction> = Vec::new();
+
+    for ix in ixs {
+        current.push(ix);
+        if current.len() > 1 && transaction_size(payer, &current) > PACKET_DATA_SIZE {
+            let ix = current.pop().expect("just pushed");
+            transactions.push(core::mem::take(&mut current));
+            current.push(ix);
+        }
+    }
+
+    if !current.is_empty() {
+        transactions.push(current);
+    }
+    transactions
+}
+
+/// Serialized size of a legacy transaction carrying `ixs`, paid by `payer`.
+pub fn transaction_size(payer: &Pubkey, ixs: &[Instruction]) -> usize {
+    // (key, is_signer)
+    let mut keys = vec![(*payer, true)];
+
+    for ix in ixs {
+        for meta in &ix.accounts {
+            match keys.iter_mut().find(|(key, _)| *key == meta.pubkey) {
+                Some((_, is_signer)) => *is_signer |= meta.is_signer,
+                None => keys.push((meta.pubkey, meta.is_signer)),
+            }
+        }
+        if !keys.iter().any(|(key, _)| *key == ix.program_id) {
+            keys.push((ix.program_id, false));
+        }
+    }
+
+    let signers = keys.iter().filter(|(_, is_signer)| *is_signer).count();
+
+    let instructions: usize = ixs
+        .iter()
+        .map(|ix| {
+            1 + short_vec_len(ix.accounts.len())
+                + ix.accounts.len()
+                + short_vec_len(ix.data.len())
+                + ix.data.len()
+        })
+        .sum();
+
+    short_vec_len(signers)
+        + 64 * signers
+        + 3 // message header
+        + short_vec_len(keys.len())
+        + 32 * keys.len()
+        + 32 // recent blockhash
+        + short_vec_len(ixs.len())
+        + instructions
+}
+
+/// Bytes of the compact-u16 length prefix for `len`.
+const fn short_vec_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{encode, Compression};
+    use crate::types::to_name;
+    use solana_sdk::message::Message;
+
+    fn serialized_size(payer: &Pubkey, ixs: &[Instruction]) -> usize {
+        let message = Message::new(ixs, Some(payer));
+        let signers = message.header.num_required_signatures as usize;
+        short_vec_len(signers) + 64 * signers + message.serialize().len()
+    }
+
+    #[test]
+    fn size_matches_serialized_transaction() {
+        let signer = Pubkey::new_unique();
+        let tape = Pubkey::new_unique();
+        let ixs = [
+            build_create_tape_ix(signer, to_name("size"), 3),
+            build_set_header_ix(signer, tape, [1; HEADER_SIZE]),
+            build_write_ix(signer, tape, &[2; 300]),
+        ];
+
+        for n in 1..=ixs.len() {
+            assert_eq!(transaction_size(&signer, &ixs[..n]), serialized_size(&signer, &ixs[..n]));
+        }
+    }
+
+    #[test]
+    fn writes_fill_a_packet() {
+        let signer = Pubkey::new_unique();
+        let tape = Pubkey::new_unique();
+        let count = segments_per_write(signer, tape);
+
+        let fits = build_write_ix(signer, tape, &vec![0; count * SEGMENT_SIZE]);
+        let too_big = build_write_ix(signer, tape, &vec![0; (count + 1) * SEGMENT_SIZE]);
+        assert!(transaction_size(&signer, &[fits]) <= PACKET_DATA_SIZE);
+        assert!(transaction_size(&signer, &[too_big]) > PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn plan_writes_every_segment_in_order() {
+        let signer = Pubkey::new_unique();
+        let name = to_name("plan");
+        let (tape, _bump) = tape_pda(signer, &name);
+
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 7) as u8).collect();
+        let encoded = encode(&tape, &data, Compression::None).unwrap();
+        let plan = plan_tape(signer, tape_ata(signer).0, name, 2, &encoded);
+
+        assert_eq!(plan.tape, tape);
+        assert_eq!(plan.root, encoded.root);
+        assert_eq!(plan.rent, encoded.finalization_rent());
+
+        let ixs: Vec<&Instruction> = plan.transactions.iter().flatten().collect();
+        let kinds: Vec<u8> = ixs.iter().map(|ix| ix.data[0]).collect();
+        assert_eq!(kinds[0], TapeInstruction::TapeCreate as u8);
+        assert_eq!(kinds[1], TapeInstruction::TapeSetHeader as u8);
+        assert_eq!(kinds[kinds.len() - 2], TapeInstruction::TapeSubsidize as u8);
+        assert_eq!(kinds[kinds.len() - 1], TapeInstruction::TapeFinalize as u8);
+
+        let written: Vec<u8> = ixs
+            .iter()
+            .filter(|ix| ix.data[0] == TapeInstruction::TapeWrite as u8)
+            .flat_map(|ix| ix.data[1..].iter().copied())
+            .collect();
+        assert_eq!(written, encoded.data());
+
+        for tx in &plan.transactions {
+            assert!(transaction_size(&signer, tx) <= PACKET_DATA_SIZE);
+        }
+
+        // Create and header share the first transaction; every write fills
+        // one of its own.
+        let writes = encoded.segments.len().div_ceil(segments_per_write(signer, tape));
+        assert_eq!(plan.transactions[0].len(), 2);
+        assert!(plan.transactions.len() <= writes + 2);
+    }
+
+    #[test]
+    fn empty_file_is_written_before_finalize() {
+        let signer = Pubkey::new_unique();
+        let name = to_name("empty");
+        let (tape, _bump) = tape_pda(signer, &name);
+
+        let encoded = encode(&tape, &[], Compression::None).unwrap();
+        let plan = plan_tape(signer, tape_ata(signer).0, name, 0, &encoded);
+        assert_eq!(plan.total_segments, 1);
+
+        let kinds: Vec<u8> = plan.transactions.iter().flatten().map(|ix| ix.data[0]).collect();
+        assert_eq!(
+            kinds,
+            [
+                TapeInstruction::TapeCreate as u8,
+                TapeInstruction::TapeSetHeader as u8,
+                TapeInstruction::TapeWrite as u8,
+                TapeInstruction::TapeSubsidize as u8,
+                TapeInstruction::TapeFinalize as u8,
+            ]
+        );
+    }
+}
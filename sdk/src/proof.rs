@@ -0,0 +1,89 @@
+//! Shortening segment proofs against a tape's cached subtree.
+//!
+//! A tape created with a non-zero `subtree_depth` keeps the top levels of its
+//! segment tree on chain (see `decode_tape_with_nodes`), heap ordered and
+//! with all-zero entries for nodes it has not cached. `TapeUpdate` and
+//! `MinerMine` accept a proof that stops wherever the cache can finish it.
+//!
+//! `build_multiproof` produces the deduplicated proof `TapeUpdateBatch`
+//! expects.
+
+use crate::consts::*;
+
+pub use tape_api::multiproof::build_multiproof;
+pub use tape_api::subtree::{subtree_depth, subtree_index};
+
+/// Number of leading hashes of `proof` the program still needs, given the
+/// tape's cached `nodes`. Cached siblings that differ from the proof (a stale
+/// cache) stop the shortening, so the result is always accepted.
+pub fn short_proof_len(
+    nodes: &[[u8; 32]],
+    segment_id: u64,
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+) -> usize {
+    let depth = subtree_depth(nodes) as usize;
+    let mut len = SEGMENT_PROOF_LEN;
+
+    while len > SEGMENT_PROOF_LEN - depth {
+        let i = len - 1;
+        let level = (SEGMENT_TREE_HEIGHT - i) as u64;
+        let cached = nodes[subtree_index(level, segment_id ^ (1 << i))];
+        if cached != proof[i] {
+            break;
+        }
+        len = i;
+    }
+
+    len
+}
+
+/// The shortest prefix of `proof` the program will accept for this tape.
+pub fn short_proof<'a>(
+    nodes: &[[u8; 32]],
+    segment_id: u64,
+    proof: &'a [[u8; 32]; SEGMENT_PROOF_LEN],
+) -> &'a [[u8; 32]] {
+    &proof[..short_proof_len(nodes, segment_id, proof)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::subtree_node_count;
+
+    fn proof() -> [[u8; 32]; SEGMENT_PROOF_LEN] {
+        core::array::from_fn(|i| [i as u8 + 1; 32])
+    }
+
+    #[test]
+    fn no_cache_keeps_full_proof() {
+        assert_eq!(short_proof(&[], 5, &proof()).len(), SEGMENT_PROOF_LEN);
+    }
+
+    #[test]
+    fn cached_siblings_are_dropped() {
+        let proof = proof();
+        let mut nodes = vec![[0; 32]; subtree_node_count(3)];
+        for i in SEGMENT_PROOF_LEN - 3..SEGMENT_PROOF_LEN {
+            let level = (SEGMENT_TREE_HEIGHT - i) as u64;
+            nodes[subtree_index(level, 5 ^ (1 << i))] = proof[i];
+        }
+        assert_eq!(short_proof_len(&nodes, 5, &proof), SEGMENT_PROOF_LEN - 3);
+
+        // A stale node near the root keeps everything below it.
+        nodes[subtree_index(1, 5 ^ (1 << (SEGMENT_PROOF_LEN - 1)))] = [0xFF; 32];
+        assert_eq!(short_proof_len(&nodes, 5, &proof), SEGMENT_PROOF_LEN);
+    }
+
+    #[test]
+    fn multiproof_dedups_shared_hashes() {
+        // Leaves 4 and 5 are siblings; every level above shares a sibling.
+        let proof = build_multiproof(&[4, 5], |i| core::array::from_fn(|l| [(i as u8) << 5 | l as u8; 32]));
+        assert_eq!(proof.len(), SEGMENT_PROOF_LEN - 1);
+        assert_eq!(proof[0], [4 << 5 | 1; 32]);
+
+        // A lone leaf needs its full proof.
+        let proof = build_multiproof(&[9], |_| [[1; 32]; SEGMENT_PROOF_LEN]);
+        assert_eq!(proof.len(), SEGMENT_PROOF_LEN);
+    }
+}
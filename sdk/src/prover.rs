@@ -0,0 +1,421 @@
+//! Proofs for recalled segments and spooled tapes, without asking the chain.
+//!
+//! The program only keeps the frontier of each Merkle tree (`brine_tree`
+//! stores filled subtrees, not nodes), so proofs come from full copies kept
+//! off chain. `FullTree` holds every level of one tree and hashes exactly
+//! like `brine_tree`: sorted pairs, and the seeded empty subtrees on the
+//! right. `ProofBuilder` keeps one per tape and per spool.
+
+use std::collections::HashMap;
+
+use solana_pubkey::Pubkey;
+use tape_api::subtree::hash_pair;
+use tape_api::utils::{compute_leaf, compute_tape_leaf};
+
+use crate::consts::*;
+use crate::types::ProofPath;
+
+/// Every level of a `brine_tree::MerkleTree<HEIGHT>`, so that proofs and
+/// updates cost one path instead of rehashing every leaf.
+#[derive(Clone, Debug)]
+pub struct FullTree<const HEIGHT: usize> {
+    /// `levels[0]` are the leaves; missing right-hand nodes are empty
+    /// subtrees.
+    levels: Vec<Vec<[u8; 32]>>,
+    /// Root of an empty subtree at each level.
+    empty: [[u8; 32]; HEIGHT],
+}
+
+impl<const HEIGHT: usize> FullTree<HEIGHT> {
+    /// An empty tree, seeded like `MerkleTree::<HEIGHT>::new(seeds)`.
+    pub fn new(seeds: &[&[u8]]) -> Self {
+        let tree = brine_tree::MerkleTree::<HEIGHT>::new(seeds);
+
+        // (the siblings of a lone leaf are the empty subtrees)
+        let lone = [compute_leaf(0, &[0; SEGMENT_SIZE])];
+        let siblings = tree.get_merkle_proof(&lone, 0);
+
+        Self {
+            levels: vec![Vec::new(); HEIGHT],
+            empty: core::array::from_fn(|level| siblings[level].to_bytes()),
+        }
+    }
+
+    pub fn from_leaves(seeds: &[&[u8]], leaves: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        let mut tree = Self::new(seeds);
+        tree.levels[0] = leaves.into_iter().collect();
+
+        for level in 1..HEIGHT {
+            let nodes = (0..tree.levels[level - 1].len().div_ceil(2))
+                .map(|i| tree.parent(level - 1, 2 * i))
+                .collect();
+            tree.levels[level] = nodes;
+        }
+        tree
+    }
+
+    /// Rebuild a tree from levels saved with `levels`. `None` if they are
+    /// not shaped like the levels of a tree of `HEIGHT`; the nodes are not
+    /// rehashed (see `is_consistent`).
+    pub fn from_levels(seeds: &[&[u8]], levels: Vec<Vec<[u8; 32]>>) -> Option<Self> {
+        let lens = Self::level_lens(levels.first()?.len());
+        let shaped = levels.len() == HEIGHT
+            && levels.iter().zip(lens).all(|(nodes, len)| nodes.len() == len);
+        if !shaped {
+            return None;
+        }
+
+        let mut tree = Self::new(seeds);
+        tree.levels = levels;
+        Some(tree)
+    }
+
+    /// Every level, leaves first, each half the length of the one below
+    /// (rounded up). Missing right-hand nodes are empty subtrees.
+    pub fn levels(&self) -> &[Vec<[u8; 32]>] {
+        &self.levels
+    }
+
+    /// Nodes stored per level for a tree of `len` leaves.
+    pub fn level_lens(len: usize) -> [usize; HEIGHT] {
+        let mut lens = [len; HEIGHT];
+        for level in 1..HEIGHT {
+            lens[level] = lens[level - 1].div_ceil(2);
+        }
+        lens
+    }
+
+    /// Whether every node above the leaves hashes from the two below it.
+    pub fn is_consistent(&self) -> bool {
+        (1..HEIGHT).all(|level| {
+            self.levels[level]
+                .iter()
+                .enumerate()
+                .all(|(i, node)| *node == self.parent(level - 1, 2 * i))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn leaf(&self, index: usize) -> Option<[u8; 32]> {
+        self.levels[0].get(index).copied()
+    }
+
+    /// The value a removed leaf takes.
+    pub fn empty_leaf(&self) -> [u8; 32] {
+        self.empty[0]
+    }
+
+    /// Root of an empty subtree at `level`, which stands in for missing
+    /// right-hand nodes.
+    pub fn empty_subtree(&self, level: usize) -> [u8; 32] {
+        self.empty[level]
+    }
+
+    fn node(&self, level: usize, index: usize) -> [u8; 32] {
+        self.levels[level]
+            .get(index)
+            .copied()
+            .unwrap_or(self.empty[level])
+    }
+
+    /// Hash of the node above `index` at `level`.
+    fn parent(&self, level: usize, index: usize) -> [u8; 32] {
+        hash_pair(&self.node(level, index), &self.node(level, index ^ 1))
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.parent(HEIGHT - 1, 0)
+    }
+
+    pub fn proof(&self, index: usize) -> [[u8; 32]; HEIGHT] {
+        core::array::from_fn(|level| self.node(level, (index >> level) ^ 1))
+    }
+
+    /// Replace leaf `index` and rehash its path.
+    pub fn set(&mut self, index: usize, leaf: [u8; 32]) {
+        self.levels[0][index] = leaf;
+
+        let mut index = index;
+        for level in 1..HEIGHT {
+            let parent = self.parent(level - 1, index);
+            index >>= 1;
+            match self.levels[level].get_mut(index) {
+                Some(node) => *node = parent,
+                None => self.levels[level].push(parent),
+            }
+        }
+    }
+
+    pub fn push(&mut self, leaf: [u8; 32]) {
+        let index = self.len();
+        self.levels[0].push(leaf);
+        self.set(index, leaf);
+    }
+}
+
+/// A spool's tape tree, with the tape number held at each leaf.
+#[derive(Clone, Debug)]
+struct SpoolTree {
+    tree: FullTree<TAPE_TREE_HEIGHT>,
+    tapes: Vec<Option<u64>>,
+}
+
+/// Full trees for the tapes and spools a miner holds.
+#[derive(Clone, Debug, Default)]
+pub struct ProofBuilder {
+    tapes: HashMap<u64, FullTree<SEGMENT_TREE_HEIGHT>>,
+    spools: HashMap<Pubkey, SpoolTree>,
+}
+
+impl ProofBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold the whole of tape `number`, whose account is `address`.
+    pub fn insert_tape(&mut self, number: u64, address: &Pubkey, segments: &[[u8; SEGMENT_SIZE]]) {
+        let leaves = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| compute_leaf(i as u64, segment).to_bytes());
+        let tree = FullTree::from_leaves(&[address.as_ref()], leaves);
+        self.tapes.insert(number, tree);
+    }
+
+    pub fn remove_tape(&mut self, number: u64) {
+        self.tapes.remove(&number);
+    }
+
+    /// Mirror a `TapeUpdate` of segment `index` on tape `number`.
+    pub fn update_segment(&mut self, number: u64, index: u64, segment: &[u8; SEGMENT_SIZE]) {
+        if let Some(tree) = self.tapes.get_mut(&number) {
+            tree.set(index as usize, compute_leaf(index, segment).to_bytes());
+        }
+    }
+
+    /// Root of tape `number`; matches `Tape.merkle_root`.
+    pub fn tape_root(&self, number: u64) -> Option<[u8; 32]> {
+        Some(self.tapes.get(&number)?.root())
+    }
+
+    /// Full proof for segment `index` of tape `number`, as `MinerMine` and
+    /// `TapeUpdate` expect it.
+    pub fn segment_proof(&self, number: u64, index: u64) -> Option<ProofPath> {
+        let tree = self.tapes.get(&number)?;
+        if index as usize >= tree.len() {
+            return None;
+        }
+        Some(ProofPath::from_array(tree.proof(index as usize)))
+    }
+
+    /// Hold the spool at `address`, given the `(tape number, value)` pairs
+    /// it packed, in the order `SpoolPack` added them.
+    pub fn insert_spool(&mut self, address: &Pubkey, packed: &[(u64, [u8; 32])]) {
+        let mut spool = SpoolTree {
+            tree: FullTree::new(&[address.as_ref()]),
+            tapes: Vec::new(),
+        };
+        for (tape, value) in packed {
+            spool.tree.push(compute_tape_leaf(*tape, value).to_bytes());
+            spool.tapes.push(Some(*tape));
+        }
+        self.spools.insert(*address, spool);
+    }
+
+    /// Mirror a `SpoolPack` of `tape` with `value`.
+    pub fn pack(&mut self, spool: &Pubkey, tape: u64, value: &[u8; 32]) {
+        if let Some(spool) = self.spools.get_mut(spool) {
+            spool.tree.push(compute_tape_leaf(tape, value).to_bytes());
+            spool.tapes.push(Some(tape));
+        }
+    }
+
+    /// Mirror a `SpoolUnpack` of `tape`: its leaf goes back to empty.
+    pub fn unpack(&mut self, spool: &Pubkey, tape: u64) {
+        let Some(spool) = self.spools.get_mut(spool) else {
+            return;
+        };
+        if let Some(index) = spool.tapes.iter().position(|t| *t == Some(tape)) {
+            let empty = spool.tree.empty_leaf();
+            spool.tree.set(index, empty);
+            spool.tapes[index] = None;
+        }
+    }
+
+    /// Root of the spool at `address`; matches `Spool.state`'s root.
+    pub fn spool_root(&self, spool: &Pubkey) -> Option<[u8; 32]> {
+        Some(self.spools.get(spool)?.tree.root())
+    }
+
+    /// Proof that `tape` is packed in `spool`, as `SpoolCommit` and
+    /// `SpoolUnpack` expect it.
+    pub fn tape_proof(&self, spool: &Pubkey, tape: u64) -> Option<[[u8; 32]; TAPE_PROOF_LEN]> {
+        let spool = self.spools.get(spool)?;
+        let index = spool.tapes.iter().position(|t| *t == Some(tape))?;
+        Some(spool.tree.proof(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{SegmentTree, TapeTree};
+    use proptest::prelude::*;
+    use tape_api::utils::{update_segment, verify_segment, write_segment};
+
+    fn segments(count: usize) -> Vec<[u8; SEGMENT_SIZE]> {
+        (0..count)
+            .map(|i| [(i % 251) as u8 + 1; SEGMENT_SIZE])
+            .collect()
+    }
+
+    #[test]
+    fn segment_proofs_verify_on_chain() {
+        let address = Pubkey::new_unique();
+        let data = segments(21);
+
+        let mut tree = SegmentTree::new(&[address.as_ref()]);
+        for (i, segment) in data.iter().enumerate() {
+            write_segment(&mut tree, i as u64, segment).unwrap();
+        }
+
+        let mut builder = ProofBuilder::new();
+        builder.insert_tape(7, &address, &data);
+        let root = tree.get_root().to_bytes();
+        assert_eq!(builder.tape_root(7), Some(root));
+
+        for index in [0, 1, 10, 20] {
+            let proof = builder.segment_proof(7, index).unwrap();
+            assert!(verify_segment(&root, index, &data[index as usize], proof.as_array()));
+        }
+        assert!(builder.segment_proof(7, 21).is_none());
+        assert!(builder.segment_proof(8, 0).is_none());
+    }
+
+    #[test]
+    fn updates_keep_proofs_valid() {
+        let address = Pubkey::new_unique();
+        let mut data = segments(9);
+
+        let mut tree = SegmentTree::new(&[address.as_ref()]);
+        for (i, segment) in data.iter().enumerate() {
+            write_segment(&mut tree, i as u64, segment).unwrap();
+        }
+        let mut builder = ProofBuilder::new();
+        builder.insert_tape(1, &address, &data);
+
+        let new = [0xAB; SEGMENT_SIZE];
+        let proof = builder.segment_proof(1, 4).unwrap();
+        update_segment(&mut tree, 4, &data[4], &new, proof.as_array()).unwrap();
+        builder.update_segment(1, 4, &new);
+        data[4] = new;
+
+        let root = tree.get_root().to_bytes();
+        assert_eq!(builder.tape_root(1), Some(root));
+        for index in 0..9 {
+            let proof = builder.segment_proof(1, index).unwrap();
+            assert!(verify_segment(&root, index, &data[index as usize], proof.as_array()));
+        }
+    }
+
+    #[test]
+    fn tape_proofs_verify_on_chain() {
+        let spool = Pubkey::new_unique();
+        let packed: Vec<_> = (0..5u64).map(|t| (t * 3 + 1, [t as u8 + 9; 32])).collect();
+
+        let mut state = TapeTree::new(&[spool.as_ref()]);
+        for (tape, value) in &packed {
+            state.try_add_leaf(compute_tape_leaf(*tape, value)).unwrap();
+        }
+
+        let mut builder = ProofBuilder::new();
+        builder.insert_spool(&spool, &packed);
+        assert_eq!(builder.spool_root(&spool), Some(state.get_root().to_bytes()));
+
+        for (tape, value) in &packed {
+            let proof = builder.tape_proof(&spool, *tape).unwrap();
+            assert!(state.contains_leaf(&proof, compute_tape_leaf(*tape, value)));
+        }
+        assert!(builder.tape_proof(&spool, 2).is_none());
+
+        // Unpack one the way `SpoolUnpack` does, then pack another.
+        let (tape, value) = packed[2];
+        let proof = builder.tape_proof(&spool, tape).unwrap();
+        state.try_remove_leaf(&proof, compute_tape_leaf(tape, &value)).unwrap();
+        builder.unpack(&spool, tape);
+        assert_eq!(builder.spool_root(&spool), Some(state.get_root().to_bytes()));
+        assert!(builder.tape_proof(&spool, tape).is_none());
+
+        state.try_add_leaf(compute_tape_leaf(100, &[1; 32])).unwrap();
+        builder.pack(&spool, 100, &[1; 32]);
+        assert_eq!(builder.spool_root(&spool), Some(state.get_root().to_bytes()));
+
+        let (tape, value) = packed[4];
+        let proof = builder.tape_proof(&spool, tape).unwrap();
+        assert!(state.contains_leaf(&proof, compute_tape_leaf(tape, &value)));
+    }
+
+    #[test]
+    fn levels_round_trip() {
+        let seeds: &[&[u8]] = &[b"levels"];
+        let leaves = (0..11u8).map(|i| [i; 32]);
+        let tree = FullTree::<SEGMENT_TREE_HEIGHT>::from_leaves(seeds, leaves);
+        assert!(tree.is_consistent());
+
+        let restored = FullTree::<SEGMENT_TREE_HEIGHT>::from_levels(seeds, tree.levels().to_vec())
+            .unwrap();
+        assert_eq!(restored.root(), tree.root());
+        assert_eq!(restored.proof(7), tree.proof(7));
+
+        let mut levels = tree.levels().to_vec();
+        levels[3].pop();
+        assert!(FullTree::<SEGMENT_TREE_HEIGHT>::from_levels(seeds, levels.clone()).is_none());
+
+        levels = tree.levels().to_vec();
+        levels[2][0][0] ^= 1;
+        let damaged = FullTree::<SEGMENT_TREE_HEIGHT>::from_levels(seeds, levels).unwrap();
+        assert!(!damaged.is_consistent());
+    }
+
+    fn segment() -> impl Strategy<Value = [u8; SEGMENT_SIZE]> {
+        prop::collection::vec(any::<u8>(), SEGMENT_SIZE).prop_map(|b| b.try_into().unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn builder_follows_random_updates(
+            mut data in prop::collection::vec(segment(), 1..48),
+            updates in prop::collection::vec((any::<usize>(), segment()), 0..16),
+        ) {
+            let address = Pubkey::new_unique();
+            let mut tree = SegmentTree::new(&[address.as_ref()]);
+            for (i, segment) in data.iter().enumerate() {
+                write_segment(&mut tree, i as u64, segment).unwrap();
+            }
+            let mut builder = ProofBuilder::new();
+            builder.insert_tape(1, &address, &data);
+
+            for (pick, new) in updates {
+                let index = pick % data.len();
+                let proof = builder.segment_proof(1, index as u64).unwrap();
+                update_segment(&mut tree, index as u64, &data[index], &new, proof.as_array()).unwrap();
+                builder.update_segment(1, index as u64, &new);
+                data[index] = new;
+                prop_assert_eq!(builder.tape_root(1), Some(tree.get_root().to_bytes()));
+            }
+
+            let root = tree.get_root().to_bytes();
+            for (index, segment) in data.iter().enumerate() {
+                let proof = builder.segment_proof(1, index as u64).unwrap();
+                prop_assert!(verify_segment(&root, index as u64, segment, proof.as_array()));
+            }
+        }
+    }
+}
@@ -0,0 +1,527 @@
+//! Reading a tape back from the ledger.
+//!
+//! Segment bytes are never stored in an account; they only exist in the
+//! instruction data of the transactions that wrote them. Every write and
+//! update moves `Tape.tail_slot` forward and emits an event carrying the
+//! previous tail, so the tape's history is a linked list of slots running
+//! from `tail_slot` back to `first_slot`. `read_tape` walks that list through
+//! a `LedgerSource`, replays the instructions it finds in ledger order,
+//! checks the rebuilt segments against `merkle_root` and undoes whatever
+//! `encoder::encode` did to the original bytes.
+
+use std::collections::BTreeMap;
+
+use bytemuck::Pod;
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use tape_api::event::{EventType, UpdateEvent, WriteEvent};
+use tape_api::utils::compute_leaf;
+
+use crate::accounts::Tape;
+use crate::consts::*;
+use crate::encoder::{TapeHeader, TAPE_HEADER_VERSION};
+use crate::instructions::*;
+use crate::types::SegmentTree;
+use crate::TAPE_PROGRAM_ID;
+
+/// A successful transaction, as far as the reader cares.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LedgerTransaction {
+    /// Top-level instructions, in order.
+    pub instructions: Vec<Instruction>,
+    /// Events the tape program emitted, in order, each as
+    /// `[discriminator, payload]`. That is the `sol_log_data` record for the
+    /// log channel, or the `EmitEvent` instruction data after its first byte
+    /// for the CPI channel.
+    pub events: Vec<Vec<u8>>,
+}
+
+/// Where the reader gets transactions from: an RPC node, a local dump of
+/// blocks, or anything else that can list a slot.
+pub trait LedgerSource {
+    type Error;
+
+    /// The successful transactions in `slot`, in ledger order. Sources may
+    /// leave out transactions that do not invoke the tape program.
+    fn transactions(&self, slot: u64) -> Result<Vec<LedgerTransaction>, Self::Error>;
+}
+
+/// A `LedgerSource` held in memory, for tests and for dumps loaded up front.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryLedger {
+    slots: BTreeMap<u64, Vec<LedgerTransaction>>,
+}
+
+impl MemoryLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `transaction` to the end of `slot`.
+    pub fn push(&mut self, slot: u64, transaction: LedgerTransaction) {
+        self.slots.entry(slot).or_default().push(transaction);
+    }
+}
+
+impl LedgerSource for MemoryLedger {
+    type Error = core::convert::Infallible;
+
+    fn transactions(&self, slot: u64) -> Result<Vec<LedgerTransaction>, Self::Error> {
+        Ok(self.slots.get(&slot).cloned().unwrap_or_default())
+    }
+}
+
+#[derive(Debug)]
+pub enum ReadError<E> {
+    Ledger(E),
+    /// `slot` is on the tape's chain but holds none of its events.
+    MissingSlot { slot: u64 },
+    /// An event in `slot` points forward, or before the tape existed.
+    BrokenChain { slot: u64 },
+    /// A tape instruction in `slot` could not be parsed or paired with its
+    /// event.
+    MalformedInstruction { slot: u64 },
+    /// An update in `slot` does not start from the segment's current bytes.
+    UpdateMismatch { slot: u64, segment: u64 },
+    /// No write for `segment` was found on the chain.
+    MissingSegment { segment: u64 },
+    /// The rebuilt segments do not hash to the tape's `merkle_root`.
+    RootMismatch,
+    /// The header names a compression this crate does not know.
+    UnknownCompression(u64),
+    /// The payload does not decompress.
+    Decompress(std::io::Error),
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for ReadError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Ledger(e) => write!(f, "ledger: {e}"),
+            Self::MissingSlot { slot } => write!(f, "slot {slot} has no events for the tape"),
+            Self::BrokenChain { slot } => write!(f, "slot chain breaks at slot {slot}"),
+            Self::MalformedInstruction { slot } => {
+                write!(f, "malformed tape instruction in slot {slot}")
+            }
+            Self::UpdateMismatch { slot, segment } => {
+                write!(f, "update of segment {segment} in slot {slot} does not match its data")
+            }
+            Self::MissingSegment { segment } => write!(f, "segment {segment} was never written"),
+            Self::RootMismatch => write!(f, "rebuilt segments do not match the tape's root"),
+            Self::UnknownCompression(c) => write!(f, "unknown compression {c}"),
+            Self::Decompress(e) => write!(f, "decompress: {e}"),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> std::error::Error for ReadError<E> {}
+
+/// Read the tape at `address` back into the bytes that were encoded.
+///
+/// Tapes whose header was not written by `encoder::encode` come back as the
+/// raw concatenated segments.
+pub fn read_tape<L: LedgerSource>(
+    ledger: &L,
+    address: &Pubkey,
+    tape: &Tape,
+) -> Result<Vec<u8>, ReadError<L::Error>> {
+    let segments = read_segments(ledger, address, tape)?;
+    let data: &[u8] = bytemuck::cast_slice(&segments);
+
+    let header = TapeHeader::from_bytes(&tape.header);
+    if header.version != TAPE_HEADER_VERSION || header.data_len > data.len() as u64 {
+        return Ok(data.to_vec());
+    }
+
+    let compression = header
+        .compression()
+        .ok_or(ReadError::UnknownCompression(header.compression))?;
+    compression
+        .decompress(&data[..header.data_len as usize])
+        .map_err(ReadError::Decompress)
+}
+
+/// Rebuild the tape's segments from the ledger and check them against its
+/// `merkle_root`.
+pub fn read_segments<L: LedgerSource>(
+    ledger: &L,
+    address: &Pubkey,
+    tape: &Tape,
+) -> Result<Vec<[u8; SEGMENT_SIZE]>, ReadError<L::Error>> {
+    let chain = walk_chain(ledger, address, tape)?;
+
+    let mut segments: Vec<Option<[u8; SEGMENT_SIZE]>> = vec![None; tape.total_segments as usize];
+    for (slot, transactions) in chain.iter().rev() {
+        for transaction in transactions {
+            replay(*slot, address, transaction, &mut segments)?;
+        }
+    }
+
+    let segments = segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| s.ok_or(ReadError::MissingSegment { segment: i as u64 }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut tree = SegmentTree::new(&[address.as_ref()]);
+    for (i, segment) in segments.iter().enumerate() {
+        tree.try_add_leaf(compute_leaf(i as u64, segment))
+            .map_err(|_| ReadError::RootMismatch)?;
+    }
+    if tree.get_root().to_bytes() != tape.merkle_root {
+        return Err(ReadError::RootMismatch);
+    }
+
+    Ok(segments)
+}
+
+/// The transactions touching the tape, newest slot first.
+fn walk_chain<L: LedgerSource>(
+    ledger: &L,
+    address: &Pubkey,
+    tape: &Tape,
+) -> Result<Vec<(u64, Vec<LedgerTransaction>)>, ReadError<L::Error>> {
+    let mut chain = Vec::new();
+    let mut slot = tape.tail_slot;
+
+    loop {
+        let transactions: Vec<LedgerTransaction> = ledger
+            .transactions(slot)
+            .map_err(ReadError::Ledger)?
+            .into_iter()
+            .filter(|tx| tx.events.iter().any(|e| prev_slot(e, address).is_some()))
+            .collect();
+
+        // (several writes can land in one slot; the earliest points back)
+        let prev = transactions
+            .iter()
+            .flat_map(|tx| tx.events.iter().filter_map(|e| prev_slot(e, address)))
+            .min();
+
+        let prev = match prev {
+            Some(prev) => prev,
+            None if slot == tape.first_slot => break,
+            None => return Err(ReadError::MissingSlot { slot }),
+        };
+
+        if prev > slot || prev < tape.first_slot {
+            return Err(ReadError::BrokenChain { slot });
+        }
+
+        chain.push((slot, transactions));
+        if prev == slot {
+            break;
+        }
+        slot = prev;
+    }
+
+    Ok(chain)
+}
+
+/// `prev_slot` of a write or update event for the tape at `address`.
+fn prev_slot(event: &[u8], address: &Pubkey) -> Option<u64> {
+    if let Some(event) = decode_event::<WriteEvent>(EventType::WriteEvent, event) {
+        return (event.address == address.to_bytes()).then_some(event.prev_slot);
+    }
+    if let Some(event) = decode_event::<UpdateEvent>(EventType::UpdateEvent, event) {
+        return (event.address == address.to_bytes()).then_some(event.prev_slot);
+    }
+    None
+}
+
+fn decode_event<T: Pod>(kind: EventType, event: &[u8]) -> Option<T> {
+    match event.split_first() {
+        Some((&discriminator, payload)) if discriminator == kind as u8 => {
+            bytemuck::try_pod_read_unaligned(payload).ok()
+        }
+        _ => None,
+    }
+}
+
+fn read_pod<T: Pod>(data: &[u8]) -> Option<(T, &[u8])> {
+    let len = core::mem::size_of::<T>();
+    if data.len() < len {
+        return None;
+    }
+    let (head, tail) = data.split_at(len);
+    Some((bytemuck::pod_read_unaligned(head), tail))
+}
+
+/// Apply the tape's writes and updates in `transaction` to `segments`.
+fn replay<E>(
+    slot: u64,
+    address: &Pubkey,
+    transaction: &LedgerTransaction,
+    segments: &mut [Option<[u8; SEGMENT_SIZE]>],
+) -> Result<(), ReadError<E>> {
+    let malformed = || ReadError::MalformedInstruction { slot };
+
+    // Plain writes only learn where they landed from their event.
+    let mut writes = transaction
+        .events
+        .iter()
+        .filter_map(|e| decode_event::<WriteEvent>(EventType::WriteEvent, e))
+        .filter(|e| e.address == address.to_bytes());
+
+    let tape_ixs = transaction.instructions.iter().filter(|ix| {
+        ix.program_id == TAPE_PROGRAM_ID
+            && ix.accounts.get(1).is_some_and(|meta| meta.pubkey == *address)
+    });
+
+    for ix in tape_ixs {
+        let Some((&discriminator, data)) = ix.data.split_first() else {
+            continue;
+        };
+
+        match TapeInstruction::try_from(&discriminator) {
+            Ok(TapeInstruction::TapeWrite) => {
+                let event = writes.next().ok_or_else(malformed)?;
+                put(segments, event.segment_number, data).ok_or_else(malformed)?;
+            }
+            Ok(TapeInstruction::TapeWriteAt) => {
+                let (args, data) = read_pod::<WriteTapeAtIxData>(data).ok_or_else(malformed)?;
+                writes.next().ok_or_else(malformed)?;
+                put(segments, args.segment_number, data).ok_or_else(malformed)?;
+            }
+            Ok(TapeInstruction::TapeUpdate) => {
+                let (args, _proof) = read_pod::<UpdateTapeIxData>(data).ok_or_else(malformed)?;
+                update(segments, slot, args.segment_number, &args.old_data, args.new_data)?;
+            }
+            Ok(TapeInstruction::TapeUpdateBatch) => {
+                let (args, mut data) =
+                    read_pod::<UpdateTapeBatchIxData>(data).ok_or_else(malformed)?;
+                for _ in 0..args.count {
+                    let (u, rest) = read_pod::<SegmentUpdate>(data).ok_or_else(malformed)?;
+                    update(segments, slot, u.segment_number, &u.old_data, u.new_data)?;
+                    data = rest;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Place `data`, split and padded as `TapeWrite` does, from `first` on.
+fn put(segments: &mut [Option<[u8; SEGMENT_SIZE]>], first: u64, data: &[u8]) -> Option<()> {
+    for (i, chunk) in data.chunks(SEGMENT_SIZE).enumerate() {
+        let slot = segments.get_mut(first as usize + i)?;
+        let mut segment = [0u8; SEGMENT_SIZE];
+        segment[..chunk.len()].copy_from_slice(chunk);
+        *slot = Some(segment);
+    }
+    Some(())
+}
+
+fn update<E>(
+    segments: &mut [Option<[u8; SEGMENT_SIZE]>],
+    slot: u64,
+    segment: u64,
+    old_data: &[u8; SEGMENT_SIZE],
+    new_data: [u8; SEGMENT_SIZE],
+) -> Result<(), ReadError<E>> {
+    match segments.get_mut(segment as usize) {
+        Some(current) if current.as_ref() == Some(old_data) => {
+            *current = Some(new_data);
+            Ok(())
+        }
+        _ => Err(ReadError::UpdateMismatch { slot, segment }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::TapeAccount;
+    use crate::encoder::{encode, Compression};
+    use crate::pda::tape_pda;
+    use crate::types::to_name;
+    use bytemuck::Zeroable;
+
+    fn event<T: Pod>(kind: EventType, event: &T) -> Vec<u8> {
+        let mut out = vec![kind as u8];
+        out.extend_from_slice(bytemuck::bytes_of(event));
+        out
+    }
+
+    fn write_tx(
+        ix: Instruction,
+        tape: &Pubkey,
+        first: u64,
+        count: u64,
+        prev: u64,
+    ) -> LedgerTransaction {
+        LedgerTransaction {
+            instructions: vec![ix],
+            events: vec![event(
+                EventType::WriteEvent,
+                &WriteEvent {
+                    num_added: count,
+                    num_total: first + count,
+                    prev_slot: prev,
+                    address: tape.to_bytes(),
+                    segment_number: first,
+                },
+            )],
+        }
+    }
+
+    /// A tape written in three slots: two writes in slot 11 (one of them
+    /// out of order), one in slot 14, then two updates in slot 20 that change
+    /// segment 1 and change it back.
+    fn written_tape(data: &[u8]) -> (Pubkey, Tape, MemoryLedger) {
+        let signer = Pubkey::new_unique();
+        let (address, _bump) = tape_pda(signer, &to_name("reader"));
+        let encoded = encode(&address, data, Compression::Deflate).unwrap();
+        let bytes = encoded.data();
+        let n = encoded.segments.len() as u64;
+        assert!(n >= 4);
+
+        let mut ledger = MemoryLedger::new();
+        let seg = |i: u64| &bytes[i as usize * SEGMENT_SIZE..(i as usize + 1) * SEGMENT_SIZE];
+
+        ledger.push(10, LedgerTransaction::default());
+        ledger.push(
+            11,
+            write_tx(build_write_at_ix(signer, address, 1, seg(1)), &address, 1, 1, 10),
+        );
+        ledger.push(
+            11,
+            write_tx(build_write_ix(signer, address, seg(0)), &address, 0, 1, 11),
+        );
+        // Unrelated traffic in a slot on the chain.
+        ledger.push(
+            14,
+            write_tx(
+                build_write_ix(signer, Pubkey::new_unique(), &[1; SEGMENT_SIZE]),
+                &Pubkey::new_unique(),
+                0,
+                1,
+                3,
+            ),
+        );
+        ledger.push(
+            14,
+            write_tx(
+                build_write_ix(signer, address, &bytes[2 * SEGMENT_SIZE..]),
+                &address,
+                2,
+                n - 2,
+                11,
+            ),
+        );
+
+        let mut final_segments = encoded.segments.clone();
+        final_segments[1] = [0xab; SEGMENT_SIZE];
+        let update = build_update_ix(
+            signer,
+            address,
+            1,
+            encoded.segments[1],
+            final_segments[1],
+            &[],
+        );
+        ledger.push(
+            20,
+            LedgerTransaction {
+                instructions: vec![update],
+                events: vec![event(
+                    EventType::UpdateEvent,
+                    &UpdateEvent {
+                        segment_number: 1,
+                        prev_slot: 14,
+                        address: address.to_bytes(),
+                    },
+                )],
+            },
+        );
+
+        let revert = build_update_ix(
+            signer,
+            address,
+            1,
+            final_segments[1],
+            encoded.segments[1],
+            &[],
+        );
+        ledger.push(
+            20,
+            LedgerTransaction {
+                instructions: vec![revert],
+                events: vec![event(
+                    EventType::UpdateEvent,
+                    &UpdateEvent {
+                        segment_number: 1,
+                        prev_slot: 20,
+                        address: address.to_bytes(),
+                    },
+                )],
+            },
+        );
+
+        let mut tape = Tape::zeroed();
+        tape.authority = signer.to_bytes();
+        tape.merkle_root = encoded.root;
+        tape.header = encoded.header.to_bytes();
+        tape.first_slot = 10;
+        tape.tail_slot = 20;
+        tape.total_segments = n;
+
+        (address, tape, ledger)
+    }
+
+    fn sample() -> Vec<u8> {
+        let mut state = 7u32;
+        (0..4000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_back_the_encoded_file() {
+        let data = sample();
+        let (address, tape, ledger) = written_tape(&data);
+        assert_eq!(read_tape(&ledger, &address, &tape).unwrap(), data);
+    }
+
+    #[test]
+    fn tape_decodes_from_account_bytes() {
+        let data = sample();
+        let (address, tape, ledger) = written_tape(&data);
+        let tape = Tape::decode(bytemuck::bytes_of(&tape)).unwrap();
+        assert_eq!(read_tape(&ledger, &address, &tape).unwrap(), data);
+    }
+
+    #[test]
+    fn wrong_root_is_rejected() {
+        let (address, mut tape, ledger) = written_tape(&sample());
+        tape.merkle_root[0] ^= 1;
+        assert!(matches!(
+            read_tape(&ledger, &address, &tape),
+            Err(ReadError::RootMismatch)
+        ));
+    }
+
+    #[test]
+    fn missing_slot_is_reported() {
+        let (address, mut tape, ledger) = written_tape(&sample());
+        tape.tail_slot = 21;
+        assert!(matches!(
+            read_tape(&ledger, &address, &tape),
+            Err(ReadError::MissingSlot { slot: 21 })
+        ));
+    }
+
+    #[test]
+    fn chain_must_stay_after_first_slot() {
+        let (address, mut tape, ledger) = written_tape(&sample());
+        tape.first_slot = 12;
+        assert!(matches!(
+            read_tape(&ledger, &address, &tape),
+            Err(ReadError::BrokenChain { slot: 14 })
+        ));
+    }
+}
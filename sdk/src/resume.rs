@@ -0,0 +1,321 @@
+//! Picking up an interrupted upload.
+//!
+//! The tape's `Writer` account is the checkpoint: its `SegmentTree` commits
+//! to every segment appended so far and its out-of-order slots hold the
+//! leaves of segments written ahead. `resume_point` checks both against the
+//! local segments, so an upload can carry on from where the chain says it
+//! stopped instead of from where the uploader thinks it stopped.
+
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+use tape_api::utils::compute_leaf;
+
+use crate::accounts::{Tape, Writer};
+use crate::consts::*;
+use crate::instructions::build_write_at_ix;
+use crate::plan::segments_per_write;
+use crate::types::SegmentTree;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResumeError {
+    /// The writer account belongs to another tape.
+    WrongWriter,
+    /// The tape already holds more segments than there are locally.
+    TapeLonger { total_segments: u64 },
+    /// The first segment that differs from the chain lies in
+    /// `first..first + len`. `len` is 1 when the chain pins it down exactly;
+    /// otherwise the writer only commits to the range as a whole.
+    Diverged { first: u64, len: u64 },
+}
+
+impl core::fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::WrongWriter => write!(f, "writer account belongs to another tape"),
+            Self::TapeLonger { total_segments } => {
+                write!(f, "tape already holds {total_segments} segments, more than the input")
+            }
+            Self::Diverged { first, len: 1 } => write!(f, "segment {first} differs on chain"),
+            Self::Diverged { first, len } => write!(
+                f,
+                "a segment in {first}..{} differs on chain",
+                first + len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+/// Where an upload stands on chain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResumePoint {
+    /// Segments appended to the tape; the upload continues from here.
+    pub next_segment: u64,
+    /// Segments past `next_segment` already waiting in the writer's slots.
+    pub pending: Vec<u64>,
+}
+
+impl ResumePoint {
+    /// Whether every one of `total` segments is on chain or pending.
+    pub fn is_complete(&self, total: u64) -> bool {
+        self.next_segment + self.pending.len() as u64 >= total
+    }
+
+    /// `TapeWriteAt`s for the segments still missing, in order.
+    ///
+    /// Out-of-order writes are used throughout so that the pending segments
+    /// can be skipped; each write also drains the slots behind it, which
+    /// keeps the rest within `WRITE_WINDOW`.
+    pub fn remaining_writes(
+        &self,
+        signer: Pubkey,
+        tape: Pubkey,
+        segments: &[[u8; SEGMENT_SIZE]],
+    ) -> Vec<Instruction> {
+        // (one segment fewer than a plain write, to make room for the offset)
+        let per_write = segments_per_write(signer, tape).saturating_sub(1).max(1);
+
+        let mut ixs = Vec::new();
+        let mut number = self.next_segment as usize;
+        while number < segments.len() {
+            if self.pending.contains(&(number as u64)) {
+                number += 1;
+                continue;
+            }
+
+            let run = segments[number..]
+                .iter()
+                .enumerate()
+                .take(per_write)
+                .take_while(|(i, _)| !self.pending.contains(&((number + i) as u64)))
+                .count();
+
+            let data: &[u8] = bytemuck::cast_slice(&segments[number..number + run]);
+            ixs.push(build_write_at_ix(signer, tape, number as u64, data));
+            number += run;
+        }
+
+        ixs
+    }
+}
+
+/// Check the tape at `address` against `segments` and find where to carry
+/// on. `writer` and `slots` come from `decode_writer_with_slots`.
+pub fn resume_point(
+    address: &Pubkey,
+    tape: &Tape,
+    writer: &Writer,
+    slots: &[[u8; 32]],
+    segments: &[[u8; SEGMENT_SIZE]],
+) -> Result<ResumePoint, ResumeError> {
+    if writer.tape != address.to_bytes() {
+        return Err(ResumeError::WrongWriter);
+    }
+
+    let total = tape.total_segments;
+    if total > segments.len() as u64 {
+        return Err(ResumeError::TapeLonger {
+            total_segments: total,
+        });
+    }
+
+    let mut local = SegmentTree::new(&[address.as_ref()]);
+    for (i, segment) in segments[..total as usize].iter().enumerate() {
+        local
+            .try_add_leaf(compute_leaf(i as u64, segment))
+            .expect("tape length is bounded by the tree");
+    }
+
+    if local.get_root().to_bytes() != writer.state.get_root().to_bytes() {
+        return Err(first_divergence(total, &local, &writer.state));
+    }
+
+    let mut pending = Vec::new();
+    for (offset, slot) in slots.iter().enumerate() {
+        if *slot == [0; 32] {
+            continue;
+        }
+
+        // Slots are indexed by segment number modulo the window.
+        let window = WRITE_WINDOW as u64;
+        let number = total + (offset as u64 + window - total % window) % window;
+
+        let matches = segments
+            .get(number as usize)
+            .is_some_and(|segment| compute_leaf(number, segment).to_bytes() == *slot);
+        if !matches {
+            return Err(ResumeError::Diverged {
+                first: number,
+                len: 1,
+            });
+        }
+        pending.push(number);
+    }
+    pending.sort_unstable();
+
+    Ok(ResumePoint {
+        next_segment: total,
+        pending,
+    })
+}
+
+/// Narrow a root mismatch down using the trees' filled subtrees.
+///
+/// After `total` appends, each set bit `l` of `total` stands for a complete
+/// subtree of `2^l` segments whose root is `filled_subtrees[l]`; together
+/// they cover `0..total`, largest first.
+fn first_divergence(total: u64, local: &SegmentTree, chain: &SegmentTree) -> ResumeError {
+    let mut first = 0;
+    for level in (0..SEGMENT_TREE_HEIGHT).rev() {
+        let len = 1u64 << level;
+        if total & len == 0 {
+            continue;
+        }
+        if local.filled_subtrees[level].to_bytes() != chain.filled_subtrees[level].to_bytes() {
+            return ResumeError::Diverged { first, len };
+        }
+        first += len;
+    }
+
+    ResumeError::Diverged {
+        first: 0,
+        len: total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{encode, Compression};
+    use crate::instructions::{TapeInstruction, WriteTapeAtIxData};
+    use bytemuck::Zeroable;
+
+    fn segments(count: usize) -> Vec<[u8; SEGMENT_SIZE]> {
+        (0..count).map(|i| [(i % 251) as u8 + 1; SEGMENT_SIZE]).collect()
+    }
+
+    /// The tape and writer after `written` in-order segments and the given
+    /// out-of-order ones.
+    fn chain_state(
+        address: &Pubkey,
+        segments: &[[u8; SEGMENT_SIZE]],
+        written: usize,
+        ahead: &[u64],
+    ) -> (Tape, Writer, Vec<[u8; 32]>) {
+        let data: &[u8] = bytemuck::cast_slice(&segments[..written]);
+        let encoded = encode(address, data, Compression::None).unwrap();
+
+        let mut writer = Writer::zeroed();
+        writer.tape = address.to_bytes();
+        writer.state = SegmentTree::new(&[address.as_ref()]);
+        for (i, segment) in segments[..written].iter().enumerate() {
+            writer.state.try_add_leaf(compute_leaf(i as u64, segment)).unwrap();
+        }
+        assert_eq!(writer.state.get_root().to_bytes(), encoded.root);
+
+        let mut slots = vec![[0; 32]; WRITE_WINDOW];
+        for &n in ahead {
+            slots[n as usize % WRITE_WINDOW] = compute_leaf(n, &segments[n as usize]).to_bytes();
+        }
+
+        let mut tape = Tape::zeroed();
+        tape.total_segments = written as u64;
+        tape.merkle_root = encoded.root;
+
+        (tape, writer, slots)
+    }
+
+    #[test]
+    fn resumes_after_the_last_segment() {
+        let address = Pubkey::new_unique();
+        let local = segments(40);
+        let (tape, writer, slots) = chain_state(&address, &local, 13, &[]);
+
+        let point = resume_point(&address, &tape, &writer, &slots, &local).unwrap();
+        assert_eq!(
+            point,
+            ResumePoint {
+                next_segment: 13,
+                pending: vec![],
+            }
+        );
+        assert!(!point.is_complete(40));
+    }
+
+    #[test]
+    fn finds_the_diverging_segment() {
+        let address = Pubkey::new_unique();
+        let local = segments(40);
+        let (tape, writer, slots) = chain_state(&address, &local, 13, &[]);
+
+        // 13 = 8 + 4 + 1: segment 12 is a subtree of its own.
+        let mut changed = local.clone();
+        changed[12][0] ^= 1;
+        assert_eq!(
+            resume_point(&address, &tape, &writer, &slots, &changed),
+            Err(ResumeError::Diverged { first: 12, len: 1 })
+        );
+
+        let mut changed = local.clone();
+        changed[9][0] ^= 1;
+        assert_eq!(
+            resume_point(&address, &tape, &writer, &slots, &changed),
+            Err(ResumeError::Diverged { first: 8, len: 4 })
+        );
+    }
+
+    #[test]
+    fn pending_segments_are_skipped() {
+        let address = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let local = segments(300);
+
+        // 258 wraps past the window and shares a slot index with 2.
+        let (tape, writer, slots) = chain_state(&address, &local, 10, &[12, 13, 258]);
+        let point = resume_point(&address, &tape, &writer, &slots, &local).unwrap();
+        assert_eq!(point.pending, vec![12, 13, 258]);
+
+        let mut covered = Vec::new();
+        for ix in point.remaining_writes(signer, address, &local) {
+            assert_eq!(ix.data[0], TapeInstruction::TapeWriteAt as u8);
+            let start = 1 + core::mem::size_of::<WriteTapeAtIxData>();
+            let args: WriteTapeAtIxData = bytemuck::pod_read_unaligned(&ix.data[1..start]);
+            let data = &ix.data[start..];
+            for (i, segment) in data.chunks(SEGMENT_SIZE).enumerate() {
+                let number = args.segment_number as usize + i;
+                assert_eq!(segment, local[number]);
+                covered.push(number as u64);
+            }
+        }
+
+        let expected: Vec<u64> = (10..300).filter(|n| ![12, 13, 258].contains(n)).collect();
+        assert_eq!(covered, expected);
+    }
+
+    #[test]
+    fn mismatched_slot_is_reported() {
+        let address = Pubkey::new_unique();
+        let local = segments(40);
+        let (tape, writer, slots) = chain_state(&address, &local, 10, &[15]);
+
+        let mut changed = local.clone();
+        changed[15][3] ^= 1;
+        assert_eq!(
+            resume_point(&address, &tape, &writer, &slots, &changed),
+            Err(ResumeError::Diverged { first: 15, len: 1 })
+        );
+    }
+
+    #[test]
+    fn longer_tape_is_rejected() {
+        let address = Pubkey::new_unique();
+        let local = segments(20);
+        let (tape, writer, slots) = chain_state(&address, &local, 20, &[]);
+
+        assert_eq!(
+            resume_point(&address, &tape, &writer, &slots, &local[..10]),
+            Err(ResumeError::TapeLonger { total_segments: 20 })
+        );
+    }
+}
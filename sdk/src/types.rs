@@ -0,0 +1,3 @@
+pub use tape_api::state::TapeState;
+pub use tape_api::types::{PoA, PoW, ProofPath, SegmentTree, TapeTree};
+pub use tape_api::utils::{from_name, to_name};
@@ -0,0 +1,144 @@
+//! Runs the instruction builders against the compiled program.
+//!
+//! Needs the program's ELF; build it first with
+//! `cargo build-sbf --manifest-path program/Cargo.toml`.
+
+#[path = "../../program/tests/support/mod.rs"]
+mod support;
+
+use bytemuck::Zeroable;
+use mollusk_svm::result::{InstructionResult, ProgramResult};
+use solana_sdk::program_error::ProgramError;
+use support::*;
+use tape_sdk::accounts::{decode_tape_with_nodes, Archive};
+use tape_sdk::consts::*;
+use tape_sdk::errors::TapeError;
+use tape_sdk::instructions::*;
+use tape_sdk::pda::*;
+use tape_sdk::types::*;
+use tape_sdk::Pubkey;
+
+/// The processor got past destructuring its accounts and checking the
+/// signer; whatever failed after that is about account contents.
+fn assert_layout_accepted(result: &InstructionResult) {
+    match &result.program_result {
+        ProgramResult::Failure(ProgramError::NotEnoughAccountKeys)
+        | ProgramResult::Failure(ProgramError::MissingRequiredSignature) => {
+            panic!("account layout rejected: {:?}", result.program_result)
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn tape_lifecycle() {
+    let mut h = Harness::new();
+    let signer = Pubkey::new_unique();
+    h.fund(signer);
+
+    let name = to_name("lifecycle");
+    let (tape, _bump) = tape_pda(signer, &name);
+
+    h.run_ok(&build_create_tape_ix(signer, name, 2));
+
+    // Segments 0 and 1 in order, then 3 ahead of 2.
+    h.run_ok(&build_write_ix(signer, tape, &[1; 2 * SEGMENT_SIZE]));
+    h.run_ok(&build_write_at_ix(signer, tape, 3, &[3; SEGMENT_SIZE]));
+    h.run_ok(&build_write_at_ix(signer, tape, 2, &[2; SEGMENT_SIZE]));
+
+    let segments = [
+        [1; SEGMENT_SIZE],
+        [1; SEGMENT_SIZE],
+        [2; SEGMENT_SIZE],
+        [3; SEGMENT_SIZE],
+    ];
+    let mut tree = SegmentTree::new(&[tape.as_ref()]);
+    let leaves: Vec<_> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, s)| tape_api::utils::compute_leaf(i as u64, s))
+        .collect();
+    for leaf in &leaves {
+        tree.try_add_leaf(*leaf).unwrap();
+    }
+    let proof: Vec<[u8; 32]> = tree
+        .get_merkle_proof(&leaves, 1)
+        .iter()
+        .map(|hash| hash.to_bytes())
+        .collect();
+
+    h.run_ok(&build_update_ix(signer, tape, 1, segments[1], [9; SEGMENT_SIZE], &proof));
+    h.run_ok(&build_set_header_ix(signer, tape, [7; HEADER_SIZE]));
+
+    let (decoded, _nodes) = decode_tape_with_nodes(&h.get(&tape).data).unwrap();
+    assert_eq!(decoded.total_segments, 4);
+    assert_eq!(decoded.header, [7; HEADER_SIZE]);
+
+    // The tape has paid no rent yet, so finalize stops at the rent check,
+    // after every account it takes has been loaded.
+    h.set_state(archive_pda().0, &Archive::zeroed());
+    h.run_err(&build_finalize_ix(signer, tape), TapeError::InsufficientRent);
+}
+
+#[test]
+fn miner_and_spool_lifecycle() {
+    let mut h = Harness::new();
+    let signer = Pubkey::new_unique();
+    h.fund(signer);
+
+    let name = to_name("miner");
+    let (miner, _bump) = miner_pda(signer, name);
+    let (spool, _bump) = spool_pda(miner, 0);
+
+    h.run_ok(&build_register_ix(signer, name));
+    h.run_ok(&build_create_spool_ix(signer, miner, 0));
+    h.run_ok(&build_destroy_spool_ix(signer, spool));
+    h.run_ok(&build_unregister_ix(signer, miner));
+
+    assert_eq!(h.get(&miner).lamports, 0);
+    assert_eq!(h.get(&spool).lamports, 0);
+}
+
+#[test]
+fn remaining_builders_reach_their_processors() {
+    let mut h = Harness::new();
+    let signer = Pubkey::new_unique();
+    h.fund(signer);
+
+    let (miner, _bump) = miner_pda(signer, to_name("miner"));
+    let (tape, _bump) = tape_pda(signer, &to_name("tape"));
+    let (spool, _bump) = spool_pda(miner, 0);
+    let ata = tape_ata(signer).0;
+
+    // Initialize is not finished yet; only its account count is checked.
+    let ixs = [
+        build_initialize_ix(signer),
+        build_airdrop_ix(signer, ata, 1),
+        build_subsidize_ix(signer, ata, tape, 1),
+        build_mine_ix(signer, miner, tape, PoW::zeroed(), PoA::zeroed()),
+        build_claim_ix(signer, ata, miner, 1),
+        build_pack_ix(signer, spool, tape, [1; 32]),
+        build_unpack_ix(signer, spool, 0, [[0; 32]; TAPE_PROOF_LEN], [1; 32]),
+        build_commit_ix(signer, miner, spool, 0, [[0; 32]; TAPE_PROOF_LEN], [1; 32]),
+    ];
+
+    for ix in &ixs {
+        assert_layout_accepted(&h.run(ix));
+    }
+}
+
+#[test]
+fn dropping_a_signer_is_rejected() {
+    let mut h = Harness::new();
+    let signer = Pubkey::new_unique();
+    h.fund(signer);
+
+    let mut ix = build_create_tape_ix(signer, to_name("unsigned"), 0);
+    ix.accounts[0].is_signer = false;
+
+    let result = h.run(&ix);
+    assert_eq!(
+        result.program_result,
+        ProgramResult::Failure(ProgramError::MissingRequiredSignature)
+    );
+}